@@ -0,0 +1,227 @@
+use std::fmt::Write;
+
+use anstyle::{AnsiColor, Style};
+use corrozy_core::diagnostics::{Diagnostic, Label, Severity};
+use serde::Serialize;
+
+/// A line of `--message-format json`, told apart by its `type`:
+///
+/// ```text
+/// {"type":"diagnostic","severity":"error","code":"E0002","message":"Undefined variable `x`","file":"src/main.crz","span":{"start":6,"end":7,"line":1,"column":7},"labels":[...],"help":[]}
+/// {"type":"summary","success":false,"errors":1,"warnings":0}
+/// ```
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Message<'a> {
+    Diagnostic(&'a Diagnostic),
+    /// Last line, whether the project was transpiled
+    Summary { success: bool, errors: usize, warnings: usize },
+}
+
+impl Message<'_> {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("diagnostics always serialize")
+    }
+}
+
+/// Prints diagnostics with the lines of source they point to:
+///
+/// ```text
+/// error[E0004]: Cannot assign `string` to `x` of type `int`
+///  --> src/main.crz:3:1
+///   |
+/// 3 | let x: int = "a";
+///   | ^
+/// ```
+pub struct Renderer {
+    color: bool,
+}
+
+impl Renderer {
+    pub fn new(color: bool) -> Self {
+        Self { color }
+    }
+
+    /// Colors when writing to a terminal, unless `NO_COLOR` is set
+    pub fn for_stderr() -> Self {
+        use std::io::IsTerminal;
+        Self::new(std::io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none())
+    }
+
+    /// `source` is the content of the diagnostic's file, when it could be read
+    pub fn render(&self, diagnostic: &Diagnostic, source: Option<&str>) -> String {
+        let mut output = String::new();
+        let severity_style = match diagnostic.severity {
+            Severity::Error => AnsiColor::Red.on_default().bold(),
+            Severity::Warning => AnsiColor::Yellow.on_default().bold(),
+        };
+        let gutter_style = AnsiColor::Blue.on_default().bold();
+
+        let header = match diagnostic.code {
+            Some(code) => format!("{}[{}]", diagnostic.severity, code),
+            None => diagnostic.severity.to_string(),
+        };
+        writeln!(
+            output,
+            "{}{}",
+            self.paint(severity_style, &header),
+            self.paint(Style::new().bold(), &format!(": {}", diagnostic.message)),
+        ).unwrap();
+
+        let mut labels: Vec<&Label> = diagnostic.labels.iter().collect();
+        labels.sort_by_key(|label| (label.span.line, label.span.column));
+
+        let width = labels.iter()
+            .map(|label| label.span.line.to_string().len())
+            .max()
+            .unwrap_or(1);
+        let gutter = |line: &str| format!("{:>width$} |", line, width = width);
+
+        let location = match (&diagnostic.file, diagnostic.span()) {
+            (Some(file), Some(span)) => Some(format!("{}:{}", file.display(), span)),
+            (Some(file), None) => Some(file.display().to_string()),
+            (None, Some(span)) => Some(span.to_string()),
+            (None, None) => None,
+        };
+        if let Some(location) = location {
+            writeln!(output, "{}{} {}", " ".repeat(width), self.paint(gutter_style, "-->"), location).unwrap();
+        }
+
+        if let Some(source) = source.filter(|_| !labels.is_empty()) {
+            writeln!(output, "{}", self.paint(gutter_style, &gutter(""))).unwrap();
+
+            let mut previous_line = None;
+            for label in &labels {
+                let span = label.span;
+                if previous_line != Some(span.line) {
+                    let text = source.lines().nth(span.line.saturating_sub(1)).unwrap_or("");
+                    writeln!(output, "{} {}", self.paint(gutter_style, &gutter(&span.line.to_string())), text).unwrap();
+                    previous_line = Some(span.line);
+                }
+
+                // Only the first line of a span is underlined, with at least one mark
+                let length = source.get(span.start..span.end)
+                    .and_then(|text| text.lines().next())
+                    .map_or(0, |text| text.chars().count())
+                    .max(1);
+                let (mark, style) = if label.primary {
+                    ("^", severity_style)
+                } else {
+                    ("-", gutter_style)
+                };
+                let mut marks = mark.repeat(length);
+                if !label.message.is_empty() {
+                    marks.push(' ');
+                    marks.push_str(&label.message);
+                }
+                writeln!(
+                    output,
+                    "{} {}{}",
+                    self.paint(gutter_style, &gutter("")),
+                    " ".repeat(span.column.saturating_sub(1)),
+                    self.paint(style, &marks),
+                ).unwrap();
+            }
+        }
+
+        for help in &diagnostic.help {
+            writeln!(
+                output,
+                "{}{} {}",
+                " ".repeat(width + 1),
+                self.paint(Style::new().bold(), "= help:"),
+                help,
+            ).unwrap();
+        }
+
+        output
+    }
+
+    fn paint(&self, style: Style, text: &str) -> String {
+        if self.color {
+            format!("{}{}{}", style.render(), text, style.render_reset())
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use corrozy_core::{diagnostics::{Diagnostic, Label}, language::Span};
+
+    use super::{Message, Renderer};
+
+    #[test]
+    fn test_render_source_excerpt() {
+        let source = "let a = 1;\nlet b = ;\n";
+        let diagnostic = Diagnostic::error("expected an expression after `=`")
+            .with_code("E0001")
+            .with_label(Label::primary(Span::at(source, 19, 20), "found `;`"))
+            .with_help("write a value after `=`")
+            .in_file(Path::new("src/main.crz"));
+
+        assert_eq!(
+            Renderer::new(false).render(&diagnostic, Some(source)),
+            "error[E0001]: expected an expression after `=`\n \
+             --> src/main.crz:2:9\n  \
+              |\n\
+             2 | let b = ;\n  \
+              |         ^ found `;`\n  \
+              = help: write a value after `=`\n"
+        );
+    }
+
+    #[test]
+    fn test_render_secondary_label() {
+        let source = "enum Status {\n    Active,\n    Active\n}";
+        let diagnostic = Diagnostic::error("Duplicate case `Active` in enum `Status`")
+            .with_label(Label::primary(Span::at(source, 30, 36), "declared again here"))
+            .with_label(Label::secondary(Span::at(source, 18, 24), "first declared here"));
+
+        assert_eq!(
+            Renderer::new(false).render(&diagnostic, Some(source)),
+            "error: Duplicate case `Active` in enum `Status`\n \
+             --> 3:5\n  \
+              |\n\
+             2 |     Active,\n  \
+              |     ------ first declared here\n\
+             3 |     Active\n  \
+              |     ^^^^^^ declared again here\n"
+        );
+    }
+
+    #[test]
+    fn test_render_without_source() {
+        let diagnostic = Diagnostic::warning("match on enum `Status` is not exhaustive, missing `Banned`")
+            .with_code("W0001")
+            .in_file(Path::new("main.crz"));
+
+        assert_eq!(
+            Renderer::new(false).render(&diagnostic, None),
+            "warning[W0001]: match on enum `Status` is not exhaustive, missing `Banned`\n --> main.crz\n"
+        );
+    }
+
+    #[test]
+    fn test_json_messages() {
+        let source = "print(x);";
+        let diagnostic = Diagnostic::error("Undefined variable `x`")
+            .with_code("E0002")
+            .with_label(Label::primary(Span::at(source, 6, 7), ""))
+            .in_file(Path::new("main.crz"));
+
+        assert_eq!(
+            Message::Diagnostic(&diagnostic).to_json(),
+            "{\"type\":\"diagnostic\",\"severity\":\"error\",\"code\":\"E0002\",\"message\":\"Undefined variable `x`\",\
+             \"file\":\"main.crz\",\"span\":{\"start\":6,\"end\":7,\"line\":1,\"column\":7},\
+             \"labels\":[{\"span\":{\"start\":6,\"end\":7,\"line\":1,\"column\":7},\"message\":\"\",\"primary\":true}],\"help\":[]}"
+        );
+        assert_eq!(
+            Message::Summary { success: false, errors: 1, warnings: 0 }.to_json(),
+            "{\"type\":\"summary\",\"success\":false,\"errors\":1,\"warnings\":0}"
+        );
+    }
+}
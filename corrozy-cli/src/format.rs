@@ -0,0 +1,80 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use corrozy_core::{CorrozyError, diagnostics::{Diagnostic, Diagnostics}, formatter::format_source};
+use walkdir::WalkDir;
+
+/// What `corrozy fmt` found, paths are relative to `root`
+pub struct Report {
+    pub root: PathBuf,
+    /// Files that weren't in the canonical style
+    pub changed: Vec<PathBuf>,
+    /// Why the files that couldn't be formatted weren't, like syntax errors
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Formats the `.crz` files under `path`, a directory or a single file.
+/// With `check` nothing is written, the report only lists what would change
+pub fn format_files(path: &Path, check: bool) -> Result<Report> {
+    let root = if path.is_file() {
+        path.parent().unwrap_or(Path::new(".")).to_path_buf()
+    } else {
+        path.to_path_buf()
+    };
+    let mut report = Report { root, changed: Vec::new(), diagnostics: Vec::new() };
+
+    for entry in WalkDir::new(path).sort_by_file_name() {
+        let entry = entry?;
+        if entry.path().extension().is_none_or(|extension| extension != "crz") {
+            continue;
+        }
+        let relative_path = entry.path().strip_prefix(&report.root).unwrap_or(entry.path()).to_path_buf();
+
+        let source = std::fs::read_to_string(entry.path())
+            .map_err(|error| CorrozyError::io(entry.path(), error))?;
+        let formatted = match format_source(&source) {
+            Ok(formatted) => formatted,
+            Err(error) => {
+                let diagnostics = Diagnostics::from_error(error).into_iter()
+                    .map(|diagnostic| diagnostic.in_file(&relative_path));
+                report.diagnostics.extend(diagnostics);
+                continue;
+            }
+        };
+
+        if formatted != source {
+            if !check {
+                std::fs::write(entry.path(), formatted)
+                    .map_err(|error| CorrozyError::io(entry.path(), error))?;
+            }
+            report.changed.push(relative_path);
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_files;
+
+    #[test]
+    fn test_check_leaves_files_alone() {
+        let directory = std::env::temp_dir().join(format!("corrozy-fmt-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("messy.crz"), "let a=1;\nif a==1{println(a);}\n").unwrap();
+        std::fs::write(directory.join("clean.crz"), "let a = 1;\n").unwrap();
+        std::fs::write(directory.join("broken.crz"), "let = 1;\n").unwrap();
+
+        let report = format_files(&directory, true).unwrap();
+        assert_eq!(report.changed, vec![std::path::PathBuf::from("messy.crz")]);
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(std::fs::read_to_string(directory.join("messy.crz")).unwrap(), "let a=1;\nif a==1{println(a);}\n");
+
+        format_files(&directory, false).unwrap();
+        let formatted = std::fs::read_to_string(directory.join("messy.crz")).unwrap();
+        assert_eq!(formatted, "let a = 1;\nif a == 1 {\n    println(a);\n}\n");
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
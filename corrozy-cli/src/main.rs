@@ -1,12 +1,20 @@
-use std::path::PathBuf;
-use anyhow::{Ok, Result};
-use clap::Parser;
-use corrozy_core::{config, transpiler};
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+use clap::{Parser, Subcommand, ValueEnum};
+use corrozy_core::{config, diagnostics::{Diagnostic, Diagnostics, Severity}, transpiler};
+
+use crate::diagnostics::{Message, Renderer};
+
+mod diagnostics;
+mod format;
 
 #[derive(Parser)]
 #[command(name = "corrozy")]
 #[command(about = "Transpiler of Corrozy to PHP")]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Directory of project
     #[arg(default_value = ".")]
     path: PathBuf,
@@ -18,19 +26,127 @@ struct Cli {
     /// Mode verbose
     #[arg(short, long, action = clap::ArgAction::SetTrue)] 
     verbose: bool,
+
+    /// How errors and warnings are printed
+    #[arg(long, value_enum, default_value_t = MessageFormat::Human)]
+    message_format: MessageFormat,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Rewrites the sources in the canonical style
+    Fmt {
+        /// Directory or file to format
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Only list the files that are not formatted, failing if there are any
+        #[arg(long)]
+        check: bool,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum MessageFormat {
+    /// Source excerpts on stderr
+    Human,
+    /// One JSON object per line on stdout, ending with a summary
+    Json,
 }
 
 fn main () -> Result<()>{
     let cli = Cli::parse();
 
+    let renderer = Renderer::for_stderr();
+
+    if let Some(Command::Fmt { path, check }) = &cli.command {
+        return format(&renderer, path, *check);
+    }
+
     let config_path = cli.path.join("corrozy.toml");
-    let config = config::Config::load(&config_path)?;
+    let (diagnostics, success) = match config::Config::load(&config_path) {
+        Ok(config) => {
+            let mut transpiler = transpiler::Transpiler::new(config);
+            let result = transpiler.transpile_project(&cli.path);
+            // Warnings of the files that were checked are reported with the errors
+            let mut diagnostics = transpiler.warnings().to_vec();
+            match result {
+                Ok(()) => (diagnostics, true),
+                Err(error) => {
+                    diagnostics.extend(Diagnostics::from_error(error));
+                    (diagnostics, false)
+                }
+            }
+        }
+        Err(error) => (Diagnostics::from_error(error), false),
+    };
+    let count = |severity| diagnostics.iter().filter(|diagnostic| diagnostic.severity == severity).count();
 
-    let mut transpiler = transpiler::Transpiler::new(config);
+    match cli.message_format {
+        MessageFormat::Human => {
+            for diagnostic in &diagnostics {
+                eprintln!("{}", render(&renderer, diagnostic, &cli.path));
+            }
+            if success {
+                println!("Transpilation completed successfully!");
+            } else {
+                let errors = count(Severity::Error);
+                eprintln!("error: could not transpile the project due to {} error{}", errors, plural(errors));
+            }
+        }
+        MessageFormat::Json => {
+            for diagnostic in &diagnostics {
+                println!("{}", Message::Diagnostic(diagnostic).to_json());
+            }
+            let summary = Message::Summary {
+                success,
+                errors: count(Severity::Error),
+                warnings: count(Severity::Warning),
+            };
+            println!("{}", summary.to_json());
+        }
+    }
 
-    transpiler.transpile_project(&cli.path)?;
+    if !success {
+        std::process::exit(1);
+    }
 
-    println!("Transpilation completed successfully!");
-    
     Ok(())
 }
+
+fn format(renderer: &Renderer, path: &Path, check: bool) -> Result<()> {
+    let report = format::format_files(path, check)?;
+
+    for diagnostic in &report.diagnostics {
+        eprintln!("{}", render(renderer, diagnostic, &report.root));
+    }
+    for file in &report.changed {
+        if check {
+            println!("Would reformat {}", file.display());
+        } else {
+            println!("Formatted {}", file.display());
+        }
+    }
+
+    if !report.diagnostics.is_empty() {
+        let count = report.diagnostics.len();
+        eprintln!("error: could not format the files due to {} error{}", count, plural(count));
+        std::process::exit(1);
+    }
+    if check && !report.changed.is_empty() {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Renders a diagnostic with the source of its file, relative to the project
+fn render(renderer: &Renderer, diagnostic: &Diagnostic, project_path: &Path) -> String {
+    let source = diagnostic.file.as_ref()
+        .and_then(|file| std::fs::read_to_string(project_path.join(file)).ok());
+    renderer.render(diagnostic, source.as_deref())
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
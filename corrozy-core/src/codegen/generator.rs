@@ -2,7 +2,7 @@ use std::rc::Rc;
 
 use anyhow::{Ok, Result};
 
-use crate::{config::Config, language::{AstNode, features::{expression::generator::ExpressionGen, function::generator::FunctionGenerator, if_else::generator::IfElseGenerator, output::generator::OutputGenerator, statement::generator::ExpStatementGenerator, declaration::generator::DeclarationGenerator}}
+use crate::{config::Config, diagnostics::locate, language::{AstNode, features::{class::generator::{ClassGenerator, ClassHeader}, expression::{ast::Expression, generator::ExpressionGen}, pattern_match::generator::MatchGenerator, function::generator::{FunctionGenerator, FunctionHeader}, enums::generator::{EnumGenerator, EnumHeader}, interface::generator::InterfaceGenerator, traits::generator::TraitGenerator, if_else::generator::IfElseGenerator, loop_for::generator::ForLoopGenerator, loop_while::generator::WhileLoopGenerator, output::generator::OutputGenerator, statement::generator::ExpStatementGenerator, declaration::generator::DeclarationGenerator}}
 };
 
 
@@ -12,6 +12,13 @@ pub struct CodeGenerator {
     function_gen: FunctionGenerator,
     exp_statement_gen: ExpStatementGenerator,
     if_else_gen: IfElseGenerator,
+    class_gen: ClassGenerator,
+    interface_gen: InterfaceGenerator,
+    trait_gen: TraitGenerator,
+    enum_gen: EnumGenerator,
+    match_gen: MatchGenerator,
+    while_loop_gen: WhileLoopGenerator,
+    for_loop_gen: ForLoopGenerator,
     expression_gen: ExpressionGen,
 }
 
@@ -25,7 +32,14 @@ impl CodeGenerator {
             output_gen: OutputGenerator::new(config.clone()),
             function_gen,
             exp_statement_gen: ExpStatementGenerator::new(ExpressionGen::new(config.clone())),
-            if_else_gen: IfElseGenerator::new(),
+            if_else_gen: IfElseGenerator::new(config.clone()),
+            class_gen: ClassGenerator::new(config.clone()),
+            interface_gen: InterfaceGenerator::new(config.clone()),
+            trait_gen: TraitGenerator::new(config.clone()),
+            enum_gen: EnumGenerator::new(config.clone()),
+            match_gen: MatchGenerator::new(config.clone()),
+            while_loop_gen: WhileLoopGenerator::new(config.clone()),
+            for_loop_gen: ForLoopGenerator::new(config.clone()),
             expression_gen,
         }
     }
@@ -46,9 +60,17 @@ impl CodeGenerator {
     pub fn generate_node(
         &self,
         node: &AstNode
+    ) -> Result<String> {
+        self.generate_node_kind(node)
+            .map_err(|e| locate(node.span(), e))
+    }
+
+    fn generate_node_kind(
+        &self,
+        node: &AstNode
     ) -> Result<String> {
         match node {
-            AstNode::Program { statements } => {
+            AstNode::Program { statements, .. } => {
                 let mut result = String::new();
                 for stmt in statements {
                     result.push_str(&self.generate_node(stmt)?);
@@ -56,39 +78,50 @@ impl CodeGenerator {
                 Ok(result)
             }
 
-            AstNode::VariableDeclaration { var_type, name, value } => {
+            AstNode::VariableDeclaration { var_type, name, value, .. } => {
                 self.declaration_gen.generate(var_type, name, value, false)
             }
             
-            AstNode::ConstantDeclaration { const_type, name, value } => {
+            AstNode::ConstantDeclaration { const_type, name, value, .. } => {
                 self.declaration_gen.generate(const_type, name, value, true)
             }
             
-            AstNode::PrintStatement { expression, newline } => {
+            AstNode::PrintStatement { expression, newline, .. } => {
                 self.output_gen.generate(expression, *newline)
             }
             
-            AstNode::ExpressionStatement { expression } => {
-                self.exp_statement_gen.generate(expression)
+            AstNode::ExpressionStatement { expression, .. } => {
+                match expression.as_ref() {
+                    Expression::Match { subject, arms } if self.match_gen.needs_statement_form(arms) => {
+                        self.match_gen.generate(subject, arms, &self.expression_gen, self)
+                    }
+                    _ => self.exp_statement_gen.generate(expression)
+                }
             }
 
             AstNode::FunctionDeclaration {
                 name,
+                type_params,
                 params,
                 return_type,
-                body
+                body,
+                ..
             } => {
-                self.function_gen.generate(
+                let header = FunctionHeader {
                     name,
+                    type_params,
                     params,
                     return_type,
+                };
+                self.function_gen.generate(
+                    header,
                     body,
                     &self.expression_gen,
                     self
                 )
             }
 
-            AstNode::IfStatement { condition, then_block, else_clause } => {
+            AstNode::IfStatement { condition, then_block, else_clause, .. } => {
                 self.if_else_gen.generate(
                     condition,
                     then_block,
@@ -98,8 +131,68 @@ impl CodeGenerator {
                 )
             }
 
-            _ => {
-                Ok(format!("// Node don't implemented: {:?}\n", node))
+            AstNode::ClassDeclaration { modifier, name, type_params, extends, implements, members, .. } => {
+                let header = ClassHeader {
+                    modifier: *modifier,
+                    name,
+                    type_params,
+                    extends: extends.as_deref(),
+                    implements,
+                };
+                self.class_gen.generate(
+                    header,
+                    members,
+                    &self.expression_gen,
+                    self
+                )
+            }
+
+            AstNode::InterfaceDeclaration { name, methods, .. } => {
+                self.interface_gen.generate(name, methods)
+            }
+
+            AstNode::TraitDeclaration { name, members, .. } => {
+                self.trait_gen.generate(
+                    name,
+                    members,
+                    &self.expression_gen,
+                    self
+                )
+            }
+
+            AstNode::EnumDeclaration { name, backing_type, implements, cases, members, .. } => {
+                let header = EnumHeader {
+                    name,
+                    backing_type: backing_type.as_deref(),
+                    implements,
+                };
+                self.enum_gen.generate(
+                    header,
+                    cases,
+                    members,
+                    &self.expression_gen,
+                    self
+                )
+            }
+
+            AstNode::WhileLoop { condition, body, .. } => {
+                self.while_loop_gen.generate(
+                    condition,
+                    body,
+                    &self.expression_gen,
+                    self
+                )
+            }
+
+            AstNode::ForLoop { init, condition, update, body, .. } => {
+                self.for_loop_gen.generate(
+                    init,
+                    condition,
+                    update,
+                    body,
+                    &self.expression_gen,
+                    self
+                )
             }
         }
 
@@ -1,6 +1,7 @@
 use std::path::Path;
-use anyhow::{Ok, Result};
+use anyhow::{anyhow, Ok, Result};
 use serde::{Deserialize, Serialize};
+use crate::CorrozyError;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Config {
@@ -15,6 +16,43 @@ pub struct TranspilerConfig {
     // pub namespace: String,
     pub strict_types: bool,
     pub include_comments: bool,
+    /// Target PHP version, decides which types can be declared natively and
+    /// rejects syntax the version doesn't have
+    #[serde(default = "default_php_version")]
+    pub php_version: String,
+    /// Reject reassigning variables that weren't declared with `let mut`
+    #[serde(default)]
+    pub require_let_mut: bool,
+}
+
+fn default_php_version() -> String {
+    "8.1".to_string()
+}
+
+impl TranspilerConfig {
+    /// Whether the target PHP version is `major.minor` or newer
+    pub fn targets_php(&self, major: u32, minor: u32) -> bool {
+        parse_php_version(&self.php_version)
+            .is_none_or(|version| version >= (major, minor))
+    }
+
+    /// Fails when `feature` can't be generated for the target PHP version
+    pub fn require_php(&self, major: u32, minor: u32, feature: &str) -> Result<()> {
+        if self.targets_php(major, minor) {
+            return Ok(());
+        }
+        Err(anyhow!("{} need PHP {}.{} or newer, php_version is {}", feature, major, minor, self.php_version))
+    }
+}
+
+fn parse_php_version(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.trim().split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = match parts.next() {
+        Some(minor) => minor.parse().ok()?,
+        None => 0,
+    };
+    Some((major, minor))
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -32,11 +70,40 @@ pub enum NamespaceMode {
     None,
 }
 
+/// The settings of a new project, for files outside of any project
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            transpiler: TranspilerConfig {
+                output_dir: "dist".to_string(),
+                src_dir: "./src".to_string(),
+                strict_types: true,
+                include_comments: true,
+                php_version: default_php_version(),
+                require_let_mut: false,
+            },
+            namespace: NamespaceConfig {
+                mode: NamespaceMode::Auto,
+                separator: ".".to_string(),
+                base_namespace: "App".to_string(),
+            },
+        }
+    }
+}
+
 impl Config {
     pub fn load(path: &Path) -> Result<Self> {
-        let content = std::fs::read_to_string(path)?;
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| CorrozyError::io(path, e))?;
         let config: Config = toml::from_str(&content)?;
 
+        if parse_php_version(&config.transpiler.php_version).is_none() {
+            return Err(anyhow!(
+                "Invalid php_version `{}`, expected a version like \"8.1\"",
+                config.transpiler.php_version
+            ));
+        }
+
         Ok(config)
     }
 }
\ No newline at end of file
@@ -0,0 +1,17 @@
+//! Codes shown next to the severity, `error[E0001]`, one for each kind of check
+
+/// The file doesn't follow the grammar
+pub const SYNTAX: &str = "E0001";
+/// A variable is used where no declaration is visible
+pub const UNDEFINED_VARIABLE: &str = "E0002";
+/// Rules of the language, like assigning a constant or `this` outside a class
+pub const SEMANTIC: &str = "E0003";
+/// A value doesn't match the declared type
+pub const TYPE_MISMATCH: &str = "E0004";
+/// Declarations that don't agree across the files of the project
+pub const PROJECT: &str = "E0005";
+/// Code that parses but can't be generated yet
+pub const CODEGEN: &str = "E0006";
+
+/// A `match` over an enum leaves cases out
+pub const NON_EXHAUSTIVE_MATCH: &str = "W0001";
@@ -0,0 +1,196 @@
+use std::{fmt, path::{Path, PathBuf}};
+
+use serde::{Serialize, Serializer, ser::SerializeStruct};
+
+use crate::language::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A message attached to a span of the source
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+    /// The primary label marks where the problem is, secondary ones give context
+    pub primary: bool,
+}
+
+impl Label {
+    pub fn primary(span: Span, message: impl Into<String>) -> Self {
+        Self { span, message: message.into(), primary: true }
+    }
+
+    pub fn secondary(span: Span, message: impl Into<String>) -> Self {
+        Self { span, message: message.into(), primary: false }
+    }
+}
+
+/// An error or warning found in a Corrozy file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: Option<&'static str>,
+    pub message: String,
+    /// File the labels point into, relative to the project
+    pub file: Option<PathBuf>,
+    pub labels: Vec<Label>,
+    pub help: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message.into())
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, message.into())
+    }
+
+    fn new(severity: Severity, message: String) -> Self {
+        Self { severity, code: None, message, file: None, labels: Vec::new(), help: Vec::new() }
+    }
+
+    pub fn with_code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help.push(help.into());
+        self
+    }
+
+    pub fn in_file(mut self, file: &Path) -> Self {
+        self.file = Some(file.to_path_buf());
+        self
+    }
+
+    /// Where the problem is, the span of the first primary label
+    pub fn span(&self) -> Option<Span> {
+        self.labels.iter()
+            .find(|label| label.primary)
+            .map(|label| label.span)
+    }
+}
+
+/// The fields plus `span`, the location of the problem, for tools reading the diagnostics
+impl Serialize for Diagnostic {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Diagnostic", 7)?;
+        state.serialize_field("severity", &self.severity)?;
+        state.serialize_field("code", &self.code)?;
+        state.serialize_field("message", &self.message)?;
+        state.serialize_field("file", &self.file)?;
+        state.serialize_field("span", &self.span())?;
+        state.serialize_field("labels", &self.labels)?;
+        state.serialize_field("help", &self.help)?;
+        state.end()
+    }
+}
+
+/// One line, `file:line:column: message`, without the parts that are unknown
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        if let Some(span) = self.span() {
+            write!(f, "{}:", span)?;
+        }
+        if self.file.is_some() || !self.labels.is_empty() {
+            write!(f, " ")?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+/// Every diagnostic of a check that doesn't stop at the first error
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Diagnostics(pub Vec<Diagnostic>);
+
+impl Diagnostics {
+    /// The diagnostics carried by `error`, a plain error becomes a single diagnostic
+    pub fn from_error(error: anyhow::Error) -> Vec<Diagnostic> {
+        match error.downcast::<Diagnostics>() {
+            Ok(diagnostics) => diagnostics.0,
+            Err(error) => match error.downcast::<Diagnostic>() {
+                Ok(diagnostic) => vec![diagnostic],
+                Err(error) => vec![Diagnostic::error(error.to_string())],
+            },
+        }
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self.0.iter().map(|diagnostic| diagnostic.to_string()).collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl std::error::Error for Diagnostics {}
+
+/// Points `error` to `span`, unless a nested node already located it
+pub fn locate(span: Span, error: anyhow::Error) -> anyhow::Error {
+    if error.is::<Diagnostic>() || error.is::<Diagnostics>() {
+        return error;
+    }
+    Diagnostic::error(error.to_string())
+        .with_label(Label::primary(span, ""))
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use anyhow::anyhow;
+
+    use super::*;
+
+    fn span(line: usize, column: usize) -> Span {
+        Span { start: 0, end: 1, line, column }
+    }
+
+    #[test]
+    fn test_display_includes_known_location() {
+        let diagnostic = Diagnostic::error("Undefined variable `x`");
+        assert_eq!(diagnostic.to_string(), "Undefined variable `x`");
+
+        let diagnostic = diagnostic.with_label(Label::primary(span(3, 5), ""));
+        assert_eq!(diagnostic.to_string(), "3:5: Undefined variable `x`");
+
+        let diagnostic = diagnostic.in_file(Path::new("src/main.crz"));
+        assert_eq!(diagnostic.to_string(), "src/main.crz:3:5: Undefined variable `x`");
+    }
+
+    #[test]
+    fn test_locate_keeps_innermost_span() {
+        let inner = locate(span(2, 9), anyhow!("`this` can only be used inside class methods"));
+        let outer = locate(span(1, 1), inner);
+
+        assert_eq!(outer.to_string(), "2:9: `this` can only be used inside class methods");
+        assert_eq!(Diagnostics::from_error(outer)[0].span(), Some(span(2, 9)));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod codes;
+pub mod diagnostic;
+pub mod syntax;
+
+pub use diagnostic::{Diagnostic, Diagnostics, Label, Severity, locate};
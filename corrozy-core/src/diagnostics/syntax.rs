@@ -0,0 +1,243 @@
+use pest::error::{Error, ErrorVariant, InputLocation};
+
+use crate::{diagnostics::{Diagnostic, Label, codes}, language::{Span, parser::Rule}};
+
+/// Punctuation worth naming when the parser expected it, like a missing `;`
+const PUNCTUATION: [&str; 10] = [";", ",", ")", "]", "}", "{", "=>", "=", ":", "("];
+
+/// Operators read as one token
+const OPERATORS: [&str; 12] = ["=>", "==", "!=", "<=", ">=", "&&", "||", "??", "?.", "::", "++", "--"];
+
+/// Words of the grammar that can't be used as names
+const KEYWORDS: [&str; 31] = [
+    "let", "mut", "const", "fn", "return", "if", "else", "while", "for", "true", "false",
+    "class", "constructor", "new", "this", "self", "public", "protected", "private",
+    "interface", "implements", "trait", "use", "extends", "abstract", "final", "parent",
+    "super", "enum", "match", "null",
+];
+
+/// Rewrites a pest error, which lists grammar rules, in terms of the language:
+/// `expected unary_expression` becomes "expected an expression after `=`"
+pub fn syntax_error(error: &Error<Rule>, source: &str) -> Diagnostic {
+    let position = match error.location {
+        InputLocation::Pos(position) | InputLocation::Span((position, _)) => position,
+    };
+    let found = token_at(source, position);
+    let previous = token_before(source, position);
+
+    if let Some(quote) = found.filter(|token| *token == "\"" || *token == "'")
+        && !source[position + 1..].contains(quote)
+    {
+        return Diagnostic::error("unterminated string literal")
+            .with_code(codes::SYNTAX)
+            .with_label(Label::primary(Span::at(source, position, position + 1), "the string starts here"))
+            .with_help(format!("close it with {}", quote));
+    }
+
+    // Right after a letter the parser only wonders whether the name goes on
+    let after_word = source[..position].chars().last().is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let expected = expected(error, after_word);
+    let mut message = match expected.as_slice() {
+        [] => match found {
+            Some(token) => format!("unexpected `{}`", token),
+            None => "unexpected end of file".to_string(),
+        },
+        items => format!("expected {}", join_or(items)),
+    };
+    if !expected.is_empty()
+        && let Some(previous) = previous
+    {
+        message.push_str(&format!(" after `{}`", previous));
+    }
+
+    // A missing `;` or `)` belongs right after the last token, not on the next line
+    let missing_punctuation = expected.len() == 1 && expected[0].starts_with('`');
+    let label = match (previous, found) {
+        (Some(_), _) if missing_punctuation => {
+            let end = source[..position].trim_end().len();
+            Label::primary(Span::at(source, end, end), format!("missing {}", expected[0]))
+        }
+        (_, Some(token)) => Label::primary(Span::at(source, position, position + token.len()), format!("found `{}`", token)),
+        (_, None) => Label::primary(Span::at(source, position, position), "found end of file"),
+    };
+
+    let mut diagnostic = Diagnostic::error(message)
+        .with_code(codes::SYNTAX)
+        .with_label(label);
+
+    if let Some(keyword) = found.filter(|token| KEYWORDS.contains(token))
+        && expected.iter().any(|item| item == "a name")
+    {
+        diagnostic = diagnostic.with_help(format!("`{}` is a reserved word and can't be used as a name", keyword));
+    }
+
+    diagnostic
+}
+
+/// What the parser could have accepted, in the words used by the messages
+fn expected(error: &Error<Rule>, after_word: bool) -> Vec<String> {
+    let positives: Vec<Rule> = match &error.variant {
+        ErrorVariant::ParsingError { positives, .. } => positives.clone(),
+        ErrorVariant::CustomError { .. } => Vec::new(),
+    };
+    let attempts = error.parse_attempts();
+
+    let mut rules = positives.clone();
+    // `expected program` says nothing, the rules being tried when it failed say more.
+    // Operators only mean the expression could go on, they aren't what is missing
+    if rules.iter().all(|rule| describe(*rule, after_word).is_none() && !is_operator(*rule))
+        && let Some(attempts) = &attempts
+    {
+        rules.extend(attempts.call_stacks().iter().filter_map(|stack| stack.deepest.get_rule().copied()));
+    }
+
+    let mut items: Vec<String> = Vec::new();
+    for description in rules.iter().filter_map(|rule| describe(*rule, after_word)) {
+        if !items.iter().any(|item| item == description) {
+            items.push(description.to_string());
+        }
+    }
+
+    let has_expression = items.iter().any(|item| item == "an expression" || item == "a statement");
+    if has_expression {
+        // Names are expressions too
+        items.retain(|item| item != "a name");
+    }
+
+    if let Some(attempts) = attempts {
+        let tokens = attempts.expected_tokens();
+        for punctuation in PUNCTUATION {
+            let excluded = match punctuation {
+                "=" => positives.contains(&Rule::assignment_operator),
+                ":" => positives.contains(&Rule::define_type),
+                "(" => has_expression,
+                _ => false,
+            };
+            let is_expected = tokens.iter().any(|token| token.to_string() == punctuation);
+            if is_expected && !excluded {
+                items.push(format!("`{}`", punctuation));
+            }
+        }
+    }
+
+    items
+}
+
+fn describe(rule: Rule, after_word: bool) -> Option<&'static str> {
+    match rule {
+        Rule::expression
+        | Rule::unary_expression
+        | Rule::binary_expression_or_single_postfix
+        | Rule::primary_expression
+        | Rule::postfix_expression
+        | Rule::prefix_update_expression
+        | Rule::literal => Some("an expression"),
+        Rule::identifier | Rule::parameter | Rule::parameter_list if !after_word => Some("a name"),
+        Rule::custom_type => Some("a type name"),
+        Rule::type_annotation
+        | Rule::single_type
+        | Rule::union_type
+        | Rule::intersection_type
+        | Rule::basic_type
+        | Rule::array_type
+        | Rule::generic_type => Some("a type"),
+        Rule::statement => Some("a statement"),
+        Rule::block => Some("a block"),
+        Rule::class_member => Some("a property or method"),
+        Rule::match_arm | Rule::match_pattern => Some("a match arm"),
+        Rule::string => Some("a string"),
+        _ => None,
+    }
+}
+
+fn is_operator(rule: Rule) -> bool {
+    matches!(
+        rule,
+        Rule::assignment_operator | Rule::binary_operator | Rule::update_operator | Rule::nullsafe_access
+    )
+}
+
+/// The token starting at `position`: a word, an operator or a single character
+fn token_at(source: &str, position: usize) -> Option<&str> {
+    let rest = &source[position..];
+    let first = rest.chars().next()?;
+
+    let length = if first.is_alphanumeric() || first == '_' {
+        rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len())
+    } else if OPERATORS.iter().any(|op| rest.starts_with(op)) {
+        2
+    } else {
+        first.len_utf8()
+    };
+
+    Some(&rest[..length])
+}
+
+/// The last token before `position`, skipping whitespace
+fn token_before(source: &str, position: usize) -> Option<&str> {
+    let before = source[..position].trim_end();
+    let last = before.chars().last()?;
+
+    if last == '"' || last == '\'' {
+        return None;
+    }
+
+    let start = if last.is_alphanumeric() || last == '_' {
+        before.rfind(|c: char| !(c.is_alphanumeric() || c == '_')).map_or(0, |index| index + 1)
+    } else if OPERATORS.iter().any(|op| before.ends_with(op)) {
+        before.len() - 2
+    } else {
+        before.len() - last.len_utf8()
+    };
+
+    Some(&before[start..])
+}
+
+/// `a`, `a or b`, `a, b or c`
+fn join_or(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [rest @ .., last] => format!("{} or {}", rest.join(", "), last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_utils::parse_with_error;
+
+    fn message(code: &str) -> String {
+        parse_with_error(code).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_missing_expression() {
+        assert_eq!(message("let a = ;"), "1:9: expected an expression after `=`");
+        assert_eq!(message("print(1 +);"), "1:10: expected an expression after `+`");
+        assert_eq!(message("foo(1, );"), "1:8: expected an expression after `,`");
+    }
+
+    #[test]
+    fn test_missing_punctuation_points_after_previous_token() {
+        assert_eq!(message("let x = 1"), "1:10: expected `;` after `1`");
+        assert_eq!(message("fn f() { return 1 }"), "1:18: expected `;` after `1`");
+        assert_eq!(message("let x = 1\nlet y = 2;"), "1:10: expected `;` after `1`");
+        assert_eq!(message("let x;"), "1:6: expected `=` after `x`");
+    }
+
+    #[test]
+    fn test_names_and_types() {
+        assert_eq!(message("let = 5;"), "1:5: expected a name after `let`");
+        assert_eq!(message("x.;"), "1:3: expected a name after `.`");
+        assert_eq!(message("let x: invalide = 1;"), "1:8: expected a type after `:`");
+
+        let error = parse_with_error("let let = 1;").unwrap_err();
+        let diagnostics = crate::diagnostics::Diagnostics::from_error(error);
+        assert_eq!(diagnostics[0].help, ["`let` is a reserved word and can't be used as a name"]);
+    }
+
+    #[test]
+    fn test_unterminated_string() {
+        assert_eq!(message("let x = \"abc;"), "1:9: unterminated string literal");
+    }
+}
@@ -0,0 +1,80 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+use crate::language::parser::Rule;
+
+/// Failures of the pipeline that are not about the rules of the language:
+/// input that can't be read, parse trees the AST builder doesn't expect and
+/// constructs that can't be generated yet. They surface as errors instead of
+/// panics or PHP that silently does something else
+#[derive(Debug, Error)]
+pub enum CorrozyError {
+    /// A file or directory of the project can't be read or written
+    #[error("{}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// The grammar produced a rule the AST builder doesn't handle in this place
+    #[error("Unexpected `{rule:?}` in {context}")]
+    UnexpectedRule { rule: Rule, context: &'static str },
+
+    /// The text of an operator or modifier the AST has no variant for
+    #[error("Unknown {context} `{text}`")]
+    UnknownToken { text: String, context: &'static str },
+
+    /// A part the grammar requires is missing from the parse tree
+    #[error("{context} is missing its {part}")]
+    MissingPart { context: &'static str, part: &'static str },
+
+    /// Valid Corrozy that can't be turned into PHP yet
+    #[error("{0} are not supported yet")]
+    Unsupported(&'static str),
+}
+
+impl CorrozyError {
+    pub fn io(path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        Self::Io { path: path.into(), source }
+    }
+
+    pub fn unexpected_rule(rule: Rule, context: &'static str) -> Self {
+        Self::UnexpectedRule { rule, context }
+    }
+
+    pub fn unknown_token(text: &str, context: &'static str) -> Self {
+        Self::UnknownToken { text: text.to_string(), context }
+    }
+
+    pub fn missing(context: &'static str, part: &'static str) -> Self {
+        Self::MissingPart { context, part }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, rc::Rc};
+
+    use crate::{Config, CorrozyError, codegen::CodeGenerator, utils::test_utils::{default_corrozy_config, parse_with_error}};
+
+    #[test]
+    fn test_unsupported_constructs_are_errors() {
+        let error = parse_with_error("let double = (x: int): int => x * 2;").unwrap_err();
+        assert_eq!(error.to_string(), "1:1: closures are not supported yet");
+
+        // Array literals parse, PHP can't be generated for them yet
+        let ast = parse_with_error("let xs = [1, 2];").unwrap();
+        let error = CodeGenerator::new(Rc::new(default_corrozy_config())).generate(&ast).unwrap_err();
+        assert!(error.to_string().ends_with("array literals are not supported yet"), "{}", error);
+    }
+
+    #[test]
+    fn test_missing_file_names_its_path() {
+        let error = Config::load(Path::new("missing/corrozy.toml")).unwrap_err();
+
+        assert!(matches!(error.downcast_ref::<CorrozyError>(), Some(CorrozyError::Io { .. })));
+        assert!(error.to_string().starts_with("missing/corrozy.toml: "), "{}", error);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::language::Span;
+
+/// A `// line` or `/* block */` comment, which the grammar skips and the
+/// AST doesn't keep
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment<'a> {
+    /// Text of the comment with its delimiters, line comments without their trailing spaces
+    pub text: &'a str,
+    pub span: Span,
+}
+
+/// Comments of `source` in order, strings are skipped so `"//"` is not one
+pub fn collect(source: &str) -> Vec<Comment<'_>> {
+    let mut comments = Vec::new();
+    let mut position = 0;
+
+    while let Some(offset) = source[position..].find(['"', '\'', '/']) {
+        let start = position + offset;
+        let rest = &source[start..];
+
+        position = if rest.starts_with("//") {
+            let line = rest.find('\n').map_or(rest, |end| &rest[..end]);
+            let text = line.trim_end();
+            comments.push(Comment { text, span: Span::at(source, start, start + text.len()) });
+            start + line.len()
+        } else if let Some(body) = rest.strip_prefix("/*") {
+            let end = body.find("*/").map_or(rest.len(), |end| end + 4);
+            comments.push(Comment { text: &rest[..end], span: Span::at(source, start, start + end) });
+            start + end
+        } else if rest.starts_with('/') {
+            start + 1
+        } else {
+            // Strings have no escapes, they end at the next quote of the same kind
+            let quote = &rest[..1];
+            rest[1..].find(quote).map_or(source.len(), |end| start + end + 2)
+        };
+    }
+
+    comments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::collect;
+
+    #[test]
+    fn test_collects_comments_outside_strings() {
+        let source = "let a = \"// not\"; // line  \nlet b = 4 / 2; /* block\n */ let c = '/*';";
+        let comments: Vec<&str> = collect(source).iter().map(|comment| comment.text).collect();
+
+        assert_eq!(comments, vec!["// line", "/* block\n */"]);
+    }
+}
@@ -0,0 +1,15 @@
+pub mod comments;
+pub mod printer;
+
+pub use printer::Printer;
+
+use anyhow::Result;
+
+use crate::language::parser::CorrozyParserImpl;
+
+/// `source` in the canonical style of Corrozy, with its comments. Fails with the
+/// syntax errors when it doesn't parse, or when a comment can't be kept in place
+pub fn format_source(source: &str) -> Result<String> {
+    let statements = CorrozyParserImpl::new().parse(source)?;
+    Printer::new(source).print(&statements)
+}
@@ -0,0 +1,684 @@
+use anyhow::Result;
+
+use crate::{diagnostics::{Diagnostic, Diagnostics, Label}, formatter::comments::{self, Comment}, language::{AstNode, Parameter, Span, features::{block::ast::{Block, ReturnStatement}, class::ast::{ClassMember, ConstructorDeclaration, MethodDeclaration, PropertyDeclaration, Visibility}, closure::ast::ClosureBody, enums::ast::EnumCase, expression::ast::{Expression, Literal, UnaryOperator}, function_call::ast::FunctionCallExp, if_else::ast::ElseClause, interface::ast::MethodSignature, loop_for::ast::ForInit, pattern_match::ast::{MatchArm, MatchArmBody, MatchPattern}, postfix::ast::PostfixSuffix}}};
+
+const INDENT: &str = "    ";
+
+/// Writes an AST back as Corrozy source in the canonical style: four spaces
+/// of indentation, one statement or member per line and at most one blank
+/// line where the source had some.
+///
+/// Comments are placed by their position in the source: those between
+/// statements or members keep their own line, one after a statement on the
+/// same line stays there. A comment inside an expression has no place in the
+/// printed code, the source isn't formatted and the comment is reported instead
+pub struct Printer<'a> {
+    source: &'a str,
+    comments: Vec<Comment<'a>>,
+    /// First comment not written yet
+    next_comment: usize,
+    output: String,
+    indent: usize,
+    /// Nothing was written on the current line, it still needs its indentation
+    at_line_start: bool,
+    /// Nothing was written since the last `{`, no blank line goes here
+    at_block_start: bool,
+    /// Offset in the source where the last statement or comment written ends
+    last_end: usize,
+    /// Comments that couldn't be written where they are
+    misplaced: Vec<Span>,
+}
+
+impl<'a> Printer<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            comments: comments::collect(source),
+            next_comment: 0,
+            output: String::new(),
+            indent: 0,
+            at_line_start: true,
+            at_block_start: true,
+            last_end: 0,
+            misplaced: Vec::new(),
+        }
+    }
+
+    /// Source of the statements of a program, ending with a newline.
+    /// Fails when a comment can't be kept in its place
+    pub fn print(mut self, statements: &[AstNode]) -> Result<String> {
+        for statement in statements {
+            self.statement(statement);
+        }
+        self.comments_before(self.source.len());
+
+        if !self.misplaced.is_empty() {
+            let diagnostics = self.misplaced.iter()
+                .map(|span| {
+                    Diagnostic::error("comment inside an expression can't be kept in place")
+                        .with_label(Label::primary(*span, ""))
+                        .with_help("move it before or after the statement")
+                })
+                .collect();
+            return Err(Diagnostics(diagnostics).into());
+        }
+        Ok(self.output)
+    }
+
+    fn write(&mut self, text: &str) {
+        if self.at_line_start && !text.is_empty() {
+            for _ in 0..self.indent {
+                self.output.push_str(INDENT);
+            }
+            self.at_line_start = false;
+        }
+        self.output.push_str(text);
+    }
+
+    fn newline(&mut self) {
+        self.output.push('\n');
+        self.at_line_start = true;
+    }
+
+    /// Keeps one blank line before what starts at `start` when the source had any
+    fn blank_line_before(&mut self, start: usize) {
+        if !self.at_block_start && has_blank_line(&self.source[self.last_end.min(start)..start]) {
+            self.newline();
+        }
+    }
+
+    fn next_comment_before(&self, offset: usize) -> Option<Comment<'a>> {
+        self.comments.get(self.next_comment)
+            .filter(|comment| comment.span.start < offset)
+            .cloned()
+    }
+
+    /// Writes the comments that start before `offset`, each on its own line
+    fn comments_before(&mut self, offset: usize) {
+        while let Some(comment) = self.next_comment_before(offset) {
+            self.next_comment += 1;
+            self.blank_line_before(comment.span.start);
+            self.write(comment.text);
+            self.newline();
+            self.last_end = comment.span.end;
+            self.at_block_start = false;
+        }
+    }
+
+    /// Writes a statement or member on its own lines with the comments around it
+    fn item(&mut self, span: Span, print: impl FnOnce(&mut Self)) {
+        let span = self.without_trailing_trivia(span);
+        self.comments_before(span.start);
+        self.blank_line_before(span.start);
+        print(self);
+
+        // Nested items took their comments, the ones left are inside an expression
+        while let Some(comment) = self.next_comment_before(span.end) {
+            self.next_comment += 1;
+            self.misplaced.push(comment.span);
+        }
+
+        self.last_end = span.end;
+        // Only separators may stand between the item and a comment on its line
+        if let Some(comment) = self.next_comment_before(self.source.len())
+            && self.source[span.end..comment.span.start].chars().all(|c| matches!(c, ' ' | '\t' | ',' | ';'))
+        {
+            self.next_comment += 1;
+            self.write(" ");
+            self.write(comment.text);
+            self.last_end = comment.span.end;
+        }
+
+        self.newline();
+        self.at_block_start = false;
+    }
+
+    /// A rule ending in an optional part, like `if` without `else`, also
+    /// takes the spaces and comments after it; they belong to what follows
+    fn without_trailing_trivia(&self, mut span: Span) -> Span {
+        loop {
+            span.end = span.start + self.source[span.start..span.end].trim_end().len();
+            match self.comments.iter().rfind(|comment| comment.span.end == span.end && comment.span.start > span.start) {
+                Some(comment) => span.end = comment.span.start,
+                None => return span,
+            }
+        }
+    }
+
+    /// Writes `{`, the items, the comments left before `close` and `}`,
+    /// or just `{}` when there is nothing inside
+    fn braces(&mut self, open: usize, close: usize, empty: bool, items: impl FnOnce(&mut Self)) {
+        if empty && self.next_comment_before(close).is_none() {
+            self.write("{}");
+            return;
+        }
+
+        self.write("{");
+        self.newline();
+        self.indent += 1;
+        self.at_block_start = true;
+        self.last_end = open;
+
+        items(self);
+        self.comments_before(close);
+
+        self.indent -= 1;
+        self.write("}");
+        self.last_end = close;
+    }
+
+    /// `{` of a declaration, the first one after where it starts
+    fn open_brace(&self, span: Span) -> usize {
+        self.source[span.start..span.end].find('{').map_or(span.start, |offset| span.start + offset)
+    }
+
+    fn block(&mut self, block: &Block) {
+        let empty = block.statements.is_empty() && block.return_statement.is_none();
+        let close = block.span.end.saturating_sub(1);
+
+        self.braces(block.span.start, close, empty, |printer| {
+            for statement in &block.statements {
+                printer.statement(statement);
+            }
+            if let Some(return_statement) = &block.return_statement {
+                printer.item(return_statement.span, |printer| printer.return_statement(return_statement));
+            }
+        });
+    }
+
+    fn statement(&mut self, node: &AstNode) {
+        self.item(node.span(), |printer| printer.node(node));
+    }
+
+    fn node(&mut self, node: &AstNode) {
+        match node {
+            AstNode::Program { statements, .. } => {
+                for statement in statements {
+                    self.statement(statement);
+                }
+            }
+            AstNode::ExpressionStatement { expression, .. } => {
+                self.expression(expression);
+                // A match on its own is a statement and needs no `;`
+                if !matches!(**expression, Expression::Match { .. }) {
+                    self.write(";");
+                }
+            }
+            AstNode::VariableDeclaration { var_type, name, value, mutable, .. } => {
+                self.write(if *mutable { "let mut " } else { "let " });
+                self.write(name);
+                self.type_annotation(var_type.as_deref());
+                self.write(" = ");
+                self.expression(value);
+                self.write(";");
+            }
+            AstNode::ConstantDeclaration { name, const_type, value, .. } => {
+                self.write("const ");
+                self.write(name);
+                self.type_annotation(const_type.as_deref());
+                self.write(" = ");
+                self.expression(value);
+                self.write(";");
+            }
+            AstNode::PrintStatement { expression, newline, .. } => {
+                self.write(if *newline { "println(" } else { "print(" });
+                self.expression(expression);
+                self.write(");");
+            }
+            AstNode::FunctionDeclaration { name, type_params, params, return_type, body, .. } => {
+                self.write("fn ");
+                self.write(name);
+                self.type_parameters(type_params);
+                self.parameters(params);
+                self.type_annotation(return_type.as_deref());
+                self.write(" ");
+                self.block(body);
+            }
+            AstNode::IfStatement { condition, then_block, else_clause, .. } => {
+                self.write("if ");
+                self.expression(condition);
+                self.write(" ");
+                self.block(then_block);
+
+                match else_clause.as_deref() {
+                    Some(ElseClause::ElseIf(else_if)) => {
+                        self.write(" else ");
+                        self.node(else_if);
+                    }
+                    Some(ElseClause::Else(else_block)) => {
+                        self.write(" else ");
+                        self.block(else_block);
+                    }
+                    None => {}
+                }
+            }
+            AstNode::ClassDeclaration { modifier, name, type_params, extends, implements, members, span } => {
+                if let Some(modifier) = modifier {
+                    self.write(modifier.to_php());
+                    self.write(" ");
+                }
+                self.write("class ");
+                self.write(name);
+                self.type_parameters(type_params);
+                if let Some(parent) = extends {
+                    self.write(" extends ");
+                    self.write(parent);
+                }
+                self.implements(implements);
+                self.write(" ");
+                self.members(*span, &[], members);
+            }
+            AstNode::InterfaceDeclaration { name, methods, span } => {
+                self.write("interface ");
+                self.write(name);
+                self.write(" ");
+                self.braces(self.open_brace(*span), span.end - 1, methods.is_empty(), |printer| {
+                    for method in methods {
+                        printer.item(method.span, |printer| printer.method_signature(method));
+                    }
+                });
+            }
+            AstNode::TraitDeclaration { name, members, span } => {
+                self.write("trait ");
+                self.write(name);
+                self.write(" ");
+                self.members(*span, &[], members);
+            }
+            AstNode::EnumDeclaration { name, backing_type, implements, cases, members, span } => {
+                self.write("enum ");
+                self.write(name);
+                if let Some(backing_type) = backing_type {
+                    self.write(": ");
+                    self.write(backing_type);
+                }
+                self.implements(implements);
+                self.write(" ");
+                self.members(*span, cases, members);
+            }
+            AstNode::WhileLoop { condition, body, .. } => {
+                self.write("while (");
+                self.expression(condition);
+                self.write(") ");
+                self.block(body);
+            }
+            AstNode::ForLoop { init, condition, update, body, .. } => {
+                self.write("for (");
+                match init.as_deref() {
+                    // The declaration brings its own `;`
+                    Some(ForInit::VariableDeclaration(declaration)) => self.node(declaration),
+                    Some(ForInit::Expression(expression)) => {
+                        self.expression(expression);
+                        self.write(";");
+                    }
+                    None => self.write(";"),
+                }
+                if let Some(condition) = condition {
+                    self.write(" ");
+                    self.expression(condition);
+                }
+                self.write(";");
+                if let Some(update) = update {
+                    self.write(" ");
+                    self.expression(update);
+                }
+                self.write(") ");
+                self.block(body);
+            }
+        }
+    }
+
+    fn return_statement(&mut self, return_statement: &ReturnStatement) {
+        match &return_statement.expression {
+            Some(expression) => {
+                self.write("return ");
+                self.expression(expression);
+                self.write(";");
+            }
+            None => self.write("return;"),
+        }
+    }
+
+    /// Body of a class, trait or enum, the cases of an enum come before its members
+    fn members(&mut self, span: Span, cases: &[EnumCase], members: &[ClassMember]) {
+        let empty = cases.is_empty() && members.is_empty();
+
+        self.braces(self.open_brace(span), span.end - 1, empty, |printer| {
+            for case in cases {
+                printer.item(case.span, |printer| printer.enum_case(case));
+            }
+            for member in members {
+                printer.member(member);
+            }
+        });
+    }
+
+    fn member(&mut self, member: &ClassMember) {
+        match member {
+            ClassMember::TraitUse(traits) => {
+                self.write("use ");
+                self.write(&traits.join(", "));
+                self.write(";");
+                self.newline();
+                self.at_block_start = false;
+            }
+            ClassMember::Property(property) => self.item(property.span, |printer| printer.property(property)),
+            ClassMember::Constructor(constructor) => self.item(constructor.span, |printer| printer.constructor(constructor)),
+            ClassMember::Method(method) => self.item(method.span, |printer| printer.method(method)),
+        }
+    }
+
+    fn property(&mut self, property: &PropertyDeclaration) {
+        self.visibility(property.visibility);
+        self.write(&property.name);
+        self.type_annotation(property.prop_type.as_deref());
+        if let Some(default) = &property.default {
+            self.write(" = ");
+            self.expression(default);
+        }
+        self.write(";");
+    }
+
+    fn constructor(&mut self, constructor: &ConstructorDeclaration) {
+        self.visibility(constructor.visibility);
+        self.write("constructor(");
+        for (index, param) in constructor.params.iter().enumerate() {
+            if index > 0 {
+                self.write(", ");
+            }
+            // A visibility promotes the parameter, even `public` has to stay
+            if let Some(promoted) = param.promoted {
+                self.write(promoted.to_php());
+                self.write(" ");
+            }
+            self.parameter(&param.param);
+        }
+        self.write(") ");
+        self.block(&constructor.body);
+    }
+
+    fn method(&mut self, method: &MethodDeclaration) {
+        if let Some(modifier) = method.modifier {
+            self.write(modifier.to_php());
+            self.write(" ");
+        }
+        self.visibility(method.visibility);
+        self.write("fn ");
+        self.write(&method.name);
+        self.type_parameters(&method.type_params);
+        self.parameters(&method.params);
+        self.type_annotation(method.return_type.as_deref());
+        match &method.body {
+            Some(body) => {
+                self.write(" ");
+                self.block(body);
+            }
+            None => self.write(";"),
+        }
+    }
+
+    fn method_signature(&mut self, signature: &MethodSignature) {
+        self.write("fn ");
+        self.write(&signature.name);
+        self.parameters(&signature.params);
+        self.type_annotation(signature.return_type.as_deref());
+        self.write(";");
+    }
+
+    fn enum_case(&mut self, case: &EnumCase) {
+        self.write(&case.name);
+        if let Some(value) = &case.value {
+            self.write(" = ");
+            self.expression(value);
+        }
+        self.write(",");
+    }
+
+    /// Members are public unless they say otherwise
+    fn visibility(&mut self, visibility: Visibility) {
+        if visibility != Visibility::Public {
+            self.write(visibility.to_php());
+            self.write(" ");
+        }
+    }
+
+    fn implements(&mut self, interfaces: &[String]) {
+        if !interfaces.is_empty() {
+            self.write(" implements ");
+            self.write(&interfaces.join(", "));
+        }
+    }
+
+    fn type_annotation(&mut self, annotation: Option<&str>) {
+        if let Some(annotation) = annotation {
+            self.write(": ");
+            self.write(annotation);
+        }
+    }
+
+    fn type_parameters(&mut self, type_params: &[String]) {
+        if !type_params.is_empty() {
+            self.write("<");
+            self.write(&type_params.join(", "));
+            self.write(">");
+        }
+    }
+
+    fn parameters(&mut self, params: &[Parameter]) {
+        self.write("(");
+        for (index, param) in params.iter().enumerate() {
+            if index > 0 {
+                self.write(", ");
+            }
+            self.parameter(param);
+        }
+        self.write(")");
+    }
+
+    fn parameter(&mut self, param: &Parameter) {
+        self.write(&param.name);
+        self.type_annotation(param.param_type.as_deref());
+    }
+
+    fn arguments(&mut self, args: &[Expression]) {
+        self.write("(");
+        for (index, arg) in args.iter().enumerate() {
+            if index > 0 {
+                self.write(", ");
+            }
+            self.expression(arg);
+        }
+        self.write(")");
+    }
+
+    fn call(&mut self, call: &FunctionCallExp) {
+        self.write(&call.name);
+        self.arguments(&call.args);
+    }
+
+    fn expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Literal(literal) => self.write(&literal_source(literal)),
+            Expression::Variable { name, .. } | Expression::Constant { name, .. } => self.write(name),
+            Expression::FunctionCall(call) => self.call(call),
+            Expression::Parenthesized(inner) => {
+                self.write("(");
+                self.expression(inner);
+                self.write(")");
+            }
+            Expression::Closure { params, return_type, body } => {
+                self.parameters(params);
+                self.type_annotation(return_type.as_deref());
+                self.write(" => ");
+                match body {
+                    ClosureBody::Expression(body) => self.expression(body),
+                    ClosureBody::Block(body) => self.block(body),
+                }
+            }
+            Expression::ArrayLiteral { elements } => {
+                self.write("[");
+                for (index, element) in elements.iter().enumerate() {
+                    if index > 0 {
+                        self.write(", ");
+                    }
+                    self.expression(element);
+                }
+                self.write("]");
+            }
+            Expression::PostfixChain { base, suffixes } => {
+                self.expression(base);
+                for suffix in suffixes {
+                    match suffix {
+                        PostfixSuffix::Index(index) => {
+                            self.write("[");
+                            self.expression(index);
+                            self.write("]");
+                        }
+                        PostfixSuffix::Property(name) => {
+                            self.write(".");
+                            self.write(name);
+                        }
+                        PostfixSuffix::MethodCall(call) => {
+                            self.write(".");
+                            self.call(call);
+                        }
+                        PostfixSuffix::NullsafeProperty(name) => {
+                            self.write("?.");
+                            self.write(name);
+                        }
+                        PostfixSuffix::NullsafeMethodCall(call) => {
+                            self.write("?.");
+                            self.call(call);
+                        }
+                    }
+                }
+            }
+            Expression::BinaryOp { left, op, right } => {
+                self.expression(left);
+                self.write(" ");
+                self.write(op.to_php());
+                self.write(" ");
+                self.expression(right);
+            }
+            Expression::UnaryOp { op, operand } => {
+                self.write(op.to_php());
+                // `- -x` written together would read as `--x`
+                if matches!(op, UnaryOperator::Negate | UnaryOperator::Plus) && starts_with_sign(operand) {
+                    self.write(" ");
+                }
+                self.expression(operand);
+            }
+            Expression::Assignment { target, op, value } => {
+                self.expression(target);
+                self.write(" ");
+                self.write(op.to_php());
+                self.write(" ");
+                self.expression(value);
+            }
+            Expression::Update { op, is_prefix, target } => {
+                if *is_prefix {
+                    self.write(op.to_php());
+                    self.expression(target);
+                } else {
+                    self.expression(target);
+                    self.write(op.to_php());
+                }
+            }
+            Expression::New { class_name, args } => {
+                self.write("new ");
+                self.write(class_name);
+                self.arguments(args);
+            }
+            Expression::This => self.write("this"),
+            Expression::StaticConstant { class_name, name } => {
+                self.write(class_name);
+                self.write("::");
+                self.write(name);
+            }
+            Expression::StaticCall { class_name, method, args } => {
+                self.write(class_name);
+                self.write("::");
+                self.write(method);
+                self.arguments(args);
+            }
+            Expression::Match { subject, arms } => {
+                self.write("match ");
+                self.expression(subject);
+                self.write(" ");
+                self.match_arms(arms);
+            }
+            Expression::ParentCall { method, args } => match method {
+                Some(method) => {
+                    self.write("parent.");
+                    self.write(method);
+                    self.arguments(args);
+                }
+                None => {
+                    self.write("super");
+                    self.arguments(args);
+                }
+            },
+        }
+    }
+
+    /// Arms of a match one per line, those with a block don't need a `,`
+    fn match_arms(&mut self, arms: &[MatchArm]) {
+        if arms.is_empty() {
+            self.write("{}");
+            return;
+        }
+
+        self.write("{");
+        self.newline();
+        self.indent += 1;
+        for arm in arms {
+            for (index, pattern) in arm.patterns.iter().enumerate() {
+                if index > 0 {
+                    self.write(" | ");
+                }
+                match pattern {
+                    MatchPattern::Wildcard => self.write("_"),
+                    MatchPattern::Value(value) => self.expression(value),
+                }
+            }
+            self.write(" => ");
+            match &arm.body {
+                MatchArmBody::Expression(body) => {
+                    self.expression(body);
+                    self.write(",");
+                }
+                MatchArmBody::Block(body) => self.block(body),
+            }
+            self.newline();
+        }
+        self.indent -= 1;
+        self.write("}");
+    }
+}
+
+/// Whether the gap between two items has a line with nothing but spaces
+fn has_blank_line(gap: &str) -> bool {
+    let lines: Vec<&str> = gap.split('\n').collect();
+    lines.len() > 2 && lines[1..lines.len() - 1].iter().any(|line| line.trim().is_empty())
+}
+
+/// Numbers as written in Corrozy, a float keeps its `.` so it stays a float
+fn literal_source(literal: &Literal) -> String {
+    match literal {
+        Literal::Float(value) => {
+            let text = value.to_string();
+            if text.contains('.') { text } else { format!("{}.0", text) }
+        }
+        _ => literal.to_php(),
+    }
+}
+
+fn starts_with_sign(expression: &Expression) -> bool {
+    match expression {
+        Expression::Literal(Literal::Integer(value)) => *value < 0,
+        Expression::Literal(Literal::Float(value)) => value.is_sign_negative(),
+        Expression::UnaryOp { op, .. } => matches!(op, UnaryOperator::Negate | UnaryOperator::Plus),
+        Expression::Update { is_prefix, .. } => *is_prefix,
+        Expression::PostfixChain { base, .. } => starts_with_sign(base),
+        _ => false,
+    }
+}
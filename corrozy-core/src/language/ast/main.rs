@@ -1,45 +1,103 @@
-use crate::language::features::{block::ast::Block, expression::ast::Expression, if_else::ast::ElseClause, loop_for::ast::ForInit};
+use crate::language::{Span, features::{block::ast::Block, class::ast::{ClassMember, ClassModifier}, enums::ast::EnumCase, expression::ast::Expression, interface::ast::MethodSignature, if_else::ast::ElseClause, loop_for::ast::ForInit}};
 
 #[derive(Debug, Clone)]
 pub enum AstNode {
-    Program { statements: Vec<AstNode> },
+    Program { statements: Vec<AstNode>, span: Span },
     ExpressionStatement {
-        expression: Box<Expression>
+        expression: Box<Expression>,
+        span: Span
     },
     VariableDeclaration {
         var_type: Option<String>,
         name: String, 
-        value: Box<Expression> 
+        value: Box<Expression>,
+        /// Declared with `let mut`
+        mutable: bool,
+        span: Span
     },
     ConstantDeclaration {
         name: String,
         const_type: Option<String>,
-        value: Box<Expression>
+        value: Box<Expression>,
+        span: Span
     },
     PrintStatement { 
         expression: Box<Expression>,
-        newline: bool
+        newline: bool,
+        span: Span
     },
     FunctionDeclaration { 
         name: String, 
+        type_params: Vec<String>,
         params: Vec<Parameter>, 
         return_type: Option<String>,
-        body: Box<Block>
+        body: Box<Block>,
+        span: Span
     },
     IfStatement {
         condition: Box<Expression>,
         then_block: Box<Block>,
-        else_clause: Option<Box<ElseClause>>
+        else_clause: Option<Box<ElseClause>>,
+        span: Span
+    },
+    ClassDeclaration {
+        modifier: Option<ClassModifier>,
+        name: String,
+        type_params: Vec<String>,
+        extends: Option<String>,
+        implements: Vec<String>,
+        members: Vec<ClassMember>,
+        span: Span
+    },
+    InterfaceDeclaration {
+        name: String,
+        methods: Vec<MethodSignature>,
+        span: Span
+    },
+    TraitDeclaration {
+        name: String,
+        members: Vec<ClassMember>,
+        span: Span
+    },
+    EnumDeclaration {
+        name: String,
+        backing_type: Option<String>,
+        implements: Vec<String>,
+        cases: Vec<EnumCase>,
+        members: Vec<ClassMember>,
+        span: Span
     },
     WhileLoop {
         condition: Box<Expression>,
-        body: Box<Block>
+        body: Box<Block>,
+        span: Span
     },
     ForLoop {
         init: Option<Box<ForInit>>,
         condition: Option<Box<Expression>>,
         update: Option<Box<Expression>>,
-        body: Box<Block>
+        body: Box<Block>,
+        span: Span
+    }
+}
+
+impl AstNode {
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Program { span, .. }
+            | Self::ExpressionStatement { span, .. }
+            | Self::VariableDeclaration { span, .. }
+            | Self::ConstantDeclaration { span, .. }
+            | Self::PrintStatement { span, .. }
+            | Self::FunctionDeclaration { span, .. }
+            | Self::IfStatement { span, .. }
+            | Self::ClassDeclaration { span, .. }
+            | Self::InterfaceDeclaration { span, .. }
+            | Self::TraitDeclaration { span, .. }
+            | Self::EnumDeclaration { span, .. }
+            | Self::WhileLoop { span, .. }
+            | Self::ForLoop { span, .. } => *span,
+        }
     }
 }
 
@@ -47,4 +105,6 @@ pub enum AstNode {
 pub struct Parameter {
     pub name: String,
     pub param_type: Option<String>,
+    /// Where the name is written
+    pub span: Span,
 }
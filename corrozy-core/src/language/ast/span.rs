@@ -0,0 +1,42 @@
+use std::fmt;
+
+use pest::iterators::Pair;
+use serde::Serialize;
+
+use crate::language::parser::Rule;
+
+/// Position of a node in its source file, lines and columns start at 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct Span {
+    /// Byte offsets of the node in the source
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    pub fn from_pair(pair: &Pair<Rule>) -> Self {
+        let span = pair.as_span();
+        let (line, column) = span.start_pos().line_col();
+
+        Self { start: span.start(), end: span.end(), line, column }
+    }
+
+    /// Span of `start..end` in `source`, finding its line and column
+    pub fn at(source: &str, start: usize, end: usize) -> Self {
+        let before = &source[..start];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        let column = before[line_start..].chars().count() + 1;
+
+        Self { start, end, line, column }
+    }
+}
+
+/// Shown as `line:column`
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
@@ -0,0 +1,34 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AssignmentOperator {
+    Assign,         // =
+    AddAssign,      // +=
+    SubtractAssign, // -=
+    MultiplyAssign, // *=
+    DivideAssign,   // /=
+    ConcatAssign,   // .=
+}
+
+impl AssignmentOperator {
+    pub fn parse_op(s: &str) -> Option<Self> {
+        match s {
+            "=" => Some(Self::Assign),
+            "+=" => Some(Self::AddAssign),
+            "-=" => Some(Self::SubtractAssign),
+            "*=" => Some(Self::MultiplyAssign),
+            "/=" => Some(Self::DivideAssign),
+            ".=" => Some(Self::ConcatAssign),
+            _ => None,
+        }
+    }
+
+    pub fn to_php(&self) -> &'static str {
+        match self {
+            Self::Assign => "=",
+            Self::AddAssign => "+=",
+            Self::SubtractAssign => "-=",
+            Self::MultiplyAssign => "*=",
+            Self::DivideAssign => "/=",
+            Self::ConcatAssign => ".=",
+        }
+    }
+}
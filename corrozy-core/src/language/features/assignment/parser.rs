@@ -0,0 +1,39 @@
+use anyhow::{Result, anyhow};
+
+use crate::{CorrozyError, language::{features::{assignment::ast::AssignmentOperator, expression::ast::Expression, postfix::ast::PostfixSuffix}, parser::{CorrozyParserImpl, Rule}}};
+
+impl CorrozyParserImpl {
+    pub fn parse_assignment(
+        &mut self,
+        target: Expression,
+        op_pair: pest::iterators::Pair<Rule>,
+        value_pair: pest::iterators::Pair<Rule>,
+    ) -> Result<Expression> {
+        let op = AssignmentOperator::parse_op(op_pair.as_str())
+            .ok_or_else(|| CorrozyError::unknown_token(op_pair.as_str(), "assignment operator"))?;
+
+        if !self.is_assignable(&target) {
+            return Err(anyhow!(
+                "Invalid assignment target: only variables, properties and indexes can be assigned"
+            ));
+        }
+
+        let value = self.parse_expression(value_pair)?;
+
+        Ok(Expression::Assignment {
+            target: Box::new(target),
+            op,
+            value: Box::new(value),
+        })
+    }
+
+    pub fn is_assignable(&self, target: &Expression) -> bool {
+        match target {
+            Expression::Variable { .. } => true,
+            Expression::PostfixChain { suffixes, .. } => {
+                matches!(suffixes.last(), Some(PostfixSuffix::Index(_) | PostfixSuffix::Property(_)))
+            }
+            _ => false,
+        }
+    }
+}
@@ -1,9 +1,11 @@
-use crate::language::{AstNode, features::expression::ast::Expression};
+use crate::language::{AstNode, Span, features::expression::ast::Expression};
 
 #[derive(Debug, Clone)]
 pub struct Block {
     pub statements: Vec<AstNode>,
     pub return_statement: Option<ReturnStatement>,
+    /// From `{` to `}`
+    pub span: Span,
 }
 
 impl Block {
@@ -11,6 +13,7 @@ impl Block {
         Block {
             statements: Vec::new(),
             return_statement: None,
+            span: Span::default(),
         }
     }
 }
@@ -19,4 +22,5 @@ impl Block {
 #[derive(Debug, Clone)]
 pub struct ReturnStatement {
     pub expression: Option<Box<Expression>>,
+    pub span: Span,
 }
@@ -1,15 +1,17 @@
+use std::rc::Rc;
+
 use anyhow::{Ok, Result};
 
-use crate::{codegen::CodeGenerator, language::{AstNode, features::{block::ast::Block, closure::{ClosureGenerator, ast::ClosureBody}, expression::{ast::Expression, generator::ExpressionGen}}}};
+use crate::{Config, codegen::CodeGenerator, diagnostics::locate, language::{AstNode, features::{block::ast::Block, closure::{ClosureGenerator, ast::ClosureBody}, expression::{ast::Expression, generator::ExpressionGen}, if_else::ast::ElseClause, loop_for::ast::ForInit, pattern_match::ast::{MatchArmBody, MatchPattern}, postfix::ast::PostfixSuffix}}};
 
 pub struct BlockGenerator {
     closure_gen: ClosureGenerator
 }
 
 impl BlockGenerator {
-    pub fn new() -> Self {
+    pub fn new(config: Rc<Config>) -> Self {
         Self {
-            closure_gen: ClosureGenerator::new()
+            closure_gen: ClosureGenerator::new(config)
         }
     }
 
@@ -22,13 +24,29 @@ impl BlockGenerator {
     ) -> Result<String> {
         let mut result = String::new();
 
+        // Nested functions become closures held in variables, so they are called through them
+        let local_functions: Vec<&String> = block.statements.iter()
+            .filter_map(|statement| match statement {
+                AstNode::FunctionDeclaration { name, .. } => Some(name),
+                _ => None,
+            })
+            .collect();
+        let renamed;
+        let block = if local_functions.is_empty() {
+            block
+        } else {
+            let mut copy = block.clone();
+            call_through_variables(&mut copy, &local_functions);
+            renamed = copy;
+            &renamed
+        };
+
         for statement in &block.statements {
             let generated = self.generate_statement(
                 statement,
-                block,
                 expression_gen,
                 code_gen
-            )?;
+            ).map_err(|e| locate(statement.span(), e))?;
             
             for line in generated.lines() {
                 if !line.trim().is_empty() {
@@ -41,16 +59,18 @@ impl BlockGenerator {
 
         // Generar return statement
         if let Some(return_stmt) = &block.return_statement {
-            result.push_str("    ");
+            let return_code = match &return_stmt.expression {
+                Some(expr) => format!("return {};", expression_gen.generate(expr, None)?),
+                None => "return;".to_string(),
+            };
 
-            match &return_stmt.expression {
-                Some(expr) => {
-                    let return_code = expression_gen.generate(expr, None)?;
-                    result.push_str(&format!("return {};\n", return_code));
-                }
-                None => {
-                    result.push_str("return;\n");
+            // A multi-line `match` is indented like the statements above
+            for line in return_code.lines() {
+                if !line.trim().is_empty() {
+                    result.push_str("    ");
+                    result.push_str(line);
                 }
+                result.push('\n');
             }
         }
 
@@ -60,22 +80,24 @@ impl BlockGenerator {
     fn generate_statement(
         &self,
         statement: &AstNode,
-        block: &Block,
-        _expression_gen: &ExpressionGen,
+        expression_gen: &ExpressionGen,
         code_gen: &CodeGenerator, 
     ) -> Result<String> {
         let mut generated = String::new();
         
         match statement {
-            AstNode::FunctionDeclaration {
-                name,
-                params,
-                return_type,
-                body
-            } => {
-                let closure_raw = self.closure_gen.generate(Some(name), params, return_type, &ClosureBody::Block(body.clone()), Some(block))?;
+            AstNode::FunctionDeclaration { name, params, return_type, body, .. } => {
+                // A PHP function declared here would be global and couldn't read the outer variables
+                let closure_raw = self.closure_gen.generate(
+                    Some(name),
+                    params,
+                    return_type,
+                    &ClosureBody::Block(body.clone()),
+                    expression_gen,
+                    Some(code_gen)
+                )?;
                 generated.push_str(&closure_raw);
-                generated.push_str("\n");
+                generated.push_str(";\n");
             }
             AstNode::VariableDeclaration { name, value , .. } => {
                 match value.as_ref() {
@@ -85,17 +107,18 @@ impl BlockGenerator {
                             &params,
                             &return_type,
                             &body,
-                            Some(block)
+                            expression_gen,
+                            Some(code_gen)
                         )?;
                         generated.push_str(&closure_raw);
-                        generated.push_str("\n");
+                        generated.push_str(";\n");
                     }
                     _ => {
                         generated.push_str(code_gen.generate_node(statement)?.as_str());
                     }
                 }
             }
-            AstNode::ExpressionStatement { expression } => {
+            AstNode::ExpressionStatement { expression, .. } => {
                 match expression.as_ref() {
                     Expression::Closure { params, return_type, body } => {
                         let closure_raw = self.closure_gen.generate(
@@ -103,10 +126,11 @@ impl BlockGenerator {
                             &params,
                             &return_type,
                             &body,
-                            Some(block)
+                            expression_gen,
+                            Some(code_gen)
                         )?;
                         generated.push_str(&closure_raw);
-                        generated.push_str("\n");
+                        generated.push_str(";\n");
                     }
                     _ => {
                         generated.push_str(code_gen.generate_node(statement)?.as_str());
@@ -119,4 +143,121 @@ impl BlockGenerator {
         };
         Ok(generated)
     }
-}
\ No newline at end of file
+}
+
+/// Turns the calls to `names` into calls of the variables holding their closures
+fn call_through_variables(block: &mut Block, names: &[&String]) {
+    for statement in &mut block.statements {
+        call_through_variables_in_node(statement, names);
+    }
+    if let Some(return_stmt) = &mut block.return_statement
+        && let Some(expr) = &mut return_stmt.expression
+    {
+        call_through_variables_in_expression(expr, names);
+    }
+}
+
+fn call_through_variables_in_node(node: &mut AstNode, names: &[&String]) {
+    match node {
+        AstNode::Program { statements, .. } => {
+            for statement in statements {
+                call_through_variables_in_node(statement, names);
+            }
+        }
+        AstNode::ExpressionStatement { expression: expr, .. }
+        | AstNode::PrintStatement { expression: expr, .. }
+        | AstNode::VariableDeclaration { value: expr, .. }
+        | AstNode::ConstantDeclaration { value: expr, .. } => {
+            call_through_variables_in_expression(expr, names);
+        }
+        AstNode::FunctionDeclaration { body, .. } => call_through_variables(body, names),
+        AstNode::IfStatement { condition, then_block, else_clause, .. } => {
+            call_through_variables_in_expression(condition, names);
+            call_through_variables(then_block, names);
+            if let Some(else_clause) = else_clause {
+                match else_clause.as_mut() {
+                    ElseClause::ElseIf(if_node) => call_through_variables_in_node(if_node, names),
+                    ElseClause::Else(block) => call_through_variables(block, names),
+                }
+            }
+        }
+        AstNode::WhileLoop { condition, body, .. } => {
+            call_through_variables_in_expression(condition, names);
+            call_through_variables(body, names);
+        }
+        AstNode::ForLoop { init, condition, update, body, .. } => {
+            if let Some(init) = init {
+                match init.as_mut() {
+                    ForInit::VariableDeclaration(declaration) => call_through_variables_in_node(declaration, names),
+                    ForInit::Expression(expr) => call_through_variables_in_expression(expr, names),
+                }
+            }
+            for expr in [condition, update].into_iter().flatten() {
+                call_through_variables_in_expression(expr, names);
+            }
+            call_through_variables(body, names);
+        }
+        // Methods can't see the variables of the function around the class
+        AstNode::ClassDeclaration { .. }
+        | AstNode::InterfaceDeclaration { .. }
+        | AstNode::TraitDeclaration { .. }
+        | AstNode::EnumDeclaration { .. } => {}
+    }
+}
+
+fn call_through_variables_in_expression(expr: &mut Expression, names: &[&String]) {
+    let all = |exprs: &mut [Expression]| {
+        for expr in exprs {
+            call_through_variables_in_expression(expr, names);
+        }
+    };
+
+    match expr {
+        Expression::Literal(_) | Expression::Variable { .. } | Expression::Constant { .. } | Expression::This | Expression::StaticConstant { .. } => {}
+        Expression::FunctionCall(call) => {
+            if names.contains(&&call.name) {
+                call.name = format!("${}", call.name);
+            }
+            all(&mut call.args);
+        }
+        Expression::Parenthesized(inner) | Expression::UnaryOp { operand: inner, .. } | Expression::Update { target: inner, .. } => {
+            call_through_variables_in_expression(inner, names);
+        }
+        Expression::Closure { body, .. } => match body {
+            ClosureBody::Block(block) => call_through_variables(block, names),
+            ClosureBody::Expression(expr) => call_through_variables_in_expression(expr, names),
+        },
+        Expression::ArrayLiteral { elements: args }
+        | Expression::New { args, .. }
+        | Expression::StaticCall { args, .. }
+        | Expression::ParentCall { args, .. } => all(args),
+        Expression::PostfixChain { base, suffixes } => {
+            call_through_variables_in_expression(base, names);
+            for suffix in suffixes {
+                match suffix {
+                    PostfixSuffix::Index(index) => call_through_variables_in_expression(index, names),
+                    PostfixSuffix::MethodCall(call) | PostfixSuffix::NullsafeMethodCall(call) => all(&mut call.args),
+                    PostfixSuffix::Property(_) | PostfixSuffix::NullsafeProperty(_) => {}
+                }
+            }
+        }
+        Expression::BinaryOp { left, right, .. } | Expression::Assignment { target: left, value: right, .. } => {
+            call_through_variables_in_expression(left, names);
+            call_through_variables_in_expression(right, names);
+        }
+        Expression::Match { subject, arms } => {
+            call_through_variables_in_expression(subject, names);
+            for arm in arms {
+                for pattern in &mut arm.patterns {
+                    if let MatchPattern::Value(value) = pattern {
+                        call_through_variables_in_expression(value, names);
+                    }
+                }
+                match &mut arm.body {
+                    MatchArmBody::Expression(expr) => call_through_variables_in_expression(expr, names),
+                    MatchArmBody::Block(block) => call_through_variables(block, names),
+                }
+            }
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use anyhow::Result;
 
-use crate::language::{features::block::ast::{Block, ReturnStatement}, parser::{CorrozyParserImpl, Rule}};
+use crate::language::{Span, features::block::ast::{Block, ReturnStatement}, parser::{CorrozyParserImpl, Rule}};
 
 
 impl CorrozyParserImpl {
     pub fn parse_block(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Block> {
+        let span = Span::from_pair(&pair);
         let mut statements = Vec::new();
         let mut return_statement = None;
         
@@ -25,10 +26,12 @@ impl CorrozyParserImpl {
         Ok(Block {
             statements,
             return_statement,
+            span,
         })
     }
     
     pub fn parse_return_statement(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Option<ReturnStatement>> {
+        let span = Span::from_pair(&pair);
         let mut expression = None;
 
         for inner_pair in pair.into_inner() {
@@ -40,7 +43,7 @@ impl CorrozyParserImpl {
             }
         }
     
-        Ok(Some(ReturnStatement { expression }))
+        Ok(Some(ReturnStatement { expression, span }))
     }
 }
 
@@ -67,7 +70,7 @@ mod tests {
             .expect("El parseo debe retornar al menos un statement");
 
         match pair {
-            AstNode::IfStatement { condition: _, then_block, else_clause } => {
+            AstNode::IfStatement { condition: _, then_block, else_clause, .. } => {
                 let block = *then_block;
                 
                 assert!(else_clause.is_none(), "Expected else_clause to be None");
@@ -75,7 +78,7 @@ mod tests {
                 assert_eq!(block.statements.len(), 1, "Expected 1 statement (println) in the block");
                 assert!(block.return_statement.is_some(), "Expected a return statement (return 42) in the block");
 
-                if let AstNode::PrintStatement { expression, newline } = &block.statements[0] {
+                if let AstNode::PrintStatement { expression, newline, .. } = &block.statements[0] {
                     assert!(newline, "println must have newline=true");
                     assert!(matches!(**expression, 
                         Expression::Literal(Literal::String(StringType::Interpolated(ref s))) if s == "Hello, World!"
@@ -84,7 +87,7 @@ mod tests {
                     panic!("Primer statement no es un PrintStatement");
                 }
 
-                if let Some(ReturnStatement { expression: Some(ref exp) }) = block.return_statement {
+                if let Some(ReturnStatement { expression: Some(ref exp), .. }) = block.return_statement {
                     assert!(matches!(**exp, 
                         Expression::Literal(Literal::Integer(val)) if val == 42
                     ), "Return statement: Expected expression to be the integer 42");
@@ -0,0 +1,104 @@
+use crate::language::{Parameter, Span, features::{block::ast::Block, expression::ast::Expression}};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Visibility {
+    Public,
+    Protected,
+    Private,
+}
+
+impl Visibility {
+    pub fn from_keyword(s: &str) -> Option<Self> {
+        match s {
+            "public" => Some(Self::Public),
+            "protected" => Some(Self::Protected),
+            "private" => Some(Self::Private),
+            _ => None,
+        }
+    }
+
+    pub fn to_php(&self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Protected => "protected",
+            Self::Private => "private",
+        }
+    }
+}
+
+/// `abstract` or `final`, on classes and methods
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClassModifier {
+    Abstract,
+    Final,
+}
+
+impl ClassModifier {
+    pub fn from_keyword(s: &str) -> Option<Self> {
+        match s {
+            "abstract" => Some(Self::Abstract),
+            "final" => Some(Self::Final),
+            _ => None,
+        }
+    }
+
+    pub fn to_php(&self) -> &'static str {
+        match self {
+            Self::Abstract => "abstract",
+            Self::Final => "final",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ClassMember {
+    /// `use TraitA, TraitB;`
+    TraitUse(Vec<String>),
+    Property(PropertyDeclaration),
+    Constructor(ConstructorDeclaration),
+    Method(MethodDeclaration),
+}
+
+#[derive(Debug, Clone)]
+pub struct PropertyDeclaration {
+    pub visibility: Visibility,
+    pub name: String,
+    pub prop_type: Option<String>,
+    pub default: Option<Box<Expression>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstructorDeclaration {
+    pub visibility: Visibility,
+    pub params: Vec<ConstructorParameter>,
+    pub body: Box<Block>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstructorParameter {
+    /// Set when the parameter is promoted to a property (PHP 8)
+    pub promoted: Option<Visibility>,
+    pub param: Parameter,
+}
+
+#[derive(Debug, Clone)]
+pub struct MethodDeclaration {
+    pub visibility: Visibility,
+    pub modifier: Option<ClassModifier>,
+    pub name: String,
+    /// Generic parameters, `fn map<U>(...)`
+    pub type_params: Vec<String>,
+    pub params: Vec<Parameter>,
+    pub return_type: Option<String>,
+    /// `None` for abstract methods
+    pub body: Option<Box<Block>>,
+    pub span: Span,
+}
+
+impl MethodDeclaration {
+    pub fn is_abstract(&self) -> bool {
+        self.modifier == Some(ClassModifier::Abstract)
+    }
+}
@@ -0,0 +1,377 @@
+use std::rc::Rc;
+
+use anyhow::{Ok, Result};
+
+use crate::{Config, codegen::CodeGenerator, language::features::{block::generator::BlockGenerator, class::ast::{ClassMember, ClassModifier, ConstructorDeclaration, ConstructorParameter, MethodDeclaration, PropertyDeclaration}, expression::generator::ExpressionGen, types::generator::{class_name, generate_class_doc, generate_signature_doc, mentions_type_params, to_native_type, to_php_param, to_phpdoc_type}}};
+
+/// Everything before the class body: `abstract class Shape extends Model implements Drawable`
+pub struct ClassHeader<'a> {
+    pub modifier: Option<ClassModifier>,
+    pub name: &'a str,
+    /// Generic parameters, documented as `@template` and erased from native types
+    pub type_params: &'a [String],
+    pub extends: Option<&'a str>,
+    pub implements: &'a [String],
+}
+
+pub struct ClassGenerator {
+    config: Rc<Config>,
+    block_gen: BlockGenerator,
+}
+
+impl ClassGenerator {
+    pub fn new(config: Rc<Config>) -> Self {
+        Self {
+            config: config.clone(),
+            block_gen: BlockGenerator::new(config),
+        }
+    }
+
+    pub fn generate(
+        &self,
+        header: ClassHeader,
+        members: &[ClassMember],
+        expression_gen: &ExpressionGen,
+        code_gen: &CodeGenerator,
+    ) -> Result<String> {
+        let mut result = generate_class_doc(header.type_params, header.extends, header.implements);
+        if let Some(modifier) = header.modifier {
+            result.push_str(&format!("{} ", modifier.to_php()));
+        }
+        result.push_str(&format!("class {}", header.name));
+        if let Some(parent) = header.extends {
+            result.push_str(&format!(" extends {}", class_name(parent)));
+        }
+        if !header.implements.is_empty() {
+            let interfaces: Vec<&str> = header.implements.iter().map(|interface| class_name(interface)).collect();
+            result.push_str(&format!(" implements {}", interfaces.join(", ")));
+        }
+        result.push_str(" {\n");
+        result.push_str(&self.generate_members(members, header.type_params, expression_gen, code_gen)?);
+        result.push_str("}\n");
+
+        Ok(result)
+    }
+
+    /// Generates the indented body shared by classes and traits,
+    /// `type_params` are the generic parameters of the class
+    pub fn generate_members(
+        &self,
+        members: &[ClassMember],
+        type_params: &[String],
+        expression_gen: &ExpressionGen,
+        code_gen: &CodeGenerator,
+    ) -> Result<String> {
+        let mut body = String::new();
+        let mut previous: Option<&ClassMember> = None;
+
+        for member in members {
+            let generated = match member {
+                ClassMember::TraitUse(traits) => format!("use {};\n", traits.join(", ")),
+                ClassMember::Property(property) => self.generate_property(property, type_params, expression_gen)?,
+                ClassMember::Constructor(constructor) => self.generate_constructor(constructor, type_params, expression_gen, code_gen)?,
+                ClassMember::Method(method) => self.generate_method(method, type_params, expression_gen, code_gen)?,
+            };
+
+            // Trait uses and properties are grouped, everything else gets a blank line before it
+            let continues_group = matches!(
+                (previous, member),
+                (Some(ClassMember::TraitUse(_)), ClassMember::TraitUse(_))
+                    | (Some(ClassMember::Property(_)), ClassMember::Property(_))
+            );
+            if !body.is_empty() && !continues_group {
+                body.push('\n');
+            }
+            previous = Some(member);
+
+            body.push_str(&generated);
+        }
+
+        let mut result = String::new();
+        for line in body.lines() {
+            if !line.trim().is_empty() {
+                result.push_str("    ");
+                result.push_str(line);
+            }
+            result.push('\n');
+        }
+
+        Ok(result)
+    }
+
+    fn generate_property(
+        &self,
+        property: &PropertyDeclaration,
+        type_params: &[String],
+        expression_gen: &ExpressionGen,
+    ) -> Result<String> {
+        let mut result = String::new();
+
+        if let Some(prop_type) = &property.prop_type {
+            let native_type = to_native_type(prop_type, type_params, &self.config);
+            if native_type.is_none() || mentions_type_params(prop_type, type_params) {
+                result.push_str(&format!("/** @var {} */\n", to_phpdoc_type(prop_type)));
+            }
+            result.push_str(property.visibility.to_php());
+            if let Some(native_type) = native_type {
+                result.push_str(&format!(" {}", native_type));
+            }
+        } else {
+            result.push_str(property.visibility.to_php());
+        }
+        result.push_str(&format!(" ${}", property.name));
+
+        if let Some(default) = &property.default {
+            result.push_str(&format!(" = {}", expression_gen.generate(default, None)?));
+        }
+        result.push_str(";\n");
+
+        Ok(result)
+    }
+
+    fn generate_constructor(
+        &self,
+        constructor: &ConstructorDeclaration,
+        type_params: &[String],
+        expression_gen: &ExpressionGen,
+        code_gen: &CodeGenerator,
+    ) -> Result<String> {
+        let params: Vec<String> = constructor.params.iter()
+            .map(|param| self.generate_constructor_param(param, type_params))
+            .collect();
+
+        let plain_params: Vec<_> = constructor.params.iter().map(|param| param.param.clone()).collect();
+        let mut result = generate_signature_doc(&[], &plain_params, None, type_params, &self.config);
+        result.push_str(&format!(
+            "{} function __construct({}) {{\n",
+            constructor.visibility.to_php(),
+            params.join(", ")
+        ));
+        result.push_str(&self.block_gen.generate(&constructor.body, expression_gen, code_gen)?);
+        result.push_str("}\n");
+
+        Ok(result)
+    }
+
+    fn generate_constructor_param(&self, param: &ConstructorParameter, type_params: &[String]) -> String {
+        let php_param = to_php_param(&param.param, type_params, &self.config);
+
+        match param.promoted {
+            Some(visibility) => format!("{} {}", visibility.to_php(), php_param),
+            None => php_param,
+        }
+    }
+
+    fn generate_method(
+        &self,
+        method: &MethodDeclaration,
+        class_type_params: &[String],
+        expression_gen: &ExpressionGen,
+        code_gen: &CodeGenerator,
+    ) -> Result<String> {
+        let type_params = [class_type_params, &method.type_params].concat();
+        let params: Vec<String> = method.params.iter()
+            .map(|param| to_php_param(param, &type_params, &self.config))
+            .collect();
+
+        let mut result = generate_signature_doc(
+            &method.type_params,
+            &method.params,
+            method.return_type.as_deref(),
+            class_type_params,
+            &self.config,
+        );
+        if let Some(modifier) = method.modifier {
+            result.push_str(&format!("{} ", modifier.to_php()));
+        }
+        result.push_str(&format!(
+            "{} function {}({})",
+            method.visibility.to_php(),
+            method.name,
+            params.join(", ")
+        ));
+        if let Some(return_type) = method.return_type.as_deref()
+            .and_then(|return_type| to_native_type(return_type, &type_params, &self.config))
+        {
+            result.push_str(&format!(": {}", return_type));
+        }
+
+        match &method.body {
+            Some(body) => {
+                result.push_str(" {\n");
+                result.push_str(&self.block_gen.generate(body, expression_gen, code_gen)?);
+                result.push_str("}\n");
+            }
+            None => result.push_str(";\n"),
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::{codegen::CodeGenerator, utils::test_utils::{default_corrozy_config, generate_php_snippet, parse_with_error}};
+
+    #[test]
+    fn test_class_generation() {
+        let php = generate_php_snippet(r#"
+            class User {
+                public name: string;
+                private visits: int = 0;
+
+                constructor(private email: string, name: string) {
+                    this.name = name;
+                }
+
+                public fn greet(greeting: string): string {
+                    return greeting;
+                }
+
+                fn track() {
+                    self.visits++;
+                }
+            }
+        "#);
+
+        assert_eq!(php, concat!(
+            "class User {\n",
+            "    public string $name;\n",
+            "    private int $visits = 0;\n",
+            "\n",
+            "    public function __construct(private string $email, string $name) {\n",
+            "        $this->name = $name;\n",
+            "    }\n",
+            "\n",
+            "    public function greet(string $greeting): string {\n",
+            "        return $greeting;\n",
+            "    }\n",
+            "\n",
+            "    public function track() {\n",
+            "        $this->visits++;\n",
+            "    }\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    fn test_new_expression_generation() {
+        let php = generate_php_snippet("let user: User = new User('a@b.c', 'Diego');");
+
+        assert_eq!(php, "$user = new User('a@b.c', 'Diego');\n");
+    }
+
+    #[test]
+    fn test_inheritance_generation() {
+        let php = generate_php_snippet(r#"
+            abstract class Shape extends Model implements Drawable {
+                abstract protected fn area(): float;
+
+                final fn describe(): string {
+                    return parent.describe(super.name());
+                }
+            }
+        "#);
+
+        assert_eq!(php, concat!(
+            "abstract class Shape extends Model implements Drawable {\n",
+            "    abstract protected function area(): float;\n",
+            "\n",
+            "    final public function describe(): string {\n",
+            "        return parent::describe(parent::name());\n",
+            "    }\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    fn test_parent_constructor_generation() {
+        let php = generate_php_snippet("final class Admin extends User { constructor(name: string) { super(name, 'admin'); } }");
+
+        assert_eq!(php, concat!(
+            "final class Admin extends User {\n",
+            "    public function __construct(string $name) {\n",
+            "        parent::__construct($name, 'admin');\n",
+            "    }\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    fn test_any_type_maps_to_mixed() {
+        let php = generate_php_snippet("class Box { value: any; }");
+
+        assert_eq!(php, "class Box {\n    public mixed $value;\n}\n");
+    }
+
+    #[test]
+    fn test_nullable_types_generation() {
+        let php = generate_php_snippet("class Node { next: Node? = null; fn find(key: string?): Node? { return self.next; } }");
+
+        assert_eq!(php, concat!(
+            "class Node {\n",
+            "    public ?Node $next = null;\n",
+            "\n",
+            "    public function find(?string $key): ?Node {\n",
+            "        return $this->next;\n",
+            "    }\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    fn test_union_and_intersection_types_generation() {
+        let php = generate_php_snippet(r#"
+            class Stats {
+                values: float[];
+
+                fn total(items: Countable & Traversable, scale: int | float?): int | float {
+                    return scale;
+                }
+            }
+        "#);
+
+        assert_eq!(php, concat!(
+            "class Stats {\n",
+            "    public array $values;\n",
+            "\n",
+            "    public function total(Countable&Traversable $items, int|float|null $scale): int|float {\n",
+            "        return $scale;\n",
+            "    }\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    fn test_union_types_fall_back_to_phpdoc_on_old_php() {
+        let mut config = default_corrozy_config();
+        config.transpiler.php_version = "7.4".to_string();
+        let ast = parse_with_error(r#"
+            class Stats {
+                total: int | float = 0;
+
+                fn scale(factor: int | float, label: string): int | float {
+                    return factor;
+                }
+            }
+        "#).unwrap();
+        let php = CodeGenerator::new(Rc::new(config)).generate(&ast).unwrap();
+
+        assert_eq!(php, concat!(
+            "class Stats {\n",
+            "    /** @var int|float */\n",
+            "    public $total = 0;\n",
+            "\n",
+            "    /**\n",
+            "     * @param int|float $factor\n",
+            "     * @return int|float\n",
+            "     */\n",
+            "    public function scale($factor, string $label) {\n",
+            "        return $factor;\n",
+            "    }\n",
+            "}\n",
+        ));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod generator;
+pub mod ast;
+pub mod parser;
+
+pub use generator::ClassGenerator;
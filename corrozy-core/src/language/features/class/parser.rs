@@ -0,0 +1,248 @@
+use anyhow::{Result, anyhow};
+
+use crate::{CorrozyError, language::{AstNode, Parameter, Span, features::{block::ast::Block, class::ast::{ClassMember, ClassModifier, ConstructorDeclaration, ConstructorParameter, MethodDeclaration, PropertyDeclaration, Visibility}, expression::ast::Expression}, parser::{CorrozyParserImpl, Rule}}};
+
+impl CorrozyParserImpl {
+    pub fn parse_class_declaration(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<AstNode> {
+        let span = Span::from_pair(&pair);
+        let mut modifier = None;
+        let mut name = String::new();
+        let mut type_params = Vec::new();
+        let mut extends = None;
+        let mut implements = Vec::new();
+        let mut members = Vec::new();
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::class_modifier => {
+                    modifier = Some(self.parse_class_modifier(inner_pair)?);
+                }
+                Rule::custom_type => {
+                    name = inner_pair.as_str().to_string();
+                }
+                Rule::type_parameters => {
+                    type_params = self.parse_type_parameters(inner_pair);
+                }
+                Rule::extends_clause => {
+                    extends = inner_pair.into_inner().next()
+                        .map(|parent_pair| self.parse_parent_type(parent_pair))
+                        .transpose()?;
+                }
+                Rule::implements_clause => {
+                    implements = self.parse_implements_clause(inner_pair)?;
+                }
+                Rule::class_member => {
+                    members.push(self.parse_class_member(inner_pair)?);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(AstNode::ClassDeclaration { modifier, name, type_params, extends, implements, members, span })
+    }
+
+    pub fn parse_class_member(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<ClassMember> {
+        let inner_pair = pair.into_inner().next()
+            .ok_or(CorrozyError::missing("class member", "declaration"))?;
+
+        match inner_pair.as_rule() {
+            Rule::trait_use => {
+                Ok(ClassMember::TraitUse(self.parse_trait_use(inner_pair)))
+            }
+            Rule::property_declaration => {
+                Ok(ClassMember::Property(self.parse_property_declaration(inner_pair)?))
+            }
+            Rule::constructor_declaration => {
+                Ok(ClassMember::Constructor(self.parse_constructor_declaration(inner_pair)?))
+            }
+            Rule::method_declaration => {
+                Ok(ClassMember::Method(self.parse_method_declaration(inner_pair)?))
+            }
+            _ => Err(CorrozyError::unexpected_rule(inner_pair.as_rule(), "class member").into())
+        }
+    }
+
+    fn parse_property_declaration(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<PropertyDeclaration> {
+        let span = Span::from_pair(&pair);
+        let mut visibility = Visibility::Public;
+        let mut name = String::new();
+        let mut prop_type = None;
+        let mut default = None;
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::visibility => {
+                    visibility = self.parse_visibility(inner_pair)?;
+                }
+                Rule::identifier => {
+                    name = inner_pair.as_str().to_string();
+                }
+                Rule::define_type => {
+                    prop_type = Some(self.parse_define_type(inner_pair)?);
+                }
+                Rule::expression => {
+                    default = Some(Box::new(self.parse_expression(inner_pair)?));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(PropertyDeclaration { visibility, name, prop_type, default, span })
+    }
+
+    fn parse_constructor_declaration(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<ConstructorDeclaration> {
+        let span = Span::from_pair(&pair);
+        let mut visibility = Visibility::Public;
+        let mut params = Vec::new();
+        let mut body = Block::new();
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::visibility => {
+                    visibility = self.parse_visibility(inner_pair)?;
+                }
+                Rule::constructor_parameter_list => {
+                    for param_pair in inner_pair.into_inner() {
+                        if param_pair.as_rule() == Rule::constructor_parameter {
+                            params.push(self.parse_constructor_parameter(param_pair)?);
+                        }
+                    }
+                }
+                Rule::block => {
+                    body = self.parse_block(inner_pair)?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(ConstructorDeclaration { visibility, params, body: Box::new(body), span })
+    }
+
+    fn parse_constructor_parameter(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<ConstructorParameter> {
+        let mut promoted = None;
+        let mut param: Option<Parameter> = None;
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::visibility => {
+                    promoted = Some(self.parse_visibility(inner_pair)?);
+                }
+                Rule::parameter => {
+                    param = Some(self.parse_parameter(inner_pair)?);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(ConstructorParameter {
+            promoted,
+            param: param.ok_or(CorrozyError::missing("constructor parameter", "name"))?,
+        })
+    }
+
+    pub fn parse_method_declaration(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<MethodDeclaration> {
+        let span = Span::from_pair(&pair);
+        let mut visibility = Visibility::Public;
+        let mut modifier = None;
+        let mut name = String::new();
+        let mut type_params = Vec::new();
+        let mut params = Vec::new();
+        let mut return_type = None;
+        let mut body = None;
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::visibility => {
+                    visibility = self.parse_visibility(inner_pair)?;
+                }
+                Rule::class_modifier => {
+                    modifier = Some(self.parse_class_modifier(inner_pair)?);
+                }
+                Rule::identifier => {
+                    name = inner_pair.as_str().to_string();
+                }
+                Rule::type_parameters => {
+                    type_params = self.parse_type_parameters(inner_pair);
+                }
+                Rule::parameter_list => {
+                    params = self.parse_parameter_list(inner_pair)?;
+                }
+                Rule::define_type => {
+                    return_type = Some(self.parse_define_type(inner_pair)?);
+                }
+                Rule::block => {
+                    body = Some(Box::new(self.parse_block(inner_pair)?));
+                }
+                _ => {}
+            }
+        }
+
+        let is_abstract = modifier == Some(ClassModifier::Abstract);
+        if is_abstract && body.is_some() {
+            return Err(anyhow!("Abstract method `{}` cannot have a body", name));
+        }
+        if !is_abstract && body.is_none() {
+            return Err(anyhow!("Method `{}` is missing a body", name));
+        }
+
+        Ok(MethodDeclaration { visibility, modifier, name, type_params, params, return_type, body, span })
+    }
+
+    fn parse_class_modifier(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<ClassModifier> {
+        ClassModifier::from_keyword(pair.as_str())
+            .ok_or_else(|| CorrozyError::unknown_token(pair.as_str(), "class modifier").into())
+    }
+
+    fn parse_visibility(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Visibility> {
+        Visibility::from_keyword(pair.as_str())
+            .ok_or_else(|| CorrozyError::unknown_token(pair.as_str(), "visibility").into())
+    }
+
+    pub fn parse_new_expression(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
+        let mut class_name = String::new();
+        let mut args = Vec::new();
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::custom_type => {
+                    class_name = inner_pair.as_str().to_string();
+                }
+                Rule::argument_list => {
+                    for arg_pair in inner_pair.into_inner() {
+                        if arg_pair.as_rule() == Rule::expression {
+                            args.push(self.parse_expression(arg_pair)?);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Expression::New { class_name, args })
+    }
+
+    pub fn parse_parent_call(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
+        let mut method = None;
+        let mut args = Vec::new();
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::function_call => {
+                    let call = self.parse_fn_call(inner_pair)?;
+                    method = Some(call.name);
+                    args = call.args;
+                }
+                Rule::argument_list => {
+                    for arg_pair in inner_pair.into_inner() {
+                        if arg_pair.as_rule() == Rule::expression {
+                            args.push(self.parse_expression(arg_pair)?);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Expression::ParentCall { method, args })
+    }
+}
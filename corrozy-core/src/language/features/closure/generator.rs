@@ -1,114 +1,66 @@
-// use crate::codegen::syntax::expression::ExpressionGen;
+use std::rc::Rc;
 
-use core::panic;
-use std::collections::HashSet;
+use anyhow::{Ok, Result};
 
-use anyhow::{anyhow, Ok, Result};
-
-use crate::language::{AstNode, Parameter, features::{block::ast::Block, closure::ast::ClosureBody, expression::ast::Expression, function_call::ast::FunctionCallExp, if_else::ast::ElseClause}};
+use crate::{Config, CorrozyError, codegen::CodeGenerator, language::{Parameter, features::{block::{ast::Block, generator::BlockGenerator}, closure::ast::ClosureBody, expression::generator::ExpressionGen, types::generator::{to_php_param, to_php_type}}}, semantic::resolver::captured_variables};
 
 #[derive(Clone)]
-pub struct ClosureGenerator;
-
-struct ClosurePreloaded {
-    _closure_transpiled: String,
-    use_clause: HashSet<String>,
+pub struct ClosureGenerator {
+    config: Rc<Config>,
 }
 
 impl ClosureGenerator {
-    pub fn new() -> Self {
-        Self
+    pub fn new(config: Rc<Config>) -> Self {
+        Self { config }
     }
 
+    /// A closure with an expression body becomes an arrow function, one with a
+    /// block body a `function` with the variables it uses in its `use` clause,
+    /// by reference when it writes them.
+    /// `name` assigns it to a variable, without the final `;`
     pub fn generate(
         &self,
         name: Option<&String>,
         params: &Vec<Parameter>,
         return_type: &Option<String>,
         body: &ClosureBody,
-        parent_scope_opt: Option<&Block>
+        expression_gen: &ExpressionGen,
+        code_gen_opt: Option<&CodeGenerator>,
     ) -> Result<String> {
         let mut result = String::new();
 
-        if name.is_some() {
-            result.push_str(format!("${} = ", name.unwrap()).as_str());
+        if let Some(name) = name {
+            result.push_str(format!("${} = ", name).as_str());
         }
-        
+
         match body {
-            ClosureBody::Expression(_expr) => {
+            ClosureBody::Expression(expr) => {
                 // Arrow function
                 result.push_str("fn(");
                 let php_params = self.generate_params(params);
                 result.push_str(&php_params);
                 result.push_str(")");
-                
-                if let Some(ret_type) = return_type {
+
+                if let Some(ret_type) = return_type.as_deref()
+                    .and_then(|ret_type| to_php_type(ret_type, &self.config))
+                {
                     result.push_str(&format!(": {}", ret_type));
                 }
-                
+
                 result.push_str(" => ");
-                // result.push_str(&self.generate_expression(expr));
-                result.push_str("EXPRESSION_PLACEHOLDER");
+                result.push_str(&expression_gen.generate(expr, code_gen_opt)?);
             }
             ClosureBody::Block(block) => {
-                // Prioritize loading loose closures in the block
-                let mut preloaded_closures: Vec<ClosurePreloaded> = vec![];
-                let mut required_vars: HashSet<String> = HashSet::new();
-
-                for statement in &block.statements {
-                    match statement {
-                        AstNode::FunctionDeclaration { name, params, return_type, body } => {
-                            let closure_preloaded = self.generate_closure_multiline(
-                                Some(name),
-                                params,
-                                return_type,
-                                body,
-                                Some(&required_vars)
-                            );
-                            required_vars.extend(closure_preloaded.use_clause.clone());
-                            preloaded_closures.push(closure_preloaded);
-                        }
-                        AstNode::VariableDeclaration { name, value, ..} => {
-                            if let Expression::Closure { params, return_type, body } = value.as_ref() {
-                                if let ClosureBody::Block(block) = body {
-                                    let closure_preloaded = self.generate_closure_multiline(
-                                        Some(name),
-                                        &params,
-                                        &return_type,
-                                        &block,
-                                        Some(&required_vars)
-                                    );
-                                    required_vars.extend(closure_preloaded.use_clause.clone());
-                                    preloaded_closures.push(closure_preloaded);
-                                }
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-                // none name for alright it have
-                let _closure_preloaded_main = self.generate_closure_multiline(
-                    None,
+                let code_gen = code_gen_opt
+                    .ok_or(CorrozyError::missing("block closure", "code generator"))?;
+                let closure = self.generate_closure_multiline(
                     params,
                     return_type,
                     block,
-                    Some(&required_vars)
-                );
-                let parent_scope = parent_scope_opt.unwrap_or_else(|| {
-                    panic!("Parent scope is required to validate closure variables");
-                }); 
-                let outsider_vars = self.collect_declared_variables_from_block(parent_scope);
-                let lost_vars: HashSet<String> = required_vars.difference(&outsider_vars)
-                    .cloned()
-                    .collect();
-                if !lost_vars.is_empty() {
-                    let vars_list: Vec<String> = lost_vars.iter().cloned().collect();
-
-                    return Err(anyhow!(
-                        "Undefined variables in closure: {}. These variables are used but not declared in the outer scope.", 
-                        vars_list.join(", ")
-                    ));
-                }
+                    expression_gen,
+                    code_gen,
+                )?;
+                result.push_str(&closure);
             }
         }
 
@@ -117,232 +69,138 @@ impl ClosureGenerator {
 
     fn generate_closure_multiline(
         &self,
-        name: Option<&String>,
         params: &Vec<Parameter>,
         return_type: &Option<String>,
         body: &Block,
-        inherited_use_vars: Option<&HashSet<String>>
-    ) -> ClosurePreloaded {
+        expression_gen: &ExpressionGen,
+        code_gen: &CodeGenerator,
+    ) -> Result<String> {
         let mut result = String::new();
 
-        // Declaration if it's not anonymous
-        if name.is_some() {
-            result.push_str(format!("${} = ", name.unwrap()).as_str());
-        }
-        
         // Traditional closure
         result.push_str("function(");
         let php_params = self.generate_params(params);
         result.push_str(&php_params);
         result.push_str(")");
-        
-        if let Some(ret_type) = return_type {
-            result.push_str(&format!(": {}", ret_type));
-        }
 
-        let use_vars = self.generate_use_clause(params, body, inherited_use_vars);
-        
+        // Variables of nested closures are captured too, so they can pass them on
+        let use_vars = captured_variables(params, body);
+
         if !use_vars.is_empty() {
+            // An assignment inside the closure has to change the variable outside
             let php_use_vars: Vec<String> = use_vars.iter()
-                .map(|var| format!("${}", var))
+                .map(|capture| if capture.by_reference { format!("&${}", capture.name) } else { format!("${}", capture.name) })
                 .collect();
             let use_clause_string = php_use_vars.join(", ");
-            
+
             result.push_str(&format!(" use ({})", use_clause_string));
         }
+
+        // PHP puts the return type after the `use` clause
+        if let Some(ret_type) = return_type.as_deref()
+            .and_then(|ret_type| to_php_type(ret_type, &self.config))
+        {
+            result.push_str(&format!(": {}", ret_type));
+        }
         result.push_str(" {\n");
 
-        // generate body
-        // result.push_str(&self.generate_block(body));
+        let block_gen = BlockGenerator::new(self.config.clone());
+        result.push_str(&block_gen.generate(body, expression_gen, code_gen)?);
 
-        result.push_str("}\n");
+        result.push('}');
 
-        ClosurePreloaded {
-            _closure_transpiled: result,
-            use_clause: use_vars
-        }
+        Ok(result)
     }
 
     fn generate_params(&self, params: &Vec<Parameter>) -> String {
-        params.iter().map(|param| {
-            if let Some(param_type) = &param.param_type {
-                format!("{} ${}", param_type, param.name)
-            } else {
-                format!("${}", param.name)
-            }
-        }).collect::<Vec<_>>().join(", ")
-    }
-    
-    fn generate_use_clause(&self,
-        params: &Vec<Parameter>,
-        closure_body: &Block,    
-        inherited_use_vars: Option<&HashSet<String>>
-    ) -> HashSet<String> {
-        let used_vars = self.collect_used_variables_from_block(closure_body, inherited_use_vars);
-        
-        let mut declared_vars = self.collect_declared_variables_from_block(closure_body);
-        for param in params {
-            declared_vars.insert(param.name.clone());
-        }
-        
-        let captured_vars: HashSet<String> = used_vars.difference(&declared_vars)
-            .cloned()
-            .collect();
-        
-        captured_vars
+        params.iter().map(|param| to_php_param(param, &[], &self.config)).collect::<Vec<_>>().join(", ")
     }
+}
 
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
 
-    fn collect_used_variables_from_block(&self, block: &Block, starting_vars: Option<&HashSet<String>>) -> HashSet<String> {
-        let mut used = if let Some(inherited) = starting_vars {
-            inherited.clone()
-        } else {
-            HashSet::new()
-        };
-        
-        for statement in &block.statements {
-            self.walk_for_used_variables(statement, &mut used);
-        }
-        
-        if let Some(return_stmt) = &block.return_statement {
-            if let Some(expr) = &return_stmt.expression {
-                self.walk_for_used_variables_in_expression(expr, &mut used);
-            }
-        }
-        
-        used
-    }
+    use crate::{codegen::CodeGenerator, language::{AstNode, Parameter, Span, features::{block::ast::{Block, ReturnStatement}, closure::ast::ClosureBody, expression::ast::{BinaryOperator, Expression}}}, utils::test_utils::{default_corrozy_config, parse_with_error}};
 
-    fn collect_declared_variables_from_block(&self, block: &Block) -> HashSet<String> {
-        let mut declared = HashSet::new();
-        
-        for statement in &block.statements {
-            self.walk_for_declared_variables(statement, &mut declared);
-        }
-        
-        declared
+    fn variable(name: &str) -> Box<Expression> {
+        Box::new(Expression::Variable { name: name.to_string(), span: Span::default() })
     }
 
-    fn walk_for_used_variables(&self, node: &AstNode, used: &mut HashSet<String>) {
-        match node {
-            AstNode::ExpressionStatement { expression } => {
-                self.walk_for_used_variables_in_expression(expression, used);
-            }
-            AstNode::VariableDeclaration { value, .. } => {
-                self.walk_for_used_variables_in_expression(value, used);
-            }
-            AstNode::ConstantDeclaration { value, .. } => {
-                self.walk_for_used_variables_in_expression(value, used);
-            }
-            AstNode::PrintStatement { expression, .. } => {
-                self.walk_for_used_variables_in_expression(expression, used);
-            }
-            AstNode::IfStatement { condition, then_block, else_clause } => {
-                self.walk_for_used_variables_in_elseif(condition, then_block, else_clause, used);
-            }
-            AstNode::WhileLoop { condition, body } => {
-                self.walk_for_used_variables_in_expression(condition, used);
-                self.walk_for_used_variables_in_block(&body, used);
-            }
-            AstNode::ForLoop { .. } => {
-                // TODO: implement when needed
-                // self.walk_for_used_variables_in_block(&body, used);
-            }
-            _ => {
-            }
+    fn closure(body: ClosureBody) -> AstNode {
+        let declaration = AstNode::VariableDeclaration {
+            mutable: false,
+            var_type: None,
+            name: "scale".to_string(),
+            value: Box::new(Expression::Closure {
+                params: vec![Parameter { name: "x".to_string(), param_type: Some("int".to_string()), span: Span::default() }],
+                return_type: Some("int".to_string()),
+                body,
+            }),
+            span: Span::default(),
+        };
+
+        // Closures are generated as values inside function bodies
+        AstNode::FunctionDeclaration {
+            name: "run".to_string(),
+            type_params: Vec::new(),
+            params: Vec::new(),
+            return_type: None,
+            body: Box::new(Block { statements: vec![declaration], return_statement: None, span: Span::default() }),
+            span: Span::default(),
         }
     }
 
-    fn walk_for_used_variables_in_elseif(&self, condition: &Box<Expression>, then_block: &Block, else_clause: &Option<Box<ElseClause>>, used: &mut HashSet<String>) {
-        self.walk_for_used_variables_in_expression(condition, used);
-        self.walk_for_used_variables_in_block(&then_block, used);
-        if else_clause.is_some() {
-            match else_clause.clone().unwrap().as_ref() {
-                ElseClause::Else(block) => {
-                    self.walk_for_used_variables_in_block(&block, used);
-                }
-                ElseClause::ElseIf(ast_node) => {
-                    match ast_node.as_ref() {
-                        AstNode::IfStatement { condition, then_block, else_clause  } => {
-                            self.walk_for_used_variables_in_elseif(condition, then_block, else_clause, used);
-                        },
-                        _ => panic!("ElseIf debe contener un IfStatement"),
-                        
-                    }
-                }
-            }
-        }
+    fn generate(node: AstNode) -> String {
+        CodeGenerator::new(Rc::new(default_corrozy_config())).generate(&[node]).unwrap()
     }
 
-    fn walk_for_used_variables_in_expression(&self, expr: &Expression, used: &mut HashSet<String>) {
-        match expr {
-            Expression::Variable(name) => {
-                used.insert(name.clone());
-            }
-            Expression::BinaryOp { left, right, .. } => {
-                self.walk_for_used_variables_in_expression(left, used);
-                self.walk_for_used_variables_in_expression(right, used);
-            }
-            Expression::FunctionCall(FunctionCallExp {args, name: _}) => {
-                for arg in args {
-                    self.walk_for_used_variables_in_expression(arg, used);
-                }
-            }
-            Expression::Parenthesized(inner) => {
-                self.walk_for_used_variables_in_expression(inner, used);
-            }
-            Expression::Closure { body, .. } => {
-                match body {
-                    ClosureBody::Expression(expr) => {
-                        self.walk_for_used_variables_in_expression(expr, used);
-                    }
-                    ClosureBody::Block(block) => {
-                        self.walk_for_used_variables_in_block(block, used);
-                    }
-                }
-            }
-            _ => {}
-        }
+    #[test]
+    fn test_arrow_function() {
+        let body = ClosureBody::Expression(Box::new(Expression::BinaryOp {
+            left: variable("x"),
+            op: BinaryOperator::Multiply,
+            right: variable("factor"),
+        }));
+
+        assert!(generate(closure(body)).contains("    $scale = fn(int $x): int => $x * $factor;\n"));
     }
 
-    fn walk_for_used_variables_in_block(&self, block: &Block, used: &mut HashSet<String>) {
-        for statement in &block.statements {
-            self.walk_for_used_variables(statement, used);
-        }
-        
-        if let Some(return_stmt) = &block.return_statement {
-            if let Some(expr) = &return_stmt.expression {
-                self.walk_for_used_variables_in_expression(expr, used);
-            }
-        }
+    #[test]
+    fn test_block_closure_captures_outer_variables() {
+        let body = ClosureBody::Block(Box::new(Block {
+            statements: Vec::new(),
+            return_statement: Some(ReturnStatement {
+                expression: Some(Box::new(Expression::BinaryOp {
+                    left: variable("x"),
+                    op: BinaryOperator::Multiply,
+                    right: variable("factor"),
+                })),
+                span: Span::default(),
+            }),
+            span: Span::default(),
+        }));
+
+        assert!(generate(closure(body)).contains(
+            "    $scale = function(int $x) use ($factor): int {\n        return $x * $factor;\n    };\n"
+        ));
     }
 
-    fn walk_for_declared_variables(&self, node: &AstNode, declared: &mut HashSet<String>) {
-        match node {
-            AstNode::VariableDeclaration { name, .. } => {
-                declared.insert(name.clone());
-            }
-            AstNode::ConstantDeclaration { name, .. } => {
-                declared.insert(name.clone());
-            }
-            AstNode::IfStatement { .. } => {
-                // TODO: else_clause
-            }
-            AstNode::WhileLoop { .. } => {
-                // self.walk_for_declared_variables_in_block(&body, declared);
-            }
-            AstNode::ForLoop { .. } => {
-                // self.walk_for_declared_variables_in_block(&body, declared);
-            }
-            _ => {
-            }
-        }
+    #[test]
+    fn test_nested_function_is_closure() {
+        let ast = parse_with_error("fn adder(a: int): int { fn add(b: int): int { return a + b; } return add(1); }").unwrap();
+
+        assert!(generate(ast[0].clone()).contains(
+            "    $add = function(int $b) use ($a): int {\n        return $a + $b;\n    };\n    return $add(1);\n"
+        ));
     }
 
-    // never used?
-    // fn walk_for_declared_variables_in_block(&self, block: &Block, declared: &mut HashSet<String>) {
-    //     for statement in &block.statements {
-    //         self.walk_for_declared_variables(statement, declared);
-    //     }
-    // }
-}
\ No newline at end of file
+    #[test]
+    fn test_written_variables_are_captured_by_reference() {
+        let ast = parse_with_error("fn run(): int { let mut count = 0; fn inc() { count = count + 1; } inc(); return count; }").unwrap();
+
+        assert!(generate(ast[0].clone()).contains(
+            "    $inc = function() use (&$count) {\n        $count = $count + 1;\n    };\n"
+        ));
+    }
+}
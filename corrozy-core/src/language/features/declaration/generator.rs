@@ -1,7 +1,7 @@
 use std::rc::Rc;
 
 use anyhow::{Ok, Result};
-use crate::{config::Config, language::features::{closure::ast::ClosureBody, expression::{ast::Expression, generator::ExpressionGen}}};
+use crate::{config::Config, language::features::{closure::ast::ClosureBody, expression::{ast::Expression, generator::ExpressionGen}, types::generator::to_phpdoc_type}};
 
 pub struct DeclarationGenerator {
     config: Rc<Config>,
@@ -63,14 +63,53 @@ impl DeclarationGenerator {
         Ok(output)
     }
     
-    fn map_type_to_phpdoc(&self, var_type: &str) -> &'static str {
-        match var_type {
-            "int" => "int",
-            "string" => "string", 
-            "bool" => "bool",
-            "float" => "float",
-            "var" => "mixed",
-            _ => "mixed"
-        }
+    fn map_type_to_phpdoc(&self, var_type: &str) -> String {
+        to_phpdoc_type(var_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::{codegen::CodeGenerator, utils::test_utils::{default_corrozy_config, parse_with_error}};
+
+    fn generate_with_comments(code: &str) -> String {
+        let mut config = default_corrozy_config();
+        config.transpiler.include_comments = true;
+
+        let ast = parse_with_error(code).unwrap();
+        CodeGenerator::new(Rc::new(config)).generate(&ast).unwrap()
+    }
+
+    #[test]
+    fn test_phpdoc_uses_custom_type_name() {
+        let php = generate_with_comments("let status: Status = Status::Active;");
+
+        assert_eq!(php, "/** @var Status $status */\n$status = Status::Active;\n");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_phpdoc_nullable_type() {
+        let php = generate_with_comments("let name: string? = null;");
+
+        assert_eq!(php, "/** @var string|null $name */\n$name = null;\n");
+    }
+
+    #[test]
+    fn test_phpdoc_any_is_mixed() {
+        let php = generate_with_comments("let value: any = 1;");
+
+        assert_eq!(php, "/** @var mixed $value */\n$value = 1;\n");
+    }
+
+    #[test]
+    fn test_phpdoc_union_and_array_types() {
+        let php = generate_with_comments("let amount: int | float = 1; let ids: int[] = load_ids();");
+
+        assert_eq!(php, concat!(
+            "/** @var int|float $amount */\n$amount = 1;\n",
+            "/** @var array<int> $ids */\n$ids = load_ids();\n",
+        ));
+    }
+}
@@ -1,55 +1,34 @@
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 
-use crate::language::{AstNode, features::{expression::ast::Expression, postfix::ast::PostfixSuffix}, parser::{CorrozyParserImpl, Rule}};
+use crate::{CorrozyError, language::{AstNode, Span, features::expression::ast::Expression, parser::{CorrozyParserImpl, Rule}}};
 
 impl CorrozyParserImpl {
-    pub fn parse_postfix_expression(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
-        let mut inner_pairs = pair.into_inner();
-
-        let base_pair = inner_pairs.next().ok_or_else(|| anyhow!("Postfix expression missing base"))?;
-        let base_expr = self.parse_primary_expression(base_pair)?;
-
-        let mut suffixes: Vec<PostfixSuffix> = Vec::new();
-        
-        for suffix_pair in inner_pairs {
-            match suffix_pair.as_rule() {
-                Rule::expression => {
-                    let index_expr = self.parse_expression(suffix_pair)?;
-                    suffixes.push(PostfixSuffix::Index(Box::new(index_expr)));
-                }
-                
-                Rule::function_call => {
-                    let func_call = self.parse_fn_call(suffix_pair)?;
-                    suffixes.push(PostfixSuffix::MethodCall(func_call));
-                }
-
-                Rule::identifier => {
-                    let name = suffix_pair.as_str().to_string();
-                    suffixes.push(PostfixSuffix::Property(name));
-                }
-
-                _ => return Err(anyhow!("Unexpected rule in postfix: {:?}", suffix_pair.as_rule())),
-            }
-        }
-
-        if suffixes.is_empty() {
-            Ok(base_expr)
-        } else {
-            Ok(Expression::PostfixChain {
-                base: Box::new(base_expr),
-                suffixes,
-            })
-        }
-    }
-    
-    fn parse_primary_expression(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
+    pub fn parse_primary_expression(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
         for inner_pair in pair.into_inner() {
             match inner_pair.as_rule() {
                 Rule::literal => {
                     return Ok(Expression::Literal(self.parse_literal(inner_pair)?));
                 }
                 Rule::identifier => {
-                    return Ok(Expression::Variable(inner_pair.as_str().to_string()));
+                    return Ok(Expression::Variable {
+                        name: inner_pair.as_str().to_string(),
+                        span: Span::from_pair(&inner_pair),
+                    });
+                }
+                Rule::new_expression => {
+                    return self.parse_new_expression(inner_pair);
+                }
+                Rule::this_expression => {
+                    return Ok(Expression::This);
+                }
+                Rule::parent_call => {
+                    return self.parse_parent_call(inner_pair);
+                }
+                Rule::static_access => {
+                    return self.parse_static_access(inner_pair);
+                }
+                Rule::match_expression => {
+                    return self.parse_match_expression(inner_pair);
                 }
                 Rule::function_call => {
                     return Ok(Expression::FunctionCall(self.parse_fn_call(inner_pair)?));
@@ -57,12 +36,21 @@ impl CorrozyParserImpl {
                 Rule::expression => {
                     return Ok(Expression::Parenthesized(Box::new(self.parse_expression(inner_pair)?)));
                 }
+                Rule::closure_expression => {
+                    return Err(CorrozyError::Unsupported("closures").into());
+                }
+                Rule::array_literal => {
+                    let elements = inner_pair.into_inner()
+                        .map(|element| self.parse_expression(element))
+                        .collect::<Result<Vec<_>>>()?;
+                    return Ok(Expression::ArrayLiteral { elements });
+                }
                 _ => {
-                    return Err(anyhow!("Unknown primary expression rule: {:?}", inner_pair.as_rule()));
+                    return Err(CorrozyError::unexpected_rule(inner_pair.as_rule(), "primary expression").into());
                 }
             }
         }
-        Err(anyhow!("Unknown primary expression"))
+        Err(CorrozyError::missing("primary expression", "value").into())
     }
 
     pub fn parse_define_type(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<String> {
@@ -74,29 +62,112 @@ impl CorrozyParserImpl {
                 _ => {}
             }
         }
-        Err(anyhow!("No type annotation found"))
+        Err(CorrozyError::missing("type definition", "type").into())
     }
 
     fn parse_type_annotation(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<String> {
+        let inner_pair = pair.into_inner().next()
+            .ok_or(CorrozyError::missing("type annotation", "type"))?;
+
+        // Types keep the Corrozy spelling without spaces: `int|float`, `A&B`, `int[]`, `string?`
+        match inner_pair.as_rule() {
+            Rule::union_type => {
+                let members = inner_pair.into_inner()
+                    .map(|member| self.parse_single_type(member))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(members.join("|"))
+            }
+            Rule::intersection_type => {
+                let members: Vec<&str> = inner_pair.into_inner()
+                    .map(|member| member.as_str())
+                    .collect();
+                Ok(members.join("&"))
+            }
+            Rule::single_type => self.parse_single_type(inner_pair),
+            _ => Err(CorrozyError::unexpected_rule(inner_pair.as_rule(), "type annotation").into()),
+        }
+    }
+
+    fn parse_single_type(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<String> {
+        let mut type_name: Option<String> = None;
+        let mut nullable = false;
+
         for inner_pair in pair.into_inner() {
             match inner_pair.as_rule() {
+                Rule::array_type => {
+                    type_name = Some(self.parse_array_type(inner_pair)?);
+                }
+                Rule::generic_type => {
+                    type_name = Some(self.parse_generic_type(inner_pair)?);
+                }
                 Rule::basic_type | Rule::custom_type => {
-                    return Ok(inner_pair.as_str().to_string());
+                    type_name = Some(inner_pair.as_str().to_string());
+                }
+                Rule::nullable_marker => {
+                    nullable = true;
+                }
+                _ => {}
+            }
+        }
+
+        let type_name = type_name.ok_or(CorrozyError::missing("type annotation", "type"))?;
+        Ok(if nullable { format!("{}?", type_name) } else { type_name })
+    }
+
+    fn parse_array_type(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<String> {
+        let text = pair.as_str();
+        let element = pair.into_inner().next()
+            .ok_or(CorrozyError::missing("array type", "element type"))?;
+
+        // Everything after the element type is the `[]` suffixes
+        let dimensions = &text[element.as_str().len()..];
+        let element_type = match element.as_rule() {
+            Rule::generic_type => self.parse_generic_type(element)?,
+            _ => element.as_str().to_string(),
+        };
+
+        Ok(format!("{}{}", element_type, dimensions))
+    }
+
+    pub fn parse_generic_type(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<String> {
+        let mut name = String::new();
+        let mut arguments = Vec::new();
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::custom_type => {
+                    name = inner_pair.as_str().to_string();
+                }
+                Rule::type_annotation => {
+                    arguments.push(self.parse_type_annotation(inner_pair)?);
                 }
                 _ => {}
             }
         }
-        Err(anyhow!("Invalid type annotation"))
+
+        Ok(format!("{}<{}>", name, arguments.join(",")))
+    }
+
+    /// Generic parameters of a function, method or class: `<K, V>`
+    pub fn parse_type_parameters(&mut self, pair: pest::iterators::Pair<Rule>) -> Vec<String> {
+        pair.into_inner()
+            .map(|param_pair| param_pair.as_str().to_string())
+            .collect()
     }
 
     pub fn parse_declaration_declaration(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<AstNode> {
+        let span = Span::from_pair(&pair);
         let is_constant = pair.as_rule() == Rule::constant_declaration;
         let mut var_type: Option<String> = None;
+        let mut mutable = false;
         let mut name = String::new();
         let mut value = None;
         
         for inner_pair in pair.into_inner() {
             match inner_pair.as_rule() {
+                Rule::mut_keyword => {
+                    mutable = true;
+                }
                 Rule::define_type => {
                     var_type = Some(self.parse_define_type(inner_pair)?);
                 }
@@ -110,12 +181,12 @@ impl CorrozyParserImpl {
             }
         }
         
-        let val = value.ok_or_else(|| anyhow!("Declaration missing value"))?;
+        let val = value.ok_or(CorrozyError::missing("declaration", "value"))?;
         
         if is_constant {
-            Ok(AstNode::ConstantDeclaration { name, const_type: var_type, value: val })
+            Ok(AstNode::ConstantDeclaration { name, const_type: var_type, value: val, span })
         } else {
-            Ok(AstNode::VariableDeclaration { var_type, name, value: val })
+            Ok(AstNode::VariableDeclaration { var_type, name, value: val, mutable, span })
         }
     }
 }
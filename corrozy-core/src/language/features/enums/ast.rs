@@ -0,0 +1,9 @@
+use crate::language::{Span, features::expression::ast::Expression};
+
+#[derive(Debug, Clone)]
+pub struct EnumCase {
+    pub name: String,
+    /// Only set on backed enums
+    pub value: Option<Box<Expression>>,
+    pub span: Span,
+}
@@ -0,0 +1,121 @@
+use std::rc::Rc;
+
+use anyhow::{Ok, Result};
+
+use crate::{Config, codegen::CodeGenerator, language::features::{class::{ast::ClassMember, generator::ClassGenerator}, enums::ast::EnumCase, expression::generator::ExpressionGen, types::generator::{class_name, generate_class_doc}}};
+
+/// Everything before the enum body: `enum Status: string implements HasLabel`
+pub struct EnumHeader<'a> {
+    pub name: &'a str,
+    pub backing_type: Option<&'a str>,
+    pub implements: &'a [String],
+}
+
+pub struct EnumGenerator {
+    config: Rc<Config>,
+    class_gen: ClassGenerator,
+}
+
+impl EnumGenerator {
+    pub fn new(config: Rc<Config>) -> Self {
+        Self {
+            class_gen: ClassGenerator::new(config.clone()),
+            config,
+        }
+    }
+
+    pub fn generate(
+        &self,
+        header: EnumHeader,
+        cases: &[EnumCase],
+        members: &[ClassMember],
+        expression_gen: &ExpressionGen,
+        code_gen: &CodeGenerator,
+    ) -> Result<String> {
+        self.config.transpiler.require_php(8, 1, "Enums")?;
+        let mut result = generate_class_doc(&[], None, header.implements);
+        result.push_str(&format!("enum {}", header.name));
+        if let Some(backing_type) = header.backing_type {
+            result.push_str(&format!(": {}", backing_type));
+        }
+        if !header.implements.is_empty() {
+            let interfaces: Vec<&str> = header.implements.iter().map(|interface| class_name(interface)).collect();
+            result.push_str(&format!(" implements {}", interfaces.join(", ")));
+        }
+        result.push_str(" {\n");
+
+        for case in cases {
+            match &case.value {
+                Some(value) => {
+                    let value_php = expression_gen.generate(value, None)?;
+                    result.push_str(&format!("    case {} = {};\n", case.name, value_php));
+                }
+                None => result.push_str(&format!("    case {};\n", case.name)),
+            }
+        }
+
+        if !cases.is_empty() && !members.is_empty() {
+            result.push('\n');
+        }
+        result.push_str(&self.class_gen.generate_members(members, &[], expression_gen, code_gen)?);
+        result.push_str("}\n");
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::{codegen::CodeGenerator, utils::test_utils::{default_corrozy_config, generate_php_snippet, parse_with_error}};
+
+    #[test]
+    fn test_backed_enum_generation() {
+        let php = generate_php_snippet(r#"
+            enum Status: string implements HasLabel {
+                Active = 'active',
+                Inactive = 'inactive',
+
+                fn label(): string {
+                    return this.value;
+                }
+            }
+        "#);
+
+        assert_eq!(php, concat!(
+            "enum Status: string implements HasLabel {\n",
+            "    case Active = 'active';\n",
+            "    case Inactive = 'inactive';\n",
+            "\n",
+            "    public function label(): string {\n",
+            "        return $this->value;\n",
+            "    }\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    fn test_pure_enum_generation() {
+        let php = generate_php_snippet("enum Suit { Hearts, Spades }");
+
+        assert_eq!(php, "enum Suit {\n    case Hearts;\n    case Spades;\n}\n");
+    }
+
+    #[test]
+    fn test_static_access_generation() {
+        let php = generate_php_snippet("let status: Status = Status::from('active'); println(Status::Active.value);");
+
+        assert_eq!(php, "$status = Status::from('active');\necho Status::Active->value . \"\\n\";\n");
+    }
+
+    #[test]
+    fn test_enums_need_php_8_1() {
+        let mut config = default_corrozy_config();
+        config.transpiler.php_version = "8.0".to_string();
+        let ast = parse_with_error("enum Suit { Hearts, Spades }").unwrap();
+
+        let err = CodeGenerator::new(Rc::new(config)).generate(&ast).unwrap_err();
+        assert_eq!(err.to_string(), "1:1: Enums need PHP 8.1 or newer, php_version is 8.0");
+    }
+}
@@ -0,0 +1,119 @@
+use anyhow::{Result, anyhow};
+
+use crate::{CorrozyError, language::{AstNode, Span, features::{class::ast::ClassMember, enums::ast::EnumCase, expression::ast::{Expression, Literal}, function_call::ast::FunctionCallExp}, parser::{CorrozyParserImpl, Rule}}};
+
+impl CorrozyParserImpl {
+    pub fn parse_enum_declaration(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<AstNode> {
+        let span = Span::from_pair(&pair);
+        let mut name = String::new();
+        let mut backing_type = None;
+        let mut implements = Vec::new();
+        let mut cases = Vec::new();
+        let mut members = Vec::new();
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::custom_type => {
+                    name = inner_pair.as_str().to_string();
+                }
+                Rule::enum_backing => {
+                    backing_type = inner_pair.into_inner().next()
+                        .map(|type_pair| type_pair.as_str().to_string());
+                }
+                Rule::implements_clause => {
+                    implements = self.parse_implements_clause(inner_pair)?;
+                }
+                Rule::enum_case => {
+                    cases.push(self.parse_enum_case(inner_pair)?);
+                }
+                Rule::enum_member => {
+                    members.push(self.parse_enum_member(inner_pair)?);
+                }
+                _ => {}
+            }
+        }
+
+        for case in &cases {
+            self.check_enum_case(&name, backing_type.as_deref(), case)?;
+        }
+
+        Ok(AstNode::EnumDeclaration { name, backing_type, implements, cases, members, span })
+    }
+
+    fn parse_enum_case(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<EnumCase> {
+        let span = Span::from_pair(&pair);
+        let mut name = String::new();
+        let mut value = None;
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::custom_type => {
+                    name = inner_pair.as_str().to_string();
+                }
+                Rule::expression => {
+                    value = Some(Box::new(self.parse_expression(inner_pair)?));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(EnumCase { name, value, span })
+    }
+
+    fn parse_enum_member(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<ClassMember> {
+        let inner_pair = pair.into_inner().next()
+            .ok_or(CorrozyError::missing("enum member", "declaration"))?;
+
+        match inner_pair.as_rule() {
+            Rule::trait_use => {
+                Ok(ClassMember::TraitUse(self.parse_trait_use(inner_pair)))
+            }
+            Rule::method_declaration => {
+                Ok(ClassMember::Method(self.parse_method_declaration(inner_pair)?))
+            }
+            _ => Err(CorrozyError::unexpected_rule(inner_pair.as_rule(), "enum member").into())
+        }
+    }
+
+    /// Backed enums need a literal of the backing type on every case, pure enums none
+    fn check_enum_case(&self, enum_name: &str, backing_type: Option<&str>, case: &EnumCase) -> Result<()> {
+        let value = case.value.as_deref();
+
+        match (backing_type, value) {
+            (None, None) => Ok(()),
+            (None, Some(_)) => Err(anyhow!(
+                "Case `{}` of pure enum `{}` cannot have a value", case.name, enum_name
+            )),
+            (Some(backing_type), None) => Err(anyhow!(
+                "Case `{}` of enum `{}` needs a {} value", case.name, enum_name, backing_type
+            )),
+            (Some("int"), Some(Expression::Literal(Literal::Integer(_))))
+            | (Some("string"), Some(Expression::Literal(Literal::String(_)))) => Ok(()),
+            (Some(backing_type), Some(_)) => Err(anyhow!(
+                "Case `{}` of enum `{}` must be a {} literal", case.name, enum_name, backing_type
+            )),
+        }
+    }
+
+    pub fn parse_static_access(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
+        let mut inner_pairs = pair.into_inner();
+
+        let type_pair = inner_pairs.next()
+            .ok_or(CorrozyError::missing("static access", "type"))?;
+        let member_pair = inner_pairs.next()
+            .ok_or(CorrozyError::missing("static access", "member"))?;
+
+        let class_name = type_pair.as_str().to_string();
+
+        match member_pair.as_rule() {
+            Rule::function_call => {
+                let FunctionCallExp { name, args, .. } = self.parse_fn_call(member_pair)?;
+                Ok(Expression::StaticCall { class_name, method: name, args })
+            }
+            Rule::custom_type => {
+                Ok(Expression::StaticConstant { class_name, name: member_pair.as_str().to_string() })
+            }
+            _ => Err(CorrozyError::unexpected_rule(member_pair.as_rule(), "static access").into())
+        }
+    }
+}
@@ -1,9 +1,17 @@
-use crate::language::{Parameter, features::{closure::ast::ClosureBody, function_call::ast::FunctionCallExp, postfix::ast::PostfixSuffix}};
+use crate::language::{Parameter, Span, features::{assignment::ast::AssignmentOperator, closure::ast::ClosureBody, function_call::ast::FunctionCallExp, pattern_match::ast::MatchArm, postfix::ast::{PostfixSuffix, UpdateOperator}}};
 
 #[derive(Debug, Clone)]
 pub enum Expression {
     Literal(Literal),
-    Variable(String),
+    Variable {
+        name: String,
+        span: Span
+    },
+    /// A name the type checker resolved to a `const`, the parser only produces variables
+    Constant {
+        name: String,
+        span: Span
+    },
     FunctionCall(FunctionCallExp),
     Parenthesized(Box<Expression>),
     Closure {
@@ -23,10 +31,62 @@ pub enum Expression {
         op: BinaryOperator, 
         right: Box<Expression> 
     },
+    UnaryOp {
+        op: UnaryOperator,
+        operand: Box<Expression>
+    },
+    Assignment {
+        target: Box<Expression>,
+        op: AssignmentOperator,
+        value: Box<Expression>
+    },
+    Update {
+        op: UpdateOperator,
+        is_prefix: bool,
+        target: Box<Expression>
+    },
+    New {
+        class_name: String,
+        args: Vec<Expression>
+    },
+    This,
+    /// `Status::Active`
+    StaticConstant {
+        class_name: String,
+        name: String
+    },
+    /// `Status::from('active')`
+    StaticCall {
+        class_name: String,
+        method: String,
+        args: Vec<Expression>
+    },
+    Match {
+        subject: Box<Expression>,
+        arms: Vec<MatchArm>
+    },
+    /// `parent.method(args)`, `method` is `None` for the parent constructor
+    ParentCall {
+        method: Option<String>,
+        args: Vec<Expression>
+    },
 }
 
+impl Expression {
+    /// Where the expression starts, for the kinds that keep their position
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Self::Variable { span, .. } | Self::Constant { span, .. } => Some(*span),
+            Self::FunctionCall(call) => Some(call.span),
+            Self::Parenthesized(inner) => inner.span(),
+            Self::PostfixChain { base, .. } => base.span(),
+            _ => None,
+        }
+    }
+}
 
-#[derive(Debug, Clone)]
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum BinaryOperator {
     // Arithmetic
     Add,          // +
@@ -45,6 +105,9 @@ pub enum BinaryOperator {
     // Logical
     And,          // &&
     Or,           // ||
+
+    // Null coalescing
+    Coalesce,     // ??
 }
 
 impl BinaryOperator {
@@ -62,6 +125,7 @@ impl BinaryOperator {
             ">=" => Some(Self::GreaterEqual),
             "&&" => Some(Self::And),
             "||" => Some(Self::Or),
+            "??" => Some(Self::Coalesce),
             _ => None,
         }
     }
@@ -80,6 +144,54 @@ impl BinaryOperator {
             Self::GreaterEqual => ">=",
             Self::And => "&&",
             Self::Or => "||",
+            Self::Coalesce => "??",
+        }
+    }
+
+    /// Binding power of the operator, higher binds tighter. Mirrors PHP precedence.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            Self::Coalesce => 1,
+            Self::Or => 2,
+            Self::And => 3,
+            Self::Equal | Self::NotEqual => 4,
+            Self::Less | Self::Greater | Self::LessEqual | Self::GreaterEqual => 5,
+            Self::Add | Self::Subtract => 6,
+            Self::Multiply | Self::Divide => 7,
+        }
+    }
+
+    /// PHP rejects chained comparisons like `a == b == c` without parentheses
+    pub fn is_associative(&self) -> bool {
+        !matches!(
+            self,
+            Self::Equal | Self::NotEqual | Self::Less | Self::Greater | Self::LessEqual | Self::GreaterEqual
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnaryOperator {
+    Not,          // !
+    Negate,       // -
+    Plus,         // +
+}
+
+impl UnaryOperator {
+    pub fn parse_op(s: &str) -> Option<Self> {
+        match s {
+            "!" => Some(Self::Not),
+            "-" => Some(Self::Negate),
+            "+" => Some(Self::Plus),
+            _ => None,
+        }
+    }
+
+    pub fn to_php(&self) -> &'static str {
+        match self {
+            Self::Not => "!",
+            Self::Negate => "-",
+            Self::Plus => "+",
         }
     }
 }
@@ -90,15 +202,21 @@ pub enum Literal {
     Float(f64),
     String(StringType),
     Boolean(bool),
+    Null,
 }
 
 impl Literal {
     pub fn to_php(&self) -> String {
         match self {
+            // PHP reads `-9223372036854775808` as the negation of a float
+            Self::Integer(i64::MIN) => "PHP_INT_MIN".to_string(),
             Self::Integer(n) => n.to_string(),
+            // A whole number keeps its `.0`, without it PHP would read an int
+            Self::Float(f) if f.fract() == 0.0 => format!("{}.0", f),
             Self::Float(f) => f.to_string(),
             Self::String(s) => s.to_php(),
             Self::Boolean(b) => (if *b { "true" } else { "false" }).to_string(),
+            Self::Null => "null".to_string(),
         }
     }
 }
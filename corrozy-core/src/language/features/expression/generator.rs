@@ -2,18 +2,22 @@ use std::rc::Rc;
 
 use anyhow::{Ok, Result};
 
-use crate::{Config, codegen::CodeGenerator, language::features::{closure::{ClosureGenerator, ast::ClosureBody}, expression::ast::Expression, function::generator::FunctionGenerator, postfix::ast::PostfixSuffix}};
+use crate::{Config, CorrozyError, codegen::CodeGenerator, language::features::{closure::{ClosureGenerator, ast::ClosureBody}, expression::ast::{BinaryOperator, Expression, UnaryOperator}, function::generator::FunctionGenerator, pattern_match::generator::MatchGenerator, postfix::ast::PostfixSuffix}};
 
 pub struct ExpressionGen {
+    config: Rc<Config>,
     closure_gen: ClosureGenerator,
     function_gen: FunctionGenerator,
+    match_gen: MatchGenerator,
 }
 
 impl ExpressionGen {
     pub fn new(config: Rc<Config>) -> Self {
         Self {
-            closure_gen: ClosureGenerator::new(),
-            function_gen: FunctionGenerator::new(config),
+            closure_gen: ClosureGenerator::new(config.clone()),
+            function_gen: FunctionGenerator::new(config.clone()),
+            match_gen: MatchGenerator::new(config.clone()),
+            config,
         }
     }
 
@@ -26,9 +30,12 @@ impl ExpressionGen {
             Expression::Literal(lit) => {
                 Ok(lit.to_php())
             }
-            Expression::Variable(name) => {
+            Expression::Variable { name, .. } => {
                 Ok(format!("${}", name))
             }
+            Expression::Constant { name, .. } => {
+                Ok(name.to_uppercase())
+            }
             Expression::FunctionCall(function_call_exp) => {
                 let name = &function_call_exp.name;
                 let args = &function_call_exp.args;
@@ -38,10 +45,64 @@ impl ExpressionGen {
                 Ok(format!("{}({})", name, arg_strs.join(", ")))
             }
             Expression::BinaryOp { left, op, right } => {
-                let left_php = self.generate(left, None)?;
-                let right_php = self.generate(right, None)?;
+                let left_php = self.generate_operand(left, op, false)?;
+                let right_php = self.generate_operand(right, op, true)?;
                 Ok(format!("{} {} {}", left_php, op.to_php(), right_php))
             }
+            Expression::UnaryOp { op, operand } => {
+                let operand_php = self.generate(operand, None)?;
+
+                // `- -$x` must not collapse into the `--` decrement operator
+                let needs_parens = matches!(operand.as_ref(), Expression::BinaryOp { .. })
+                    || (*op != UnaryOperator::Not && operand_php.starts_with(op.to_php()));
+
+                if needs_parens {
+                    Ok(format!("{}({})", op.to_php(), operand_php))
+                } else {
+                    Ok(format!("{}{}", op.to_php(), operand_php))
+                }
+            }
+            Expression::Assignment { target, op, value } => {
+                let target_php = self.generate(target, None)?;
+                let value_php = self.generate(value, None)?;
+                Ok(format!("{} {} {}", target_php, op.to_php(), value_php))
+            }
+            Expression::Update { op, is_prefix, target } => {
+                let target_php = self.generate(target, None)?;
+                if *is_prefix {
+                    Ok(format!("{}{}", op.to_php(), target_php))
+                } else {
+                    Ok(format!("{}{}", target_php, op.to_php()))
+                }
+            }
+            Expression::New { class_name, args } => {
+                let arg_strs: Vec<String> = args.iter()
+                    .map(|arg| self.generate(arg, None))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(format!("new {}({})", class_name, arg_strs.join(", ")))
+            }
+            Expression::This => {
+                Ok("$this".to_string())
+            }
+            Expression::StaticConstant { class_name, name } => {
+                Ok(format!("{}::{}", class_name, name))
+            }
+            Expression::StaticCall { class_name, method, args } => {
+                let arg_strs: Vec<String> = args.iter()
+                    .map(|arg| self.generate(arg, None))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(format!("{}::{}({})", class_name, method, arg_strs.join(", ")))
+            }
+            Expression::Match { subject, arms } => {
+                self.match_gen.generate_expression(subject, arms, self)
+            }
+            Expression::ParentCall { method, args } => {
+                let arg_strs: Vec<String> = args.iter()
+                    .map(|arg| self.generate(arg, None))
+                    .collect::<Result<Vec<_>>>()?;
+                let method = method.as_deref().unwrap_or("__construct");
+                Ok(format!("parent::{}({})", method, arg_strs.join(", ")))
+            }
             Expression::Parenthesized(inner) => {
                 let inner_php = self.generate(inner, None)?;
                 Ok(format!("({})", inner_php))
@@ -66,6 +127,17 @@ impl ExpressionGen {
                         PostfixSuffix::Property(prop_name) => {
                             result = format!("{}->{}", result, prop_name);
                         }
+                        PostfixSuffix::NullsafeMethodCall(func_call) => {
+                            self.config.transpiler.require_php(8, 0, "Nullsafe `?.` calls")?;
+                            let arg_strs: Vec<String> = func_call.args.iter()
+                                .map(|arg| self.generate(arg, None))
+                                .collect::<Result<Vec<_>>>()?;
+                            result = format!("{}?->{}({})", result, func_call.name, arg_strs.join(", "));
+                        }
+                        PostfixSuffix::NullsafeProperty(prop_name) => {
+                            self.config.transpiler.require_php(8, 0, "Nullsafe `?.` properties")?;
+                            result = format!("{}?->{}", result, prop_name);
+                        }
                     }
                 }
 
@@ -76,10 +148,7 @@ impl ExpressionGen {
                 match body.to_owned() {
                     ClosureBody::Block(block) => {
                         let code_gen = code_gen_opt
-                                    .as_ref()
-                                    .ok_or_else(|| anyhow::anyhow!(
-                                        "Code generator is required for block closures"
-                                    ))?;
+                                    .ok_or(CorrozyError::missing("block closure", "code generator"))?;
 
                         let result = self.function_gen.generate_fn_headless(
                             params,
@@ -96,24 +165,50 @@ impl ExpressionGen {
                             params,
                             return_type,
                             body,
-                            None
+                            self,
+                            code_gen_opt
                         )?;
                         Ok(result)
                     }
                 }
             }
-            _ => {
-                Err(anyhow::anyhow!("Unsupported expression type for PHP generation"))
+            Expression::ArrayLiteral { .. } => {
+                Err(CorrozyError::Unsupported("array literals").into())
             }
         }
     }
+
+    /// Wraps a nested binary operand in parentheses only when PHP would otherwise bind it differently
+    fn generate_operand(
+        &self,
+        operand: &Expression,
+        parent_op: &BinaryOperator,
+        is_right: bool,
+    ) -> Result<String> {
+        let operand_php = self.generate(operand, None)?;
+
+        match operand {
+            Expression::BinaryOp { op, .. } => {
+                let needs_parens = op.precedence() < parent_op.precedence()
+                    || (op.precedence() == parent_op.precedence()
+                        && (is_right || !parent_op.is_associative()));
+
+                if needs_parens {
+                    Ok(format!("({})", operand_php))
+                } else {
+                    Ok(operand_php)
+                }
+            }
+            _ => Ok(operand_php),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::rc::Rc;
 
-    use crate::{codegen::CodeGenerator, language::parser::CorrozyParserImpl, utils::test_utils::default_corrozy_config};
+    use crate::{codegen::CodeGenerator, language::{Span, features::expression::{ast::{BinaryOperator, Expression, UnaryOperator}, generator::ExpressionGen}, parser::CorrozyParserImpl}, utils::test_utils::default_corrozy_config};
     
     #[test]
     fn test_raw_string_literal() {
@@ -136,4 +231,157 @@ mod tests {
 
         assert_eq!(php.trim(), "\"Hello, $name!\";");
     }
-}
\ No newline at end of file
+
+    fn variable(name: &str) -> Box<Expression> {
+        Box::new(Expression::Variable { name: name.to_string(), span: Span::default() })
+    }
+
+    fn binary(left: Box<Expression>, op: BinaryOperator, right: Box<Expression>) -> Box<Expression> {
+        Box::new(Expression::BinaryOp { left, op, right })
+    }
+
+    #[test]
+    fn test_binary_precedence_without_extra_parens() {
+        let mut parser = CorrozyParserImpl::new();
+        let ast = parser.parse("a + b * c || d;").unwrap();
+
+        let code_gen = CodeGenerator::new(Rc::new(default_corrozy_config()));
+        let php = code_gen.generate(&ast).unwrap();
+
+        assert_eq!(php.trim(), "$a + $b * $c || $d;");
+    }
+
+    #[test]
+    fn test_binary_lower_precedence_operand_gets_parens() {
+        let expression_gen = ExpressionGen::new(Rc::new(default_corrozy_config()));
+        // (a + b) * c
+        let expr = binary(
+            binary(variable("a"), BinaryOperator::Add, variable("b")),
+            BinaryOperator::Multiply,
+            variable("c"),
+        );
+
+        assert_eq!(expression_gen.generate(&expr, None).unwrap(), "($a + $b) * $c");
+    }
+
+    #[test]
+    fn test_binary_right_nested_same_precedence_gets_parens() {
+        let expression_gen = ExpressionGen::new(Rc::new(default_corrozy_config()));
+        // a - (b - c) vs (a - b) - c
+        let right_nested = binary(
+            variable("a"),
+            BinaryOperator::Subtract,
+            binary(variable("b"), BinaryOperator::Subtract, variable("c")),
+        );
+        let left_nested = binary(
+            binary(variable("a"), BinaryOperator::Subtract, variable("b")),
+            BinaryOperator::Subtract,
+            variable("c"),
+        );
+
+        assert_eq!(expression_gen.generate(&right_nested, None).unwrap(), "$a - ($b - $c)");
+        assert_eq!(expression_gen.generate(&left_nested, None).unwrap(), "$a - $b - $c");
+    }
+
+    #[test]
+    fn test_binary_chained_comparison_gets_parens() {
+        let expression_gen = ExpressionGen::new(Rc::new(default_corrozy_config()));
+        // PHP rejects `$a == $b == $c`
+        let expr = binary(
+            binary(variable("a"), BinaryOperator::Equal, variable("b")),
+            BinaryOperator::Equal,
+            variable("c"),
+        );
+
+        assert_eq!(expression_gen.generate(&expr, None).unwrap(), "($a == $b) == $c");
+    }
+
+    #[test]
+    fn test_unary_operators_generation() {
+        let mut parser = CorrozyParserImpl::new();
+        let ast = parser.parse("!isActive && -x < +y;").unwrap();
+
+        let code_gen = CodeGenerator::new(Rc::new(default_corrozy_config()));
+        let php = code_gen.generate(&ast).unwrap();
+
+        assert_eq!(php.trim(), "!$isActive && -$x < +$y;");
+    }
+
+    #[test]
+    fn test_nested_negation_does_not_emit_decrement() {
+        let mut parser = CorrozyParserImpl::new();
+        let ast = parser.parse("- -x;").unwrap();
+
+        let code_gen = CodeGenerator::new(Rc::new(default_corrozy_config()));
+        let php = code_gen.generate(&ast).unwrap();
+
+        assert_eq!(php.trim(), "-(-$x);");
+    }
+
+    #[test]
+    fn test_unary_over_binary_operand_gets_parens() {
+        let expression_gen = ExpressionGen::new(Rc::new(default_corrozy_config()));
+        let expr = Expression::UnaryOp {
+            op: UnaryOperator::Not,
+            operand: binary(variable("a"), BinaryOperator::And, variable("b")),
+        };
+
+        assert_eq!(expression_gen.generate(&expr, None).unwrap(), "!($a && $b)");
+    }
+
+    #[test]
+    fn test_negative_constant_generation() {
+        let mut parser = CorrozyParserImpl::new();
+        let ast = parser.parse("const MIN_TEMP: float = -273.15;").unwrap();
+
+        let code_gen = CodeGenerator::new(Rc::new(default_corrozy_config()));
+        let php = code_gen.generate(&ast).unwrap();
+
+        assert_eq!(php.trim(), "const MIN_TEMP = -273.15;");
+    }
+
+    #[test]
+    fn test_nullsafe_and_coalesce_generation() {
+        let mut parser = CorrozyParserImpl::new();
+        let ast = parser.parse("let city = user?.address?.city ?? 'unknown';").unwrap();
+
+        let code_gen = CodeGenerator::new(Rc::new(default_corrozy_config()));
+        let php = code_gen.generate(&ast).unwrap();
+
+        assert_eq!(php.trim(), "$city = $user?->address?->city ?? 'unknown';");
+    }
+
+    #[test]
+    fn test_nullsafe_method_call_generation() {
+        let mut parser = CorrozyParserImpl::new();
+        let ast = parser.parse("let name = repo.find(id)?.getName() ?? null;").unwrap();
+
+        let code_gen = CodeGenerator::new(Rc::new(default_corrozy_config()));
+        let php = code_gen.generate(&ast).unwrap();
+
+        assert_eq!(php.trim(), "$name = $repo->find($id)?->getName() ?? null;");
+    }
+
+    #[test]
+    fn test_nullsafe_needs_php_8() {
+        let mut config = default_corrozy_config();
+        config.transpiler.php_version = "7.4".to_string();
+        let ast = CorrozyParserImpl::new().parse("let city = user?.city;").unwrap();
+
+        let err = CodeGenerator::new(Rc::new(config)).generate(&ast).unwrap_err();
+        assert_eq!(err.to_string(), "1:1: Nullsafe `?.` properties need PHP 8.0 or newer, php_version is 7.4");
+    }
+
+    #[test]
+    fn test_coalesce_binds_looser_than_comparison() {
+        let expression_gen = ExpressionGen::new(Rc::new(default_corrozy_config()));
+        // (a ?? b) == c
+        let expr = binary(
+            binary(variable("a"), BinaryOperator::Coalesce, variable("b")),
+            BinaryOperator::Equal,
+            variable("c"),
+        );
+
+        assert_eq!(expression_gen.generate(&expr, None).unwrap(), "($a ?? $b) == $c");
+    }
+}
@@ -1,42 +1,133 @@
-use anyhow::{anyhow, Result};
+use std::{iter::Peekable, vec::IntoIter};
 
-use crate::language::{features::expression::ast::{BinaryOperator, Expression, Literal, StringType}, parser::{CorrozyParserImpl, Rule}};
+use anyhow::Result;
+
+use crate::{CorrozyError, language::{features::expression::ast::{BinaryOperator, Expression, Literal, StringType, UnaryOperator}, parser::{CorrozyParserImpl, Rule}}};
 
 
 impl CorrozyParserImpl {
 
     pub fn parse_expression(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
-        // expression = { binary_expression_or_single_postfix }
-        let inner_pair = pair.into_inner().next()
-            .ok_or_else(|| anyhow!("Expression is empty"))?;
+        // expression = { binary_expression_or_single_postfix ~ (assignment_operator ~ expression)? }
+        let mut inner_pairs = pair.into_inner();
+        let inner_pair = inner_pairs.next()
+            .ok_or(CorrozyError::missing("expression", "operand"))?;
 
-        match inner_pair.as_rule() {
+        let expr = match inner_pair.as_rule() {
             Rule::binary_expression_or_single_postfix => {
-                self.parse_binary_or_postfix_expression(inner_pair)
+                self.parse_binary_or_postfix_expression(inner_pair)?
             }
-            _ => Err(anyhow!("Unexpected rule inside expression: {:?}", inner_pair.as_rule()))
+            _ => return Err(CorrozyError::unexpected_rule(inner_pair.as_rule(), "expression").into())
+        };
+
+        match (inner_pairs.next(), inner_pairs.next()) {
+            (Some(op_pair), Some(value_pair)) => self.parse_assignment(expr, op_pair, value_pair),
+            _ => Ok(expr),
         }
     }
 
     pub fn parse_binary_or_postfix_expression(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
         let mut inner_pairs = pair.into_inner();
         
-        let mut left = self.parse_postfix_expression(inner_pairs.next().unwrap())?;
+        let first_pair = inner_pairs.next()
+            .ok_or(CorrozyError::missing("binary expression", "left operand"))?;
+        let first = self.parse_unary_expression(first_pair)?;
         
+        // The grammar yields a flat `operand (operator operand)*` list,
+        // the tree is built afterwards by precedence climbing
+        let mut rest: Vec<(BinaryOperator, Expression)> = Vec::new();
+
         while let (Some(op_pair), Some(right_pair)) = (inner_pairs.next(), inner_pairs.next()) {
             let operator = BinaryOperator::from_str(op_pair.as_str())
-                .ok_or_else(|| anyhow!("Unknown binary operator: {}", op_pair.as_str()))?;
-            
-            let right = self.parse_postfix_expression(right_pair)?;
+                .ok_or_else(|| CorrozyError::unknown_token(op_pair.as_str(), "binary operator"))?;
             
+            let right = self.parse_unary_expression(right_pair)?;
+            rest.push((operator, right));
+        }
+        
+        Ok(self.climb_precedence(first, 0, &mut rest.into_iter().peekable()))
+    }
+
+    pub fn parse_unary_expression(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
+        let mut operators: Vec<UnaryOperator> = Vec::new();
+        let mut operand = None;
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::unary_operator => {
+                    let operator = UnaryOperator::parse_op(inner_pair.as_str())
+                        .ok_or_else(|| CorrozyError::unknown_token(inner_pair.as_str(), "unary operator"))?;
+                    operators.push(operator);
+                }
+                // `-9223372036854775808` only fits an i64 with its sign, so a minus
+                // right before a plain integer is parsed as part of the number
+                Rule::postfix_expression if operators.last() == Some(&UnaryOperator::Negate) && is_integer(inner_pair.as_str()) => {
+                    operators.pop();
+                    let value = format!("-{}", inner_pair.as_str()).parse::<i64>()?;
+                    operand = Some(Expression::Literal(Literal::Integer(value)));
+                }
+                Rule::postfix_expression => {
+                    operand = Some(self.parse_postfix_expression(inner_pair)?);
+                }
+                Rule::prefix_update_expression => {
+                    operand = Some(self.parse_prefix_update_expression(inner_pair)?);
+                }
+                _ => return Err(CorrozyError::unexpected_rule(inner_pair.as_rule(), "unary expression").into()),
+            }
+        }
+
+        let mut expr = operand.ok_or(CorrozyError::missing("unary expression", "operand"))?;
+
+        // Operators apply from the innermost one outwards: `!-x` is `!(-x)`
+        for op in operators.into_iter().rev() {
+            expr = match (op, expr) {
+                // Keep `-5` as a plain literal so it stays a constant value
+                (UnaryOperator::Negate, Expression::Literal(Literal::Integer(n))) if n != i64::MIN => {
+                    Expression::Literal(Literal::Integer(-n))
+                }
+                (UnaryOperator::Negate, Expression::Literal(Literal::Float(f))) => {
+                    Expression::Literal(Literal::Float(-f))
+                }
+                (op, operand) => Expression::UnaryOp {
+                    op,
+                    operand: Box::new(operand),
+                },
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn climb_precedence(
+        &self,
+        mut left: Expression,
+        min_precedence: u8,
+        rest: &mut Peekable<IntoIter<(BinaryOperator, Expression)>>,
+    ) -> Expression {
+        while let Some((op, _)) = rest.peek() {
+            let op = *op;
+            if op.precedence() < min_precedence {
+                break;
+            }
+            let (_, mut right) = rest.next().unwrap();
+
+            // All operators are left-associative, so only tighter ones steal the right operand
+            while let Some((next_op, _)) = rest.peek() {
+                if next_op.precedence() <= op.precedence() {
+                    break;
+                }
+                let next_precedence = next_op.precedence();
+                right = self.climb_precedence(right, next_precedence, rest);
+            }
+
             left = Expression::BinaryOp {
                 left: Box::new(left),
-                op: operator,
+                op,
                 right: Box::new(right),
             };
         }
-        
-        Ok(left)
+
+        left
     }
     
     pub fn parse_literal(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Literal> {
@@ -57,10 +148,13 @@ impl CorrozyParserImpl {
                     let value = inner_pair.as_str() == "true";
                     return Ok(Literal::Boolean(value));
                 }
+                Rule::null => {
+                    return Ok(Literal::Null);
+                }
                 _ => {}
             }
         }
-        Err(anyhow!("Unknown literal type"))
+        Err(CorrozyError::missing("literal", "value").into())
     }
 
     pub fn parse_string_literal(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Literal> {
@@ -81,6 +175,10 @@ impl CorrozyParserImpl {
                 _ => {}
             }
         }
-        Err(anyhow!("Unknown string type"))
+        Err(CorrozyError::missing("string literal", "quotes").into())
     }
 }
+
+fn is_integer(text: &str) -> bool {
+    !text.is_empty() && text.bytes().all(|byte| byte.is_ascii_digit())
+}
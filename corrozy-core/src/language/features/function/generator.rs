@@ -2,7 +2,15 @@ use std::rc::Rc;
 
 use anyhow::Result;
 
-use crate::{Config, codegen::CodeGenerator, language::{Parameter, features::{block::{ast::Block, generator::BlockGenerator}, expression::generator::ExpressionGen}}};
+use crate::{Config, codegen::CodeGenerator, language::{Parameter, features::{block::{ast::Block, generator::BlockGenerator}, expression::generator::ExpressionGen, types::generator::{generate_signature_doc, to_native_type, to_php_param}}}};
+
+/// Everything before the function body: `fn first<T>(items: T[]): T`
+pub struct FunctionHeader<'a> {
+    pub name: &'a str,
+    pub type_params: &'a [String],
+    pub params: &'a [Parameter],
+    pub return_type: &'a Option<String>,
+}
 
 pub struct FunctionGenerator {
     config: Rc<Config>,
@@ -12,35 +20,37 @@ pub struct FunctionGenerator {
 impl FunctionGenerator {
     pub fn new(config: Rc<Config>) -> Self {
         Self {
-            config,
-            block_gen: BlockGenerator::new(),
+            config: config.clone(),
+            block_gen: BlockGenerator::new(config),
         }
     }
 
     pub fn generate(
         &self,
-        name: &str,
-        params: &[Parameter],
-        return_type: &Option<String>,
+        header: FunctionHeader,
         body: &Block,
         expression_gen: &ExpressionGen,
-        // node_generator: Rc<dyn Fn(&AstNode) -> Result<String>>,
         code_gen: &CodeGenerator, 
     ) -> Result<String> {
+        let FunctionHeader { name, type_params, params, return_type } = header;
         let mut result = String::new();
 
-        if self.config.transpiler.include_comments && (!params.is_empty() || return_type.is_some()) {
-            result.push_str(&self.generate_doc(params, return_type));
+        // Generics only exist in the docblock, so it is emitted for them even without comments
+        if self.config.transpiler.include_comments || !type_params.is_empty() {
+            result.push_str(&generate_signature_doc(type_params, params, return_type.as_deref(), &[], &self.config));
         }
 
-        result.push_str(&format!("function {}(", name));
-        let php_params: Vec<String> = params.iter().map(|param| {
-            format!("${}", param.name)
-        }).collect();
-        result.push_str(&php_params.join(", "));
-        result.push_str(") {\n");
-
-        // result.push_str(&self.generate_body(body)?);
+        // Generic parameters are erased to `mixed` in the native signature
+        let php_params: Vec<String> = params.iter()
+            .map(|param| to_php_param(param, type_params, &self.config))
+            .collect();
+        result.push_str(&format!("function {}({})", name, php_params.join(", ")));
+        if let Some(return_type) = return_type.as_deref()
+            .and_then(|return_type| to_native_type(return_type, type_params, &self.config))
+        {
+            result.push_str(&format!(": {}", return_type));
+        }
+        result.push_str(" {\n");
 
         result.push_str(&self.block_gen.generate(body, expression_gen, code_gen)?);
 
@@ -54,7 +64,6 @@ impl FunctionGenerator {
         params: &[Parameter],
         body: &Block,
         expression_gen: &ExpressionGen,
-        // node_generator: Rc<dyn Fn(&AstNode) -> Result<String>>,
         code_gen: &CodeGenerator, 
     ) -> Result<String> {
         let mut result = String::new();
@@ -73,26 +82,43 @@ impl FunctionGenerator {
 
         return Ok(result);
     }
+}
 
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
 
-    fn generate_doc (
-        &self,
-        params: &[Parameter], 
-        return_type: &Option<String>,
-    ) -> String {
-        let mut result = String::new();
-        result.push_str("/**\n");
+    use crate::{codegen::CodeGenerator, utils::test_utils::{default_corrozy_config, generate_php_snippet, parse_with_error}};
 
-        for param in params {
-            let php_type = param.param_type.as_deref().unwrap_or("mixed");
-            result.push_str(&format!(" * @param {} ${}\n", php_type, param.name));
-        }
+    #[test]
+    fn test_native_signature_types() {
+        let php = generate_php_snippet("fn pick(a: int|string, b: string?): int|float { return 1; }");
 
-        if let Some(ret_type) = return_type {
-            result.push_str(&format!(" * @return {}\n", ret_type));
-        }
+        assert!(php.starts_with("function pick(int|string $a, ?string $b): int|float {\n"), "{}", php);
+    }
+
+    #[test]
+    fn test_untyped_parameters_are_not_documented() {
+        let mut config = default_corrozy_config();
+        config.transpiler.include_comments = true;
+
+        let ast = parse_with_error("fn log(message, level: int) { println(message); }").unwrap();
+        let php = CodeGenerator::new(Rc::new(config)).generate(&ast).unwrap();
 
-        result.push_str(" */\n");
-        result
+        assert!(php.starts_with("function log($message, int $level) {\n"), "{}", php);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_docblock_follows_include_comments() {
+        let mut config = default_corrozy_config();
+        config.transpiler.php_version = "7.4".to_string();
+        let ast = parse_with_error("fn half(value: int|float): float { return value / 2; }").unwrap();
+
+        let php = CodeGenerator::new(Rc::new(config.clone())).generate(&ast).unwrap();
+        assert!(php.starts_with("function half($value): float {\n"), "{}", php);
+
+        config.transpiler.include_comments = true;
+        let php = CodeGenerator::new(Rc::new(config)).generate(&ast).unwrap();
+        assert!(php.starts_with("/**\n * @param int|float $value\n */\nfunction half($value): float {\n"), "{}", php);
+    }
+}
@@ -1,10 +1,11 @@
 use anyhow::Result;
 
-use crate::language::{AstNode, Parameter, features::{block::ast::Block, function_call::ast::FunctionCallExp}, parser::{CorrozyParserImpl, Rule}};
+use crate::language::{AstNode, Parameter, Span, features::{block::ast::Block, function_call::ast::FunctionCallExp}, parser::{CorrozyParserImpl, Rule}};
 
 
 impl CorrozyParserImpl {
     pub fn parse_fn_call(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<FunctionCallExp> {
+        let span = Span::from_pair(&pair);
         let mut name = String::new();
         let mut args = Vec::new();
         
@@ -24,12 +25,14 @@ impl CorrozyParserImpl {
             }
         }
         
-        Ok(FunctionCallExp{ name, args })
+        Ok(FunctionCallExp{ name, args, span })
     }
 
     pub fn parse_function_declaration(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<AstNode> {
+        let span = Span::from_pair(&pair);
         let mut return_type: Option<String> = None;
         let mut name = String::new();
+        let mut type_params = Vec::new();
         let mut params = Vec::new();
         let mut body: Block = Block::new();
 
@@ -38,6 +41,9 @@ impl CorrozyParserImpl {
                 Rule::identifier => {
                     name = inner_pair.as_str().to_string();
                 }
+                Rule::type_parameters => {
+                    type_params = self.parse_type_parameters(inner_pair);
+                }
                 Rule::parameter_list => {
                     params = self.parse_parameter_list(inner_pair)?;
                 }
@@ -53,20 +59,24 @@ impl CorrozyParserImpl {
 
         Ok(AstNode::FunctionDeclaration {
             name,
+            type_params,
             params,
             return_type,
             body: Box::new(body),
+            span,
         })
     }
 
     pub fn parse_parameter(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Parameter> {
         let mut name = String::new();
         let mut param_type = None;
+        let mut span = Span::default();
 
         for inner_pair in pair.into_inner() {
             match inner_pair.as_rule() {
                 Rule::identifier => {
                     name = inner_pair.as_str().to_string();
+                    span = Span::from_pair(&inner_pair);
                 }
                 Rule::define_type => {
                     param_type = Some(self.parse_define_type(inner_pair)?);
@@ -78,10 +88,11 @@ impl CorrozyParserImpl {
         Ok(Parameter {
             name,
             param_type,
+            span,
         })
     }
 
-    fn parse_parameter_list(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Vec<Parameter>> {
+    pub fn parse_parameter_list(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Vec<Parameter>> {
         let mut params = Vec::new();
         
         for inner_pair in pair.into_inner() {
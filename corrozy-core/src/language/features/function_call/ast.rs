@@ -1,8 +1,9 @@
-use crate::language::features::expression::ast::Expression;
+use crate::language::{Span, features::expression::ast::Expression};
 
 
 #[derive(Debug, Clone)]
 pub struct FunctionCallExp {
     pub name: String,
     pub args: Vec<Expression>,
+    pub span: Span,
 }
@@ -1,15 +1,17 @@
+use std::rc::Rc;
+
 use anyhow::{Ok, Result};
 
-use crate::{codegen::CodeGenerator, language::{AstNode, features::{block::{ast::Block, generator::BlockGenerator}, expression::{ast::Expression, generator::ExpressionGen}, if_else::ast::ElseClause}}, };
+use crate::{Config, CorrozyError, codegen::CodeGenerator, language::{AstNode, features::{block::{ast::Block, generator::BlockGenerator}, expression::{ast::Expression, generator::ExpressionGen}, if_else::ast::ElseClause}}, };
 
 pub struct IfElseGenerator{
     block_gen: BlockGenerator,
 }
 
 impl IfElseGenerator {
-    pub fn new() -> Self {
+    pub fn new(config: Rc<Config>) -> Self {
         Self {
-            block_gen: BlockGenerator::new(),
+            block_gen: BlockGenerator::new(config),
         }
     }
 
@@ -60,7 +62,7 @@ impl IfElseGenerator {
         match else_clause.as_ref() {
             ElseClause::ElseIf(ast_node) => {
                 match ast_node.as_ref() {
-                    AstNode::IfStatement { condition, then_block, else_clause } => {
+                    AstNode::IfStatement { condition, then_block, else_clause, .. } => {
                         let raw = self.generate(
                             condition,
                             then_block,
@@ -73,7 +75,7 @@ impl IfElseGenerator {
                         Ok(result)
                     }
                     _ => {
-                        Err(anyhow::anyhow!("Invalid ElseClause type"))
+                        Err(CorrozyError::missing("else if clause", "if statement").into())
                     }
                 }
             } 
@@ -1,9 +1,10 @@
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 
-use crate::language::{AstNode, features::{block::ast::Block, expression::ast::Expression, if_else::ast::ElseClause}, parser::{CorrozyParserImpl, Rule}};
+use crate::{CorrozyError, language::{AstNode, Span, features::{block::ast::Block, expression::ast::Expression, if_else::ast::ElseClause}, parser::{CorrozyParserImpl, Rule}}};
 
 impl CorrozyParserImpl {
     pub fn parse_if_statement(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<AstNode> {
+        let span = Span::from_pair(&pair);
         let mut condition: Option<Box<Expression>> = None;
         let mut then_block: Option<Box<Block>> = None;
         let mut else_clause: Option<Box<ElseClause>> = None;
@@ -26,9 +27,10 @@ impl CorrozyParserImpl {
         }
 
         Ok(AstNode::IfStatement {
-            condition: condition.expect("Grammar guarantees condition exists"),
-            then_block: then_block.expect("Grammar guarantees then_block exists"),
+            condition: condition.ok_or(CorrozyError::missing("if statement", "condition"))?,
+            then_block: then_block.ok_or(CorrozyError::missing("if statement", "block"))?,
             else_clause,
+            span,
         })
     }
 
@@ -45,6 +47,6 @@ impl CorrozyParserImpl {
                 _ => {}
             }
         }
-        Err(anyhow!("Empty else clause"))
+        Err(CorrozyError::missing("else clause", "block").into())
     }
 }
\ No newline at end of file
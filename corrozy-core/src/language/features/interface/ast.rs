@@ -0,0 +1,9 @@
+use crate::language::{Parameter, Span};
+
+#[derive(Debug, Clone)]
+pub struct MethodSignature {
+    pub name: String,
+    pub params: Vec<Parameter>,
+    pub return_type: Option<String>,
+    pub span: Span,
+}
@@ -0,0 +1,80 @@
+use std::rc::Rc;
+
+use anyhow::{Ok, Result};
+
+use crate::{Config, language::features::{interface::ast::MethodSignature, types::generator::{generate_signature_doc, to_php_param, to_php_type}}};
+
+pub struct InterfaceGenerator {
+    config: Rc<Config>,
+}
+
+impl InterfaceGenerator {
+    pub fn new(config: Rc<Config>) -> Self {
+        Self { config }
+    }
+
+    pub fn generate(
+        &self,
+        name: &str,
+        methods: &[MethodSignature],
+    ) -> Result<String> {
+        let mut result = format!("interface {} {{\n", name);
+
+        for method in methods {
+            let doc = generate_signature_doc(&[], &method.params, method.return_type.as_deref(), &[], &self.config);
+            for line in doc.lines() {
+                result.push_str(&format!("    {}\n", line));
+            }
+            result.push_str(&format!("    {};\n", self.generate_signature(method)));
+        }
+
+        result.push_str("}\n");
+
+        Ok(result)
+    }
+
+    /// Interface methods are always public in PHP
+    fn generate_signature(&self, method: &MethodSignature) -> String {
+        let params: Vec<String> = method.params.iter()
+            .map(|param| to_php_param(param, &[], &self.config))
+            .collect();
+
+        let mut result = format!("public function {}({})", method.name, params.join(", "));
+        if let Some(return_type) = method.return_type.as_deref()
+            .and_then(|return_type| to_php_type(return_type, &self.config))
+        {
+            result.push_str(&format!(": {}", return_type));
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_utils::generate_php_snippet;
+
+    #[test]
+    fn test_interface_generation() {
+        let php = generate_php_snippet(r#"
+            interface Shape {
+                fn area(): float;
+                fn scale(factor: float, origin);
+            }
+        "#);
+
+        assert_eq!(php, concat!(
+            "interface Shape {\n",
+            "    public function area(): float;\n",
+            "    public function scale(float $factor, $origin);\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    fn test_class_implements_generation() {
+        let php = generate_php_snippet("class Circle implements Shape, Named {}");
+
+        assert_eq!(php, "class Circle implements Shape, Named {\n}\n");
+    }
+}
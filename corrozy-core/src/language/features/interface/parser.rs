@@ -0,0 +1,66 @@
+use anyhow::{Result, anyhow};
+
+use crate::language::{AstNode, Span, features::interface::ast::MethodSignature, parser::{CorrozyParserImpl, Rule}};
+
+impl CorrozyParserImpl {
+    pub fn parse_interface_declaration(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<AstNode> {
+        let span = Span::from_pair(&pair);
+        let mut name = String::new();
+        let mut methods = Vec::new();
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::custom_type => {
+                    name = inner_pair.as_str().to_string();
+                }
+                Rule::method_signature => {
+                    methods.push(self.parse_method_signature(inner_pair)?);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(AstNode::InterfaceDeclaration { name, methods, span })
+    }
+
+    fn parse_method_signature(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<MethodSignature> {
+        let span = Span::from_pair(&pair);
+        let mut name = String::new();
+        let mut params = Vec::new();
+        let mut return_type = None;
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::identifier => {
+                    name = inner_pair.as_str().to_string();
+                }
+                Rule::parameter_list => {
+                    params = self.parse_parameter_list(inner_pair)?;
+                }
+                Rule::define_type => {
+                    return_type = Some(self.parse_define_type(inner_pair)?);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(MethodSignature { name, params, return_type, span })
+    }
+
+    pub fn parse_implements_clause(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Vec<String>> {
+        pair.into_inner()
+            .map(|parent_pair| self.parse_parent_type(parent_pair))
+            .collect()
+    }
+
+    /// Parent class or interface, with the type arguments it gets: `Repository<User>`
+    pub fn parse_parent_type(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<String> {
+        let inner_pair = pair.into_inner().next()
+            .ok_or_else(|| anyhow!("Parent type is empty"))?;
+
+        match inner_pair.as_rule() {
+            Rule::generic_type => self.parse_generic_type(inner_pair),
+            _ => Ok(inner_pair.as_str().to_string()),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::rc::Rc;
+
+use anyhow::{Ok, Result};
+
+use crate::{Config, CorrozyError, codegen::CodeGenerator, language::{AstNode, features::{block::{ast::Block, generator::BlockGenerator}, expression::{ast::Expression, generator::ExpressionGen}, loop_for::ast::ForInit}}};
+
+pub struct ForLoopGenerator {
+    block_gen: BlockGenerator,
+}
+
+impl ForLoopGenerator {
+    pub fn new(config: Rc<Config>) -> Self {
+        Self {
+            block_gen: BlockGenerator::new(config),
+        }
+    }
+
+    pub fn generate(
+        &self,
+        init: &Option<Box<ForInit>>,
+        condition: &Option<Box<Expression>>,
+        update: &Option<Box<Expression>>,
+        body: &Block,
+        expression_gen: &ExpressionGen,
+        code_gen: &CodeGenerator,
+    ) -> Result<String> {
+        let mut result = String::new();
+
+        let init_str = match init {
+            Some(init) => self.generate_init(init, expression_gen)?,
+            None => String::new(),
+        };
+        let condition_str = match condition {
+            Some(expr) => expression_gen.generate(expr, None)?,
+            None => String::new(),
+        };
+        let update_str = match update {
+            Some(expr) => expression_gen.generate(expr, None)?,
+            None => String::new(),
+        };
+
+        // PHP allows every clause to be empty: `for (;;)`
+        let mut header = init_str;
+        for clause in [condition_str, update_str] {
+            header.push(';');
+            if !clause.is_empty() {
+                header.push(' ');
+                header.push_str(&clause);
+            }
+        }
+
+        result.push_str(&format!("for ({}) {{\n", header));
+        result.push_str(&self.block_gen.generate(body, expression_gen, code_gen)?);
+        result.push_str("}\n");
+
+        Ok(result)
+    }
+
+    fn generate_init(
+        &self,
+        init: &ForInit,
+        expression_gen: &ExpressionGen,
+    ) -> Result<String> {
+        match init {
+            ForInit::VariableDeclaration(declaration) => {
+                match declaration.as_ref() {
+                    AstNode::VariableDeclaration { name, value, .. } => {
+                        let value_php = expression_gen.generate(value, None)?;
+                        Ok(format!("${} = {}", name, value_php))
+                    }
+                    _ => Err(CorrozyError::missing("for loop initializer", "declaration").into()),
+                }
+            }
+            ForInit::Expression(expr) => {
+                expression_gen.generate(expr, None)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::{codegen::CodeGenerator, language::parser::CorrozyParserImpl, utils::test_utils::default_corrozy_config};
+
+    fn generate(code: &str) -> String {
+        let mut parser = CorrozyParserImpl::new();
+        let ast = parser.parse(code).unwrap();
+
+        let code_gen = CodeGenerator::new(Rc::new(default_corrozy_config()));
+        code_gen.generate(&ast).unwrap()
+    }
+
+    #[test]
+    fn test_for_loop_generation() {
+        let php = generate("for (let i = 0; i < 10; next(i)) { println(i); }");
+
+        assert_eq!(php, "for ($i = 0; $i < 10; next($i)) {\n    echo $i . \"\\n\";\n}\n");
+    }
+
+    #[test]
+    fn test_for_loop_generation_empty_clauses() {
+        let php = generate("for (;;) { println(1); }");
+
+        assert_eq!(php, "for (;;) {\n    echo 1 . \"\\n\";\n}\n");
+    }
+}
@@ -1 +1,5 @@
-pub mod ast;
\ No newline at end of file
+pub mod generator;
+pub mod ast;
+pub mod parser;
+
+pub use generator::ForLoopGenerator;
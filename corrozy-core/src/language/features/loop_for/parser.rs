@@ -0,0 +1,66 @@
+use anyhow::Result;
+
+use crate::{CorrozyError, language::{AstNode, Span, features::{block::ast::Block, expression::ast::Expression, loop_for::ast::ForInit}, parser::{CorrozyParserImpl, Rule}}};
+
+impl CorrozyParserImpl {
+    pub fn parse_for_loop(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<AstNode> {
+        let span = Span::from_pair(&pair);
+        let mut init: Option<Box<ForInit>> = None;
+        let mut condition: Option<Box<Expression>> = None;
+        let mut update: Option<Box<Expression>> = None;
+        let mut body: Option<Box<Block>> = None;
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::for_init => {
+                    init = Some(Box::new(self.parse_for_init(inner_pair)?));
+                }
+                Rule::for_condition => {
+                    condition = Some(Box::new(self.parse_for_clause(inner_pair)?));
+                }
+                Rule::for_update => {
+                    update = Some(Box::new(self.parse_for_clause(inner_pair)?));
+                }
+                Rule::block => {
+                    body = Some(Box::new(self.parse_block(inner_pair)?));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(AstNode::ForLoop {
+            init,
+            condition,
+            update,
+            body: body.ok_or(CorrozyError::missing("for loop", "body"))?,
+            span,
+        })
+    }
+
+    fn parse_for_init(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<ForInit> {
+        let inner_pair = pair.into_inner().next()
+            .ok_or(CorrozyError::missing("for loop", "initializer"))?;
+
+        match inner_pair.as_rule() {
+            Rule::variable_declaration => {
+                let declaration = self.parse_declaration_declaration(inner_pair)?;
+                Ok(ForInit::VariableDeclaration(Box::new(declaration)))
+            }
+            Rule::expression_statement => {
+                match self.parse_expression_statement(inner_pair)? {
+                    AstNode::ExpressionStatement { expression, .. } => Ok(ForInit::Expression(expression)),
+                    _ => Err(CorrozyError::missing("for loop initializer", "expression").into()),
+                }
+            }
+            _ => Err(CorrozyError::unexpected_rule(inner_pair.as_rule(), "for loop initializer").into())
+        }
+    }
+
+    fn parse_for_clause(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
+        // for_condition = { expression } | for_update = { expression }
+        let inner_pair = pair.into_inner().next()
+            .ok_or(CorrozyError::missing("for loop clause", "expression"))?;
+
+        self.parse_expression(inner_pair)
+    }
+}
@@ -0,0 +1,53 @@
+use std::rc::Rc;
+
+use anyhow::{Ok, Result};
+
+use crate::{Config, codegen::CodeGenerator, language::features::{block::{ast::Block, generator::BlockGenerator}, expression::{ast::Expression, generator::ExpressionGen}}};
+
+pub struct WhileLoopGenerator {
+    block_gen: BlockGenerator,
+}
+
+impl WhileLoopGenerator {
+    pub fn new(config: Rc<Config>) -> Self {
+        Self {
+            block_gen: BlockGenerator::new(config),
+        }
+    }
+
+    pub fn generate(
+        &self,
+        condition: &Expression,
+        body: &Block,
+        expression_gen: &ExpressionGen,
+        code_gen: &CodeGenerator,
+    ) -> Result<String> {
+        let mut result = String::new();
+
+        let condition_str = expression_gen.generate(condition, None)?;
+
+        result.push_str(&format!("while ({}) {{\n", condition_str));
+        result.push_str(&self.block_gen.generate(body, expression_gen, code_gen)?);
+        result.push_str("}\n");
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::{codegen::CodeGenerator, language::parser::CorrozyParserImpl, utils::test_utils::default_corrozy_config};
+
+    #[test]
+    fn test_while_loop_generation() {
+        let mut parser = CorrozyParserImpl::new();
+        let ast = parser.parse("while (count < 3) { println(count); }").unwrap();
+
+        let code_gen = CodeGenerator::new(Rc::new(default_corrozy_config()));
+        let php = code_gen.generate(&ast).unwrap();
+
+        assert_eq!(php, "while ($count < 3) {\n    echo $count . \"\\n\";\n}\n");
+    }
+}
@@ -0,0 +1,29 @@
+use anyhow::Result;
+
+use crate::{CorrozyError, language::{AstNode, Span, features::{block::ast::Block, expression::ast::Expression}, parser::{CorrozyParserImpl, Rule}}};
+
+impl CorrozyParserImpl {
+    pub fn parse_while_loop(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<AstNode> {
+        let span = Span::from_pair(&pair);
+        let mut condition: Option<Box<Expression>> = None;
+        let mut body: Option<Box<Block>> = None;
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::expression => {
+                    condition = Some(Box::new(self.parse_expression(inner_pair)?));
+                }
+                Rule::block => {
+                    body = Some(Box::new(self.parse_block(inner_pair)?));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(AstNode::WhileLoop {
+            condition: condition.ok_or(CorrozyError::missing("while loop", "condition"))?,
+            body: body.ok_or(CorrozyError::missing("while loop", "body"))?,
+            span,
+        })
+    }
+}
@@ -7,5 +7,13 @@ pub mod if_else;
 pub mod statement;
 pub mod closure;
 pub mod loop_for;
+pub mod loop_while;
 pub mod function_call;
-pub mod postfix;
\ No newline at end of file
+pub mod postfix;
+pub mod assignment;
+pub mod class;
+pub mod interface;
+pub mod types;
+pub mod traits;
+pub mod enums;
+pub mod pattern_match;
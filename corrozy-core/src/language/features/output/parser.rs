@@ -1,11 +1,12 @@
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use pest::iterators::Pair;
 
-use crate::language::{AstNode, parser::{CorrozyParserImpl, Rule}};
+use crate::{CorrozyError, language::{AstNode, Span, parser::{CorrozyParserImpl, Rule}}};
 
 
 impl CorrozyParserImpl {
     pub fn parse_output_statement(&mut self, pair: Pair<Rule>) -> Result<AstNode> {
+        let span = Span::from_pair(&pair);
         let is_println = pair.as_rule() == Rule::println_statement;
         
         for inner_pair in pair.into_inner() {
@@ -13,10 +14,11 @@ impl CorrozyParserImpl {
                 return Ok(AstNode::PrintStatement {
                     expression: Box::new(self.parse_expression(inner_pair)?),
                     newline: is_println,
+                    span,
                 });
             }
         }
-        Err(anyhow!("Invalid output statement"))
+        Err(CorrozyError::missing("print statement", "expression").into())
     }
 }
 
@@ -38,7 +40,7 @@ mod tests {
             .expect("Parsed statements should not be empty");
 
         match pair {
-            AstNode::PrintStatement { expression, newline } => {
+            AstNode::PrintStatement { expression, newline, .. } => {
                 assert!(newline, "Expected newline to be true for println");
 
                 match *expression {
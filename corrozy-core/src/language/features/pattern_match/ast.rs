@@ -0,0 +1,27 @@
+use crate::language::features::{block::ast::Block, expression::ast::Expression};
+
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    /// Alternatives separated by `|`
+    pub patterns: Vec<MatchPattern>,
+    pub body: MatchArmBody,
+}
+
+impl MatchArm {
+    pub fn is_wildcard(&self) -> bool {
+        self.patterns.iter().any(|pattern| matches!(pattern, MatchPattern::Wildcard))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum MatchPattern {
+    /// `_`
+    Wildcard,
+    Value(Expression),
+}
+
+#[derive(Debug, Clone)]
+pub enum MatchArmBody {
+    Expression(Box<Expression>),
+    Block(Box<Block>),
+}
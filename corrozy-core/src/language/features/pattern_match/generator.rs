@@ -0,0 +1,361 @@
+use std::rc::Rc;
+
+use anyhow::{Ok, Result, anyhow};
+
+use crate::{Config, codegen::CodeGenerator, language::features::{block::generator::BlockGenerator, expression::{ast::Expression, generator::ExpressionGen}, pattern_match::ast::{MatchArm, MatchArmBody, MatchPattern}}};
+
+/// Variable holding the subject when an if-chain needs to compare it more than once
+const SUBJECT_VARIABLE: &str = "$__match";
+
+pub struct MatchGenerator {
+    config: Rc<Config>,
+    block_gen: BlockGenerator,
+}
+
+impl MatchGenerator {
+    pub fn new(config: Rc<Config>) -> Self {
+        Self {
+            block_gen: BlockGenerator::new(config.clone()),
+            config,
+        }
+    }
+
+    /// Whether a `match` statement is generated as a `switch` or an `if` chain,
+    /// PHP before 8.0 has no `match`
+    pub fn needs_statement_form(&self, arms: &[MatchArm]) -> bool {
+        arms.iter().any(|arm| matches!(arm.body, MatchArmBody::Block(_)))
+            || !self.config.transpiler.targets_php(8, 0)
+    }
+
+    /// PHP 8 `match`, only possible when every arm is an expression
+    pub fn generate_expression(
+        &self,
+        subject: &Expression,
+        arms: &[MatchArm],
+        expression_gen: &ExpressionGen,
+    ) -> Result<String> {
+        self.config.transpiler.require_php(8, 0, "`match` expressions")?;
+        let mut result = format!("match ({}) {{\n", expression_gen.generate(subject, None)?);
+
+        for arm in arms {
+            let MatchArmBody::Expression(body) = &arm.body else {
+                return Err(anyhow!("A `match` with block arms can only be used as a statement"));
+            };
+
+            let conditions = if arm.is_wildcard() {
+                "default".to_string()
+            } else {
+                self.generate_patterns(&arm.patterns, expression_gen)?.join(", ")
+            };
+            let body_php = expression_gen.generate(body, None)?;
+
+            // Nested multi-line expressions keep their relative indentation
+            result.push_str(&format!("    {} => {},\n", conditions, body_php.replace('\n', "\n    ")));
+        }
+
+        result.push('}');
+        Ok(result)
+    }
+
+    /// Statement form for arms with blocks: a `switch` when every pattern is an enum case,
+    /// where loose and strict comparison agree, an `if` chain with `===` otherwise
+    pub fn generate(
+        &self,
+        subject: &Expression,
+        arms: &[MatchArm],
+        expression_gen: &ExpressionGen,
+        code_gen: &CodeGenerator,
+    ) -> Result<String> {
+        let only_enum_cases = arms.iter()
+            .flat_map(|arm| &arm.patterns)
+            .all(|pattern| matches!(pattern, MatchPattern::Wildcard | MatchPattern::Value(Expression::StaticConstant { .. })));
+
+        if only_enum_cases {
+            self.generate_switch(subject, arms, expression_gen, code_gen)
+        } else {
+            self.generate_if_chain(subject, arms, expression_gen, code_gen)
+        }
+    }
+
+    fn generate_switch(
+        &self,
+        subject: &Expression,
+        arms: &[MatchArm],
+        expression_gen: &ExpressionGen,
+        code_gen: &CodeGenerator,
+    ) -> Result<String> {
+        let mut result = String::new();
+        let exhaustive = arms.iter().any(|arm| arm.is_wildcard());
+
+        // Without a `_` arm the subject is needed again for the error
+        let subject_php = if exhaustive {
+            expression_gen.generate(subject, None)?
+        } else {
+            self.reusable_subject(subject, expression_gen, &mut result)?
+        };
+        result.push_str(&format!("switch ({}) {{\n", subject_php));
+
+        for arm in arms {
+            if arm.is_wildcard() {
+                result.push_str("    default:\n");
+            } else {
+                for pattern in self.generate_patterns(&arm.patterns, expression_gen)? {
+                    result.push_str(&format!("    case {}:\n", pattern));
+                }
+            }
+
+            let (body, returns) = self.generate_body(&arm.body, expression_gen, code_gen)?;
+            for line in body.lines() {
+                if !line.trim().is_empty() {
+                    result.push_str("    ");
+                    result.push_str(line);
+                }
+                result.push('\n');
+            }
+            if !returns {
+                result.push_str("        break;\n");
+            }
+        }
+
+        if !exhaustive {
+            result.push_str("    default:\n");
+            result.push_str(&format!("        {}\n", unhandled(&subject_php)));
+        }
+
+        result.push_str("}\n");
+        Ok(result)
+    }
+
+    fn generate_if_chain(
+        &self,
+        subject: &Expression,
+        arms: &[MatchArm],
+        expression_gen: &ExpressionGen,
+        code_gen: &CodeGenerator,
+    ) -> Result<String> {
+        let mut result = String::new();
+        let subject_php = self.reusable_subject(subject, expression_gen, &mut result)?;
+
+        let mut conditional_arms = arms.iter().filter(|arm| !arm.is_wildcard()).peekable();
+        let wildcard_arm = arms.iter().find(|arm| arm.is_wildcard());
+
+        if conditional_arms.peek().is_none() {
+            // Only a wildcard arm, its body always runs, and without any arm nothing matches
+            match wildcard_arm {
+                Some(arm) => result.push_str(&self.generate_unindented_body(&arm.body, expression_gen, code_gen)?),
+                None => result.push_str(&format!("{}\n", unhandled(&subject_php))),
+            }
+            return Ok(result);
+        }
+
+        for (index, arm) in conditional_arms.enumerate() {
+            let condition = self.generate_patterns(&arm.patterns, expression_gen)?
+                .into_iter()
+                .map(|pattern| format!("{} === {}", subject_php, pattern))
+                .collect::<Vec<_>>()
+                .join(" || ");
+
+            if index == 0 {
+                result.push_str(&format!("if ({}) {{\n", condition));
+            } else {
+                result.push_str(&format!(" elseif ({}) {{\n", condition));
+            }
+            result.push_str(&self.generate_body(&arm.body, expression_gen, code_gen)?.0);
+            result.push('}');
+        }
+
+        result.push_str(" else {\n");
+        match wildcard_arm {
+            Some(arm) => result.push_str(&self.generate_body(&arm.body, expression_gen, code_gen)?.0),
+            None => result.push_str(&format!("    {}\n", unhandled(&subject_php))),
+        }
+        result.push('}');
+
+        result.push('\n');
+        Ok(result)
+    }
+
+    /// The subject when it is a variable or constant, otherwise it is stored
+    /// in `$__match` first so it is only evaluated once
+    fn reusable_subject(&self, subject: &Expression, expression_gen: &ExpressionGen, result: &mut String) -> Result<String> {
+        let subject_php = expression_gen.generate(subject, None)?;
+        if matches!(subject, Expression::Variable { .. } | Expression::Constant { .. }) {
+            return Ok(subject_php);
+        }
+
+        result.push_str(&format!("{} = {};\n", SUBJECT_VARIABLE, subject_php));
+        Ok(SUBJECT_VARIABLE.to_string())
+    }
+
+    fn generate_patterns(&self, patterns: &[MatchPattern], expression_gen: &ExpressionGen) -> Result<Vec<String>> {
+        patterns.iter()
+            .filter_map(|pattern| match pattern {
+                MatchPattern::Value(value) => Some(expression_gen.generate(value, None)),
+                MatchPattern::Wildcard => None,
+            })
+            .collect()
+    }
+
+    /// Arm body indented one level, and whether it ends with a `return`
+    fn generate_body(
+        &self,
+        body: &MatchArmBody,
+        expression_gen: &ExpressionGen,
+        code_gen: &CodeGenerator,
+    ) -> Result<(String, bool)> {
+        match body {
+            MatchArmBody::Block(block) => {
+                Ok((self.block_gen.generate(block, expression_gen, code_gen)?, block.return_statement.is_some()))
+            }
+            MatchArmBody::Expression(expr) => {
+                Ok((format!("    {};\n", expression_gen.generate(expr, None)?), false))
+            }
+        }
+    }
+
+    fn generate_unindented_body(
+        &self,
+        body: &MatchArmBody,
+        expression_gen: &ExpressionGen,
+        code_gen: &CodeGenerator,
+    ) -> Result<String> {
+        let (indented, _) = self.generate_body(body, expression_gen, code_gen)?;
+
+        Ok(indented.lines()
+            .map(|line| format!("{}\n", line.strip_prefix("    ").unwrap_or(line)))
+            .collect())
+    }
+}
+
+/// What PHP's own `match` does when no arm matches
+fn unhandled(subject_php: &str) -> String {
+    format!("throw new \\UnhandledMatchError('Unhandled match case ' . \\var_export({}, true));", subject_php)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::{codegen::CodeGenerator, utils::test_utils::{default_corrozy_config, generate_php_snippet, parse_with_error}};
+
+    #[test]
+    fn test_match_expression_generation() {
+        let php = generate_php_snippet(r#"let label = match code { 1 | 2 => "low", "x" => "unknown", _ => "high" };"#);
+
+        assert_eq!(php, concat!(
+            "$label = match ($code) {\n",
+            "    1, 2 => \"low\",\n",
+            "    \"x\" => \"unknown\",\n",
+            "    default => \"high\",\n",
+            "};\n",
+        ));
+    }
+
+    #[test]
+    fn test_returned_match_is_indented() {
+        let php = generate_php_snippet(r#"fn label(s: Status): string { return match s { Status::Active => "on", _ => "off" }; }"#);
+
+        assert!(php.contains(concat!(
+            "    return match ($s) {\n",
+            "        Status::Active => \"on\",\n",
+            "        default => \"off\",\n",
+            "    };\n",
+            "}\n",
+        )), "{}", php);
+    }
+
+    #[test]
+    fn test_match_with_blocks_generates_if_chain() {
+        let php = generate_php_snippet(r#"
+            fn describe(code: int) {
+                match code {
+                    1 | 2 => { println("low"); }
+                    3 => log("three"),
+                    _ => { println("high"); }
+                }
+            }
+        "#);
+
+        assert!(php.contains(concat!(
+            "    if ($code === 1 || $code === 2) {\n",
+            "        echo \"low\" . \"\\n\";\n",
+            "    } elseif ($code === 3) {\n",
+            "        log(\"three\");\n",
+            "    } else {\n",
+            "        echo \"high\" . \"\\n\";\n",
+            "    }\n",
+        )), "{}", php);
+    }
+
+    #[test]
+    fn test_match_over_enum_cases_generates_switch() {
+        let php = generate_php_snippet(r#"
+            match user.status() {
+                Status::Active | Status::Pending => { notify(user); }
+                Status::Inactive => { return; }
+            }
+        "#);
+
+        assert_eq!(php, concat!(
+            "$__match = $user->status();\n",
+            "switch ($__match) {\n",
+            "    case Status::Active:\n",
+            "    case Status::Pending:\n",
+            "        notify($user);\n",
+            "        break;\n",
+            "    case Status::Inactive:\n",
+            "        return;\n",
+            "    default:\n",
+            "        throw new \\UnhandledMatchError('Unhandled match case ' . \\var_export($__match, true));\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    fn test_match_without_wildcard_throws() {
+        let php = generate_php_snippet("let r = 0; match x { 1 => { r = 10; } 2 | 3 => { r = 20; } }");
+
+        assert!(php.ends_with(concat!(
+            "if ($x === 1) {\n",
+            "    $r = 10;\n",
+            "} elseif ($x === 2 || $x === 3) {\n",
+            "    $r = 20;\n",
+            "} else {\n",
+            "    throw new \\UnhandledMatchError('Unhandled match case ' . \\var_export($x, true));\n",
+            "}\n",
+        )), "{}", php);
+    }
+
+    #[test]
+    fn test_match_subject_is_evaluated_once() {
+        let php = generate_php_snippet("match next() { 1 => { println(1); } }");
+
+        assert_eq!(php, concat!(
+            "$__match = next();\n",
+            "if ($__match === 1) {\n",
+            "    echo 1 . \"\\n\";\n",
+            "} else {\n",
+            "    throw new \\UnhandledMatchError('Unhandled match case ' . \\var_export($__match, true));\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    fn test_match_before_php_8() {
+        let mut config = default_corrozy_config();
+        config.transpiler.php_version = "7.4".to_string();
+        let code_gen = CodeGenerator::new(Rc::new(config));
+
+        let php = code_gen.generate(&parse_with_error("match code { 1 => log('one'), _ => log('other') }").unwrap()).unwrap();
+        assert_eq!(php, concat!(
+            "if ($code === 1) {\n",
+            "    log('one');\n",
+            "} else {\n",
+            "    log('other');\n",
+            "}\n",
+        ));
+
+        let err = code_gen.generate(&parse_with_error("let label = match code { 1 => 'one', _ => 'other' };").unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "1:1: `match` expressions need PHP 8.0 or newer, php_version is 7.4");
+    }
+}
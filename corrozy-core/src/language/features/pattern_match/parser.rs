@@ -0,0 +1,74 @@
+use anyhow::Result;
+
+use crate::{CorrozyError, language::{AstNode, Span, features::{expression::ast::Expression, pattern_match::ast::{MatchArm, MatchArmBody, MatchPattern}}, parser::{CorrozyParserImpl, Rule}}};
+
+impl CorrozyParserImpl {
+    pub fn parse_match_expression(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
+        let mut subject: Option<Box<Expression>> = None;
+        let mut arms = Vec::new();
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::expression => {
+                    subject = Some(Box::new(self.parse_expression(inner_pair)?));
+                }
+                Rule::match_arm => {
+                    arms.push(self.parse_match_arm(inner_pair)?);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Expression::Match {
+            subject: subject.ok_or(CorrozyError::missing("match", "subject"))?,
+            arms,
+        })
+    }
+
+    fn parse_match_arm(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<MatchArm> {
+        let mut patterns = Vec::new();
+        let mut body = None;
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::match_pattern => {
+                    patterns.push(self.parse_match_pattern(inner_pair)?);
+                }
+                Rule::block => {
+                    body = Some(MatchArmBody::Block(Box::new(self.parse_block(inner_pair)?)));
+                }
+                Rule::expression => {
+                    body = Some(MatchArmBody::Expression(Box::new(self.parse_expression(inner_pair)?)));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(MatchArm {
+            patterns,
+            body: body.ok_or(CorrozyError::missing("match arm", "body"))?,
+        })
+    }
+
+    fn parse_match_pattern(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<MatchPattern> {
+        let inner_pair = pair.into_inner().next()
+            .ok_or(CorrozyError::missing("match pattern", "value"))?;
+
+        match inner_pair.as_rule() {
+            Rule::wildcard_pattern => Ok(MatchPattern::Wildcard),
+            Rule::expression => Ok(MatchPattern::Value(self.parse_expression(inner_pair)?)),
+            _ => Err(CorrozyError::unexpected_rule(inner_pair.as_rule(), "match pattern").into())
+        }
+    }
+
+    pub fn parse_match_statement(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<AstNode> {
+        let span = Span::from_pair(&pair);
+        let inner_pair = pair.into_inner().next()
+            .ok_or(CorrozyError::missing("match statement", "expression"))?;
+
+        Ok(AstNode::ExpressionStatement {
+            expression: Box::new(self.parse_match_expression(inner_pair)?),
+            span,
+        })
+    }
+}
@@ -4,5 +4,32 @@ use crate::language::features::{expression::ast::Expression, function_call::ast:
 pub enum PostfixSuffix {
     Index(Box<Expression>),
     Property(String),
-    MethodCall(FunctionCallExp)
-}
\ No newline at end of file
+    MethodCall(FunctionCallExp),
+    /// `?.name`
+    NullsafeProperty(String),
+    /// `?.name()`
+    NullsafeMethodCall(FunctionCallExp),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpdateOperator {
+    Increment,    // ++
+    Decrement,    // --
+}
+
+impl UpdateOperator {
+    pub fn parse_op(s: &str) -> Option<Self> {
+        match s {
+            "++" => Some(Self::Increment),
+            "--" => Some(Self::Decrement),
+            _ => None,
+        }
+    }
+
+    pub fn to_php(&self) -> &'static str {
+        match self {
+            Self::Increment => "++",
+            Self::Decrement => "--",
+        }
+    }
+}
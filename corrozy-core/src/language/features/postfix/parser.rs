@@ -0,0 +1,101 @@
+use anyhow::{Result, anyhow};
+
+use crate::{CorrozyError, language::{features::{expression::ast::Expression, postfix::ast::{PostfixSuffix, UpdateOperator}}, parser::{CorrozyParserImpl, Rule}}};
+
+impl CorrozyParserImpl {
+    pub fn parse_postfix_expression(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
+        let mut inner_pairs = pair.into_inner();
+
+        let base_pair = inner_pairs.next().ok_or(CorrozyError::missing("postfix expression", "base"))?;
+        let base_expr = self.parse_primary_expression(base_pair)?;
+
+        let mut suffixes: Vec<PostfixSuffix> = Vec::new();
+        let mut update_op: Option<UpdateOperator> = None;
+        
+        for suffix_pair in inner_pairs {
+            match suffix_pair.as_rule() {
+                Rule::expression => {
+                    let index_expr = self.parse_expression(suffix_pair)?;
+                    suffixes.push(PostfixSuffix::Index(Box::new(index_expr)));
+                }
+                
+                Rule::function_call => {
+                    let func_call = self.parse_fn_call(suffix_pair)?;
+                    suffixes.push(PostfixSuffix::MethodCall(func_call));
+                }
+
+                Rule::identifier => {
+                    let name = suffix_pair.as_str().to_string();
+                    suffixes.push(PostfixSuffix::Property(name));
+                }
+
+                Rule::nullsafe_access => {
+                    suffixes.push(self.parse_nullsafe_access(suffix_pair)?);
+                }
+
+                Rule::update_operator => {
+                    update_op = Some(self.parse_update_operator(suffix_pair)?);
+                }
+
+                _ => return Err(CorrozyError::unexpected_rule(suffix_pair.as_rule(), "postfix expression").into()),
+            }
+        }
+
+        let expr = if suffixes.is_empty() {
+            base_expr
+        } else {
+            Expression::PostfixChain {
+                base: Box::new(base_expr),
+                suffixes,
+            }
+        };
+
+        match update_op {
+            Some(op) => self.build_update(op, false, expr),
+            None => Ok(expr),
+        }
+    }
+
+    fn parse_nullsafe_access(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<PostfixSuffix> {
+        let inner_pair = pair.into_inner().next()
+            .ok_or(CorrozyError::missing("nullsafe access", "member"))?;
+
+        match inner_pair.as_rule() {
+            Rule::function_call => Ok(PostfixSuffix::NullsafeMethodCall(self.parse_fn_call(inner_pair)?)),
+            Rule::identifier => Ok(PostfixSuffix::NullsafeProperty(inner_pair.as_str().to_string())),
+            _ => Err(CorrozyError::unexpected_rule(inner_pair.as_rule(), "nullsafe access").into()),
+        }
+    }
+
+    pub fn parse_prefix_update_expression(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<Expression> {
+        let mut inner_pairs = pair.into_inner();
+
+        let op_pair = inner_pairs.next().ok_or(CorrozyError::missing("prefix update", "operator"))?;
+        let op = self.parse_update_operator(op_pair)?;
+
+        let target_pair = inner_pairs.next().ok_or(CorrozyError::missing("prefix update", "target"))?;
+        let target = self.parse_postfix_expression(target_pair)?;
+
+        self.build_update(op, true, target)
+    }
+
+    fn parse_update_operator(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<UpdateOperator> {
+        UpdateOperator::parse_op(pair.as_str())
+            .ok_or_else(|| CorrozyError::unknown_token(pair.as_str(), "update operator").into())
+    }
+
+    fn build_update(&mut self, op: UpdateOperator, is_prefix: bool, target: Expression) -> Result<Expression> {
+        if !self.is_assignable(&target) {
+            return Err(anyhow!(
+                "Invalid {} target: only variables, properties and indexes can be updated",
+                op.to_php()
+            ));
+        }
+
+        Ok(Expression::Update {
+            op,
+            is_prefix,
+            target: Box::new(target),
+        })
+    }
+}
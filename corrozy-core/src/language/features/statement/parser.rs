@@ -1,11 +1,18 @@
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 
-use crate::language::{AstNode, parser::{CorrozyParserImpl, Rule}};
+use crate::{CorrozyError, diagnostics::locate, language::{AstNode, Span, parser::{CorrozyParserImpl, Rule}}};
 
 impl CorrozyParserImpl { 
+    /// Errors found inside the statement point to where it starts
     pub fn parse_statement(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<AstNode> {
+        let span = Span::from_pair(&pair);
+        self.parse_statement_kind(pair)
+            .map_err(|e| locate(span, e))
+    }
+
+    fn parse_statement_kind(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<AstNode> {
         let inner_pair = pair.into_inner().next()
-            .ok_or_else(|| anyhow!("Statement is empty"))?;
+            .ok_or(CorrozyError::missing("statement", "declaration"))?;
         
         match inner_pair.as_rule() {
             Rule::variable_declaration | Rule::constant_declaration => {
@@ -20,6 +27,21 @@ impl CorrozyParserImpl {
             Rule::expression_statement => {
                 return self.parse_expression_statement(inner_pair);
             }
+            Rule::class_declaration => {
+                self.parse_class_declaration(inner_pair)
+            }
+            Rule::interface_declaration => {
+                self.parse_interface_declaration(inner_pair)
+            }
+            Rule::trait_declaration => {
+                self.parse_trait_declaration(inner_pair)
+            }
+            Rule::enum_declaration => {
+                self.parse_enum_declaration(inner_pair)
+            }
+            Rule::match_statement => {
+                self.parse_match_statement(inner_pair)
+            }
             Rule::if_statement => {
                 return self.parse_if_statement(inner_pair);
             }
@@ -29,7 +51,7 @@ impl CorrozyParserImpl {
             Rule::for_loop => {
                 return self.parse_for_loop(inner_pair);
             }
-            _ => Err(anyhow!("Unknown statement type: {:?}", inner_pair.as_rule()))
+            _ => Err(CorrozyError::unexpected_rule(inner_pair.as_rule(), "statement").into())
         }
     }
    
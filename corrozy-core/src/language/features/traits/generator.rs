@@ -0,0 +1,80 @@
+use std::rc::Rc;
+
+use anyhow::{Ok, Result};
+
+use crate::{Config, codegen::CodeGenerator, language::features::{class::{ast::ClassMember, generator::ClassGenerator}, expression::generator::ExpressionGen}};
+
+pub struct TraitGenerator {
+    class_gen: ClassGenerator,
+}
+
+impl TraitGenerator {
+    pub fn new(config: Rc<Config>) -> Self {
+        Self {
+            class_gen: ClassGenerator::new(config),
+        }
+    }
+
+    pub fn generate(
+        &self,
+        name: &str,
+        members: &[ClassMember],
+        expression_gen: &ExpressionGen,
+        code_gen: &CodeGenerator,
+    ) -> Result<String> {
+        let mut result = format!("trait {} {{\n", name);
+        result.push_str(&self.class_gen.generate_members(members, &[], expression_gen, code_gen)?);
+        result.push_str("}\n");
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_utils::generate_php_snippet;
+
+    #[test]
+    fn test_trait_generation() {
+        let php = generate_php_snippet(r#"
+            trait HasTimestamps {
+                use Clock;
+                protected createdAt: int = 0;
+
+                fn touch() {
+                    this.createdAt = now();
+                }
+            }
+        "#);
+
+        assert_eq!(php, concat!(
+            "trait HasTimestamps {\n",
+            "    use Clock;\n",
+            "\n",
+            "    protected int $createdAt = 0;\n",
+            "\n",
+            "    public function touch() {\n",
+            "        $this->createdAt = now();\n",
+            "    }\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    fn test_class_uses_traits() {
+        let php = generate_php_snippet(r#"
+            class Post {
+                use HasTimestamps, SoftDeletes;
+                title: string;
+            }
+        "#);
+
+        assert_eq!(php, concat!(
+            "class Post {\n",
+            "    use HasTimestamps, SoftDeletes;\n",
+            "\n",
+            "    public string $title;\n",
+            "}\n",
+        ));
+    }
+}
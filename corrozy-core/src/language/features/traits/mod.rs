@@ -0,0 +1,4 @@
+pub mod generator;
+pub mod parser;
+
+pub use generator::TraitGenerator;
@@ -0,0 +1,32 @@
+use anyhow::Result;
+
+use crate::language::{AstNode, Span, parser::{CorrozyParserImpl, Rule}};
+
+impl CorrozyParserImpl {
+    pub fn parse_trait_declaration(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<AstNode> {
+        let span = Span::from_pair(&pair);
+        let mut name = String::new();
+        let mut members = Vec::new();
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::custom_type => {
+                    name = inner_pair.as_str().to_string();
+                }
+                Rule::class_member => {
+                    members.push(self.parse_class_member(inner_pair)?);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(AstNode::TraitDeclaration { name, members, span })
+    }
+
+    pub fn parse_trait_use(&mut self, pair: pest::iterators::Pair<Rule>) -> Vec<String> {
+        pair.into_inner()
+            .filter(|inner_pair| inner_pair.as_rule() == Rule::custom_type)
+            .map(|inner_pair| inner_pair.as_str().to_string())
+            .collect()
+    }
+}
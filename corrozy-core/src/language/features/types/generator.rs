@@ -0,0 +1,348 @@
+use crate::{Config, language::Parameter};
+
+/// Maps a Corrozy type annotation to the type used in native PHP signatures,
+/// `None` when the target PHP version can't declare it
+pub fn to_php_type(type_str: &str, config: &Config) -> Option<String> {
+    let transpiler = &config.transpiler;
+
+    if let [_, _, ..] = split_top_level(type_str, '|').as_slice() {
+        if !transpiler.targets_php(8, 0) {
+            return None;
+        }
+
+        let mut members: Vec<String> = Vec::new();
+        let mut nullable = false;
+        for member in split_top_level(type_str, '|') {
+            let member = member.strip_suffix('?').inspect(|_| nullable = true).unwrap_or(member);
+            let php_member = to_php_type(member, config)?;
+            // `mixed` can't be part of a union, it already covers every member
+            if php_member == "mixed" {
+                return Some(php_member);
+            }
+            if !members.contains(&php_member) {
+                members.push(php_member);
+            }
+        }
+        if nullable {
+            members.push("null".to_string());
+        }
+        return Some(members.join("|"));
+    }
+
+    if let [_, _, ..] = split_top_level(type_str, '&').as_slice() {
+        return transpiler.targets_php(8, 1).then(|| type_str.to_string());
+    }
+
+    if let Some(inner) = type_str.strip_suffix('?') {
+        // `mixed` already includes null and can't be marked nullable
+        return match to_php_type(inner, config)?.as_str() {
+            "mixed" => Some("mixed".to_string()),
+            php_type => Some(format!("?{}", php_type)),
+        };
+    }
+
+    if type_str.ends_with("[]") {
+        return Some("array".to_string());
+    }
+
+    // Generic arguments are erased, `Collection<User>` is declared as `Collection`
+    if let Some((name, _)) = split_generic(type_str) {
+        return Some(if name == "Array" { "array".to_string() } else { name.to_string() });
+    }
+
+    match type_str {
+        "any" => transpiler.targets_php(8, 0).then(|| "mixed".to_string()),
+        _ => Some(type_str.to_string()),
+    }
+}
+
+/// Maps a Corrozy type annotation to the type used in PHPDoc tags
+pub fn to_phpdoc_type(type_str: &str) -> String {
+    if let members @ [_, _, ..] = split_top_level(type_str, '|').as_slice() {
+        let phpdoc_members: Vec<String> = members.iter().map(|member| to_phpdoc_type(member)).collect();
+        let mut members: Vec<String> = Vec::new();
+        for member in phpdoc_members {
+            if member == "mixed" {
+                return member;
+            }
+            for part in member.split('|') {
+                if !members.iter().any(|existing| existing == part) {
+                    members.push(part.to_string());
+                }
+            }
+        }
+        // PHPDoc lists `null` last: `int|float|null`
+        if let Some(position) = members.iter().position(|member| member == "null") {
+            let null = members.remove(position);
+            members.push(null);
+        }
+        return members.join("|");
+    }
+
+    if let members @ [_, _, ..] = split_top_level(type_str, '&').as_slice() {
+        let members: Vec<String> = members.iter().map(|member| to_phpdoc_type(member)).collect();
+        return members.join("&");
+    }
+
+    if let Some(inner) = type_str.strip_suffix('?') {
+        return match to_phpdoc_type(inner).as_str() {
+            "mixed" => "mixed".to_string(),
+            phpdoc_type => format!("{}|null", phpdoc_type),
+        };
+    }
+
+    if let Some(element) = type_str.strip_suffix("[]") {
+        return format!("array<{}>", to_phpdoc_type(element));
+    }
+
+    if let Some((name, arguments)) = split_generic(type_str) {
+        let arguments: Vec<String> = arguments.iter().map(|argument| to_phpdoc_type(argument)).collect();
+        let name = if name == "Array" { "array" } else { name };
+        return format!("{}<{}>", name, arguments.join(", "));
+    }
+
+    match type_str {
+        "int" | "string" | "bool" | "float" => type_str.to_string(),
+        // Enums and classes are documented by their own name
+        custom if custom.starts_with(|c: char| c.is_ascii_uppercase()) => custom.to_string(),
+        _ => "mixed".to_string(),
+    }
+}
+
+/// Whether the annotation accepts `null`
+pub fn is_nullable(type_str: &str) -> bool {
+    split_top_level(type_str, '|').into_iter().any(|member| member.ends_with('?') || member == "any")
+}
+
+/// Native type of an annotation that may use the generic parameters in `type_params`,
+/// which PHP can't declare and are erased to `mixed`
+pub fn to_native_type(type_str: &str, type_params: &[String], config: &Config) -> Option<String> {
+    to_php_type(&erase_type_params(type_str, type_params), config)
+}
+
+/// Typed PHP parameter, e.g. `string $name`, untyped when the type can't be declared natively
+pub fn to_php_param(param: &Parameter, type_params: &[String], config: &Config) -> String {
+    match param.param_type.as_deref().and_then(|param_type| to_native_type(param_type, type_params, config)) {
+        Some(php_type) => format!("{} ${}", php_type, param.name),
+        None => format!("${}", param.name),
+    }
+}
+
+/// Replaces every generic parameter in the annotation with `any`: `T[]` becomes `any[]`
+pub fn erase_type_params(type_str: &str, type_params: &[String]) -> String {
+    let mut result = String::new();
+    let mut word = String::new();
+
+    for c in type_str.chars().chain(std::iter::once(' ')) {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            word.push(c);
+            continue;
+        }
+        if type_params.contains(&word) {
+            result.push_str("any");
+        } else {
+            result.push_str(&word);
+        }
+        word.clear();
+        result.push(c);
+    }
+
+    result.pop();
+    result
+}
+
+/// Whether the annotation mentions any of the generic parameters in `type_params`
+pub fn mentions_type_params(type_str: &str, type_params: &[String]) -> bool {
+    type_str.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .any(|word| type_params.iter().any(|param| param == word))
+}
+
+/// PHPDoc block for what a signature can't say natively: a `@template` for each of its own
+/// `templates` and every type that uses a generic parameter or doesn't exist in the target
+/// PHP version. `outer_type_params` are in scope without being declared here, those of the
+/// class around a method or constructor. Empty when the native signature says it all
+pub fn generate_signature_doc(
+    templates: &[String],
+    params: &[Parameter],
+    return_type: Option<&str>,
+    outer_type_params: &[String],
+    config: &Config,
+) -> String {
+    let type_params = [outer_type_params, templates].concat();
+    let needs_doc = |type_str: &str| {
+        mentions_type_params(type_str, &type_params) || to_native_type(type_str, &type_params, config).is_none()
+    };
+
+    let mut tags: Vec<String> = templates.iter()
+        .map(|template| format!("@template {}", template))
+        .collect();
+
+    for param in params {
+        if let Some(param_type) = &param.param_type
+            && needs_doc(param_type)
+        {
+            tags.push(format!("@param {} ${}", to_phpdoc_type(param_type), param.name));
+        }
+    }
+
+    if let Some(return_type) = return_type
+        && needs_doc(return_type)
+    {
+        tags.push(format!("@return {}", to_phpdoc_type(return_type)));
+    }
+
+    generate_doc(&tags)
+}
+
+/// PHPDoc block of a class or enum: a `@template` for each of its `type_params` and the
+/// type arguments it passes to a generic parent, which PHP can't declare
+pub fn generate_class_doc(type_params: &[String], extends: Option<&str>, implements: &[String]) -> String {
+    let mut tags: Vec<String> = type_params.iter()
+        .map(|type_param| format!("@template {}", type_param))
+        .collect();
+
+    if let Some(parent) = extends.filter(|parent| split_generic(parent).is_some()) {
+        tags.push(format!("@extends {}", to_phpdoc_type(parent)));
+    }
+    for interface in implements.iter().filter(|interface| split_generic(interface).is_some()) {
+        tags.push(format!("@implements {}", to_phpdoc_type(interface)));
+    }
+
+    generate_doc(&tags)
+}
+
+/// Name of a parent class or interface without its type arguments: `Repository<User>` is `Repository`
+pub fn class_name(type_str: &str) -> &str {
+    split_generic(type_str).map_or(type_str, |(name, _)| name)
+}
+
+/// Docblock with one line per tag, empty without tags
+fn generate_doc(tags: &[String]) -> String {
+    if tags.is_empty() {
+        return String::new();
+    }
+
+    let mut result = String::from("/**\n");
+    for tag in tags {
+        result.push_str(&format!(" * {}\n", tag));
+    }
+    result.push_str(" */\n");
+    result
+}
+
+/// Splits on `separator` outside of generic arguments: `Map<K,V>|null` splits into two members
+pub(crate) fn split_top_level(type_str: &str, separator: char) -> Vec<&str> {
+    let mut members = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (index, c) in type_str.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            c if c == separator && depth == 0 => {
+                members.push(&type_str[start..index]);
+                start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+
+    members.push(&type_str[start..]);
+    members
+}
+
+/// `Map<K,V>` into `("Map", ["K", "V"])`, `None` when the type isn't a generic application
+pub(crate) fn split_generic(type_str: &str) -> Option<(&str, Vec<&str>)> {
+    let arguments = type_str.strip_suffix('>')?;
+    let (name, arguments) = arguments.split_once('<')?;
+
+    Some((name, split_top_level(arguments, ',')))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{language::Span, utils::test_utils::default_corrozy_config};
+
+    fn config_for(php_version: &str) -> Config {
+        let mut config = default_corrozy_config();
+        config.transpiler.php_version = php_version.to_string();
+        config
+    }
+
+    #[test]
+    fn test_native_types_follow_php_version() {
+        let php81 = config_for("8.1");
+        assert_eq!(to_php_type("int|float", &php81).as_deref(), Some("int|float"));
+        assert_eq!(to_php_type("int|string?", &php81).as_deref(), Some("int|string|null"));
+        assert_eq!(to_php_type("Countable&Traversable", &php81).as_deref(), Some("Countable&Traversable"));
+        assert_eq!(to_php_type("int[]", &php81).as_deref(), Some("array"));
+        assert_eq!(to_php_type("int[]|string[]", &php81).as_deref(), Some("array"));
+        assert_eq!(to_php_type("int|any", &php81).as_deref(), Some("mixed"));
+
+        let php80 = config_for("8.0");
+        assert_eq!(to_php_type("int|float", &php80).as_deref(), Some("int|float"));
+        assert_eq!(to_php_type("Countable&Traversable", &php80), None);
+
+        let php74 = config_for("7.4");
+        assert_eq!(to_php_type("int|float", &php74), None);
+        assert_eq!(to_php_type("any", &php74), None);
+        assert_eq!(to_php_type("string?", &php74).as_deref(), Some("?string"));
+    }
+
+    #[test]
+    fn test_phpdoc_types() {
+        assert_eq!(to_phpdoc_type("int|float"), "int|float");
+        assert_eq!(to_phpdoc_type("int?|float"), "int|float|null");
+        assert_eq!(to_phpdoc_type("A&B"), "A&B");
+        assert_eq!(to_phpdoc_type("int[]"), "array<int>");
+        assert_eq!(to_phpdoc_type("User[][]?"), "array<array<User>>|null");
+        assert_eq!(to_phpdoc_type("any[]"), "array<mixed>");
+    }
+
+    #[test]
+    fn test_signature_doc_only_lists_dropped_types() {
+        let params = vec![
+            Parameter { name: "value".to_string(), param_type: Some("int|float".to_string()), span: Span::default() },
+            Parameter { name: "label".to_string(), param_type: Some("string".to_string()), span: Span::default() },
+        ];
+
+        assert_eq!(
+            generate_signature_doc(&[], &params, Some("int[]|string?"), &[], &config_for("7.4")),
+            "/**\n * @param int|float $value\n * @return array<int>|string|null\n */\n"
+        );
+        assert_eq!(generate_signature_doc(&[], &params, Some("int"), &[], &config_for("8.0")), "");
+    }
+
+    #[test]
+    fn test_generic_types() {
+        let config = default_corrozy_config();
+        let type_params = vec!["T".to_string()];
+
+        assert_eq!(erase_type_params("Map<T,Tag>|T[]?", &type_params), "Map<any,Tag>|any[]?");
+        assert_eq!(to_native_type("T", &type_params, &config).as_deref(), Some("mixed"));
+        assert_eq!(to_native_type("T[]", &type_params, &config).as_deref(), Some("array"));
+        assert_eq!(to_native_type("Collection<T>", &type_params, &config).as_deref(), Some("Collection"));
+        assert_eq!(to_native_type("Array<int|string>?", &[], &config).as_deref(), Some("?array"));
+
+        assert_eq!(to_phpdoc_type("Array<T>"), "array<T>");
+        assert_eq!(to_phpdoc_type("Map<string,Array<int|float>>?"), "Map<string, array<int|float>>|null");
+        assert!(mentions_type_params("Collection<T>", &type_params));
+        assert!(!mentions_type_params("Tag", &type_params));
+    }
+
+    #[test]
+    fn test_signature_doc_for_generics() {
+        let params = vec![
+            Parameter { name: "items".to_string(), param_type: Some("T[]".to_string()), span: Span::default() },
+            Parameter { name: "limit".to_string(), param_type: Some("int".to_string()), span: Span::default() },
+        ];
+        let type_params = vec!["T".to_string()];
+
+        assert_eq!(
+            generate_signature_doc(&type_params, &params, Some("T"), &[], &default_corrozy_config()),
+            "/**\n * @template T\n * @param array<T> $items\n * @return T\n */\n"
+        );
+    }
+}
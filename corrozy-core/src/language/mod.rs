@@ -3,4 +3,5 @@ pub mod parser;
 // pub mod codegen;
 pub mod ast;
 
-pub use ast::main::*;
\ No newline at end of file
+pub use ast::main::*;
+pub use ast::span::Span;
\ No newline at end of file
@@ -1,8 +1,10 @@
-use anyhow::{anyhow, Ok, Result};
-use pest::Parser;
+use anyhow::Result;
+use pest::{Parser, error::InputLocation};
 use pest_derive::Parser;
 
-use crate::language::AstNode;
+use crate::{CorrozyError, diagnostics::{Diagnostic, Diagnostics, syntax::syntax_error}, language::{AstNode, Span}};
+
+use super::recovery::skip_statement;
 
 
 #[derive(Parser)]
@@ -17,8 +19,39 @@ impl CorrozyParserImpl {
     }
 
     pub fn parse(&mut self,  input: &str) -> Result<Vec<AstNode>> {
-        let pairs = CorrozyParser::parse(Rule::program, input)
-            .map_err(|e| anyhow!("Parse error: {}", e))?;
+        let (statements, diagnostics) = self.parse_recovering(input);
+
+        if diagnostics.is_empty() {
+            Ok(statements)
+        } else {
+            Err(Diagnostics(diagnostics).into())
+        }
+    }
+
+    /// Parses as much of `input` as possible: a statement with a syntax error is
+    /// reported and skipped up to the next `;` or `}`, so a single run finds
+    /// every error of the file. Returns the statements that could be parsed
+    pub fn parse_recovering(&mut self, input: &str) -> (Vec<AstNode>, Vec<Diagnostic>) {
+        // The expected tokens let syntax errors name a missing `;` or `)`
+        pest::set_error_detail(true);
+
+        let mut text = input.to_string();
+        let mut diagnostics = Vec::new();
+        let pairs = loop {
+            let error = match CorrozyParser::parse(Rule::program, &text) {
+                Ok(pairs) => break pairs,
+                Err(error) => error,
+            };
+            diagnostics.push(syntax_error(&error, &text));
+
+            let position = match error.location {
+                InputLocation::Pos(position) | InputLocation::Span((position, _)) => position,
+            };
+            if !skip_statement(&mut text, position) {
+                // Nothing left to skip, like a block that is never closed
+                return (Vec::new(), diagnostics);
+            }
+        };
 
         let mut statements = Vec::new();
         
@@ -27,7 +60,10 @@ impl CorrozyParserImpl {
                 Rule::program => {
                     for inner_pair in pair.into_inner() {
                         if inner_pair.as_rule() == Rule::statement {
-                            statements.push(self.parse_statement(inner_pair)?);
+                            match self.parse_statement(inner_pair) {
+                                Ok(statement) => statements.push(statement),
+                                Err(error) => diagnostics.extend(Diagnostics::from_error(error)),
+                            }
                         }
                     }
                 }
@@ -35,27 +71,20 @@ impl CorrozyParserImpl {
             }
         }
 
-        Ok(statements)
+        (statements, diagnostics)
     }
 
 
     pub fn parse_expression_statement(&mut self, pair: pest::iterators::Pair<Rule>) -> Result<AstNode> {
+        let span = Span::from_pair(&pair);
         for inner_pair in pair.into_inner() {
             if inner_pair.as_rule() == Rule::expression {
                 return Ok(AstNode::ExpressionStatement {
                     expression: Box::new(self.parse_expression(inner_pair)?),
+                    span,
                 });
             }
         }
-        Err(anyhow!("Invalid expression statement"))
-    }
-
-    // Coming soon
-    pub fn parse_while_loop(&mut self, _pair: pest::iterators::Pair<Rule>) -> Result<AstNode> {
-        todo!("While loops not implemented yet")
-    }
-    
-    pub fn parse_for_loop(&mut self, _pair: pest::iterators::Pair<Rule>) -> Result<AstNode> {
-        todo!("For loops not implemented yet")
+        Err(CorrozyError::missing("expression statement", "expression").into())
     }
 }
\ No newline at end of file
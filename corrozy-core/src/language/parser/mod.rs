@@ -1,3 +1,4 @@
 pub mod main;
+mod recovery;
 
 pub use main::*;
\ No newline at end of file
@@ -0,0 +1,112 @@
+/// Blanks the statement that failed to parse at `position`, from the end of the
+/// previous statement to the next `;` or `}`, so the parser can go on after it.
+/// Newlines are kept and every byte becomes a space, so offsets, lines and columns
+/// of the rest of the file don't move. Returns false when there is nothing left to skip
+pub fn skip_statement(text: &mut String, position: usize) -> bool {
+    let code = code_positions(text);
+    let bytes = text.as_bytes();
+    let position = position.min(bytes.len());
+    let is_code = |index: usize, byte: u8| code[index] && bytes[index] == byte;
+
+    let start = (0..position).rev()
+        .find(|&index| is_code(index, b';') || is_code(index, b'{') || is_code(index, b'}'))
+        .map_or(0, |index| index + 1);
+
+    // Blocks opened by the broken statement, like the body of an `if`, are skipped with it
+    let mut depth = 0;
+    let mut end = bytes.len();
+    let mut closing = None;
+    for index in position..bytes.len() {
+        if is_code(index, b'{') {
+            depth += 1;
+        } else if is_code(index, b'}') {
+            if depth == 0 {
+                end = index;
+                closing = Some(index);
+                break;
+            }
+            depth -= 1;
+            if depth == 0 {
+                end = index + 1;
+                break;
+            }
+        } else if is_code(index, b';') && depth == 0 {
+            end = index + 1;
+            break;
+        }
+    }
+
+    // A `}` that closes nothing is the broken statement itself
+    if text[start..end].trim().is_empty()
+        && let Some(closing) = closing
+    {
+        end = closing + 1;
+    }
+    if text[start..end].trim().is_empty() {
+        return false;
+    }
+
+    let blank: String = text[start..end].chars()
+        .map(|c| if c == '\n' { "\n".to_string() } else { " ".repeat(c.len_utf8()) })
+        .collect();
+    text.replace_range(start..end, &blank);
+    true
+}
+
+/// For every byte, whether it is code rather than part of a string or a comment
+fn code_positions(text: &str) -> Vec<bool> {
+    let bytes = text.as_bytes();
+    let mut code = vec![true; bytes.len()];
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let rest = &text[index..];
+        let skipped = if rest.starts_with("//") {
+            rest.find('\n').unwrap_or(rest.len())
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            comment.find("*/").map_or(rest.len(), |end| end + 4)
+        } else if bytes[index] == b'"' || bytes[index] == b'\'' {
+            let quote = bytes[index] as char;
+            rest[1..].find(quote).map_or(rest.len(), |end| end + 2)
+        } else {
+            0
+        };
+
+        if skipped > 0 {
+            code[index..index + skipped].fill(false);
+            index += skipped;
+        } else {
+            index += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::skip_statement;
+
+    fn skip(code: &str, position: usize) -> Option<String> {
+        let mut text = code.to_string();
+        skip_statement(&mut text, position).then_some(text)
+    }
+
+    #[test]
+    fn test_skips_to_next_semicolon() {
+        assert_eq!(skip("let a = 1;\nlet b = ;\nlet c = 3;", 19).unwrap(), "let a = 1;\n         \nlet c = 3;");
+        assert_eq!(skip("print(\"a;b\" +);\nlet c = 3;", 14).unwrap(), "               \nlet c = 3;");
+    }
+
+    #[test]
+    fn test_keeps_closing_brace_of_block() {
+        assert_eq!(skip("fn f() { return 1 }", 18).unwrap(), "fn f() {          }");
+        assert_eq!(skip("if (x ==) { print(1); }\nlet y = 2;", 8).unwrap(), "                       \nlet y = 2;");
+    }
+
+    #[test]
+    fn test_skips_unexpected_closing_brace() {
+        assert_eq!(skip("let a = 1; }", 11).unwrap(), "let a = 1;  ");
+        assert_eq!(skip("fn f() {", 8), None);
+    }
+}
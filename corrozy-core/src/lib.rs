@@ -1,8 +1,14 @@
 pub mod language;
 pub mod config;
+pub mod diagnostics;
+pub mod formatter;
+pub mod error;
 pub mod transpiler;
 pub mod codegen;
+pub mod semantic;
+pub mod typeck;
 pub mod utils;
 
 pub use transpiler::Transpiler;
-pub use config::Config;
\ No newline at end of file
+pub use config::Config;
+pub use error::CorrozyError;
\ No newline at end of file
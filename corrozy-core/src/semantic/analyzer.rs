@@ -0,0 +1,723 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Ok, Result, anyhow};
+
+use crate::{diagnostics::{Diagnostic, Label, codes, locate}, language::{AstNode, Parameter, Span, features::{block::ast::Block, class::ast::{ClassMember, ClassModifier, MethodDeclaration}, closure::ast::ClosureBody, enums::ast::EnumCase, pattern_match::ast::{MatchArm, MatchArmBody, MatchPattern}, expression::ast::{BinaryOperator, Expression, Literal, StringType}, postfix::ast::{PostfixSuffix, UpdateOperator}, types::generator::split_top_level}}, semantic::walk::ScopedWalk};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BindingKind {
+    Variable,
+    Constant,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Binding {
+    kind: BindingKind,
+    /// Declared with a nullable type and not narrowed by a `!= null` check
+    nullable: bool,
+    /// Declared with `let mut`, parameters can always be reassigned
+    mutable: bool,
+}
+
+/// Class or trait whose members are being checked
+struct ClassContext {
+    name: String,
+    /// Traits always count as having a parent, it is resolved where they are used
+    has_parent: bool,
+}
+
+/// A `match` whose patterns are all cases of the same enum, checked for
+/// exhaustiveness once every enum of the project is known
+#[derive(Debug, Clone)]
+pub struct EnumMatch {
+    pub enum_name: String,
+    pub cases: Vec<String>,
+    /// Statement the `match` is in
+    pub span: Span,
+}
+
+/// Checks that run over the whole AST before code generation
+pub struct SemanticAnalyzer {
+    scopes: Vec<HashMap<String, Binding>>,
+    classes: Vec<ClassContext>,
+    enum_matches: Vec<EnumMatch>,
+    require_let_mut: bool,
+    /// Statement being checked
+    span: Span,
+}
+
+impl Default for SemanticAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SemanticAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            scopes: vec![HashMap::new()],
+            classes: Vec::new(),
+            enum_matches: Vec::new(),
+            require_let_mut: false,
+            span: Span::default(),
+        }
+    }
+
+    /// Rejects reassigning variables that weren't declared with `let mut`
+    pub fn require_let_mut(mut self, required: bool) -> Self {
+        self.require_let_mut = required;
+        self
+    }
+
+    pub fn enum_matches(&self) -> &[EnumMatch] {
+        &self.enum_matches
+    }
+
+    pub fn analyze(&mut self, ast: &[AstNode]) -> Result<()> {
+        for node in ast {
+            self.check_node(node)?;
+        }
+        Ok(())
+    }
+
+    fn check_node(&mut self, node: &AstNode) -> Result<()> {
+        let outer = std::mem::replace(&mut self.span, node.span());
+        let result = self.check_node_kind(node)
+            .map_err(|e| locate(node.span(), e));
+        self.span = outer;
+        result
+    }
+
+    fn check_node_kind(&mut self, node: &AstNode) -> Result<()> {
+        match node {
+            AstNode::Program { statements, .. } => {
+                self.analyze(statements)
+            }
+            AstNode::ExpressionStatement { expression, .. } => {
+                self.check_expression(expression)
+            }
+            AstNode::VariableDeclaration { name, var_type, value, mutable, .. } => {
+                self.check_expression(value)?;
+                self.declare(name, BindingKind::Variable, var_type.as_deref(), *mutable)
+            }
+            AstNode::ConstantDeclaration { name, const_type, value, .. } => {
+                self.check_expression(value)?;
+                self.declare(name, BindingKind::Constant, const_type.as_deref(), false)
+            }
+            AstNode::PrintStatement { expression, .. } => {
+                self.check_expression(expression)
+            }
+            AstNode::FunctionDeclaration { params, body, .. } => {
+                self.check_block(body, params)
+            }
+            AstNode::ClassDeclaration { modifier, name, extends, members, .. } => {
+                if *modifier != Some(ClassModifier::Abstract)
+                    && let Some(method) = self.first_abstract_method(members)
+                {
+                    return Err(locate(method.span, anyhow!(
+                        "Class `{}` declares abstract method `{}` but is not abstract",
+                        name, method.name
+                    )));
+                }
+                self.check_class_members(name, extends.is_some(), members)
+            }
+            AstNode::EnumDeclaration { name, cases, members, .. } => {
+                if let Some(method) = self.first_abstract_method(members) {
+                    return Err(locate(
+                        method.span,
+                        anyhow!("Enum `{}` cannot declare abstract method `{}`", name, method.name),
+                    ));
+                }
+                self.check_enum_cases(name, cases)?;
+                self.check_class_members(name, false, members)
+            }
+            AstNode::TraitDeclaration { name, members, .. } => {
+                self.check_class_members(name, true, members)
+            }
+            AstNode::InterfaceDeclaration { .. } => Ok(()),
+            AstNode::IfStatement { .. } | AstNode::WhileLoop { .. } | AstNode::ForLoop { .. } => {
+                self.walk_control_flow(node)
+            }
+        }
+    }
+
+    fn first_abstract_method<'a>(&self, members: &'a [ClassMember]) -> Option<&'a MethodDeclaration> {
+        members.iter().find_map(|member| match member {
+            ClassMember::Method(method) if method.is_abstract() => Some(method),
+            _ => None,
+        })
+    }
+
+    fn record_enum_match(&mut self, arms: &[MatchArm]) {
+        if arms.iter().any(|arm| arm.is_wildcard()) {
+            return;
+        }
+
+        let mut enum_name: Option<&str> = None;
+        let mut cases = Vec::new();
+
+        for pattern in arms.iter().flat_map(|arm| &arm.patterns) {
+            match pattern {
+                MatchPattern::Value(Expression::StaticConstant { class_name, name })
+                    if enum_name.is_none_or(|known| known == class_name) =>
+                {
+                    enum_name = Some(class_name);
+                    cases.push(name.clone());
+                }
+                _ => return,
+            }
+        }
+
+        if let Some(enum_name) = enum_name {
+            self.enum_matches.push(EnumMatch { enum_name: enum_name.to_string(), cases, span: self.span });
+        }
+    }
+
+    fn check_enum_cases(&self, enum_name: &str, cases: &[EnumCase]) -> Result<()> {
+        let mut names = HashMap::new();
+        let mut values = HashSet::new();
+
+        for case in cases {
+            if let Some(first) = names.insert(case.name.as_str(), case.span) {
+                let diagnostic = Diagnostic::error(format!("Duplicate case `{}` in enum `{}`", case.name, enum_name))
+                    .with_code(codes::SEMANTIC)
+                    .with_label(Label::primary(case.span, "declared again here"))
+                    .with_label(Label::secondary(first, "first declared here"));
+                return Err(diagnostic.into());
+            }
+            // `'on'` and `"on"` are the same value for PHP
+            if let Some(Expression::Literal(literal)) = case.value.as_deref()
+                && !values.insert(literal_value(literal))
+            {
+                return Err(locate(
+                    case.span,
+                    anyhow!("Duplicate value {} in enum `{}`", literal.to_php(), enum_name),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_class_members(&mut self, name: &str, has_parent: bool, members: &[ClassMember]) -> Result<()> {
+        self.classes.push(ClassContext { name: name.to_string(), has_parent });
+        let result = self.check_members(members);
+        self.classes.pop();
+        result
+    }
+
+    fn check_members(&mut self, members: &[ClassMember]) -> Result<()> {
+        for member in members {
+            match member {
+                ClassMember::TraitUse(_) => {}
+                ClassMember::Property(property) => {
+                    if let Some(default) = &property.default {
+                        self.check_expression(default)
+                            .map_err(|e| locate(property.span, e))?;
+                    }
+                }
+                ClassMember::Constructor(constructor) => {
+                    let params: Vec<Parameter> = constructor.params.iter()
+                        .map(|param| param.param.clone())
+                        .collect();
+                    self.check_block(&constructor.body, &params)
+                        .map_err(|e| locate(constructor.span, e))?;
+                }
+                ClassMember::Method(method) => {
+                    if let Some(body) = &method.body {
+                        self.check_block(body, &method.params)
+                            .map_err(|e| locate(method.span, e))?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_block(&mut self, block: &Block, params: &[Parameter]) -> Result<()> {
+        self.with_scope(params, |analyzer| {
+            analyzer.analyze(&block.statements)?;
+            if let Some(return_stmt) = &block.return_statement
+                && let Some(expr) = &return_stmt.expression
+            {
+                analyzer.check_expression(expr)
+                    .map_err(|e| locate(return_stmt.span, e))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Runs `check` inside a new scope that starts with `params` declared
+    fn with_scope(
+        &mut self,
+        params: &[Parameter],
+        check: impl FnOnce(&mut Self) -> Result<()>,
+    ) -> Result<()> {
+        let mut scope = HashMap::new();
+        for param in params {
+            scope.insert(param.name.clone(), Binding {
+                kind: BindingKind::Variable,
+                nullable: param.param_type.as_deref().is_some_and(is_declared_nullable),
+                mutable: true,
+            });
+        }
+
+        self.scopes.push(scope);
+        let result = check(self);
+        self.scopes.pop();
+
+        result
+    }
+
+    fn check_expression(&mut self, expr: &Expression) -> Result<()> {
+        match expr {
+            Expression::Literal(_) | Expression::Variable { .. } | Expression::Constant { .. } => Ok(()),
+            Expression::FunctionCall(call) => {
+                for arg in &call.args {
+                    self.check_expression(arg)?;
+                }
+                Ok(())
+            }
+            Expression::Parenthesized(inner) => self.check_expression(inner),
+            Expression::Closure { params, body, .. } => {
+                match body {
+                    ClosureBody::Block(block) => self.check_block(block, params),
+                    ClosureBody::Expression(expr) => {
+                        self.with_scope(params, |analyzer| analyzer.check_expression(expr))
+                    }
+                }
+            }
+            Expression::ArrayLiteral { elements } => {
+                for element in elements {
+                    self.check_expression(element)?;
+                }
+                Ok(())
+            }
+            Expression::PostfixChain { base, suffixes } => {
+                self.check_expression(base)?;
+                self.check_nullable_access(base, suffixes)?;
+                for suffix in suffixes {
+                    match suffix {
+                        PostfixSuffix::Index(index) => self.check_expression(index)?,
+                        PostfixSuffix::MethodCall(call) | PostfixSuffix::NullsafeMethodCall(call) => {
+                            for arg in &call.args {
+                                self.check_expression(arg)?;
+                            }
+                        }
+                        PostfixSuffix::Property(_) | PostfixSuffix::NullsafeProperty(_) => {}
+                    }
+                }
+                Ok(())
+            }
+            Expression::BinaryOp { left, op, right } => {
+                self.check_expression(left)?;
+                self.with_non_null(&non_null_in_right(left, *op), |analyzer| analyzer.check_expression(right))
+            }
+            Expression::UnaryOp { operand, .. } => self.check_expression(operand),
+            Expression::New { args, .. } => {
+                for arg in args {
+                    self.check_expression(arg)?;
+                }
+                Ok(())
+            }
+            Expression::This => {
+                if self.classes.is_empty() {
+                    return Err(anyhow!("`this` can only be used inside class methods"));
+                }
+                Ok(())
+            }
+            Expression::StaticConstant { .. } => Ok(()),
+            Expression::StaticCall { args, .. } => {
+                for arg in args {
+                    self.check_expression(arg)?;
+                }
+                Ok(())
+            }
+            Expression::Match { subject, arms } => {
+                self.check_expression(subject)?;
+
+                for (index, arm) in arms.iter().enumerate() {
+                    if arm.is_wildcard() && index + 1 < arms.len() {
+                        return Err(anyhow!("Unreachable match arm after `_`"));
+                    }
+                    for pattern in &arm.patterns {
+                        if let MatchPattern::Value(value) = pattern {
+                            self.check_expression(value)?;
+                        }
+                    }
+                    match &arm.body {
+                        MatchArmBody::Expression(body) => self.check_expression(body)?,
+                        MatchArmBody::Block(block) => self.check_block(block, &[])?,
+                    }
+                }
+
+                self.record_enum_match(arms);
+                Ok(())
+            }
+            Expression::ParentCall { args, .. } => {
+                match self.classes.last() {
+                    None => return Err(anyhow!("`parent` can only be used inside class methods")),
+                    Some(class) if !class.has_parent => {
+                        return Err(anyhow!(
+                            "`parent` is used in class `{}` which does not extend another class",
+                            class.name
+                        ));
+                    }
+                    Some(_) => {}
+                }
+                for arg in args {
+                    self.check_expression(arg)?;
+                }
+                Ok(())
+            }
+            Expression::Assignment { target, value, .. } => {
+                if let Some(name) = self.constant_root(target) {
+                    return Err(at_target(target, anyhow!("Cannot assign to constant `{}`", name)));
+                }
+                if let Some(name) = self.immutable_root(target) {
+                    return Err(at_target(target, anyhow!(
+                        "Cannot assign twice to immutable variable `{}`, declare it with `let mut {}`",
+                        name, name
+                    )));
+                }
+                self.check_expression(target)?;
+                self.check_expression(value)
+            }
+            Expression::Update { op, target, .. } => {
+                let action = match op {
+                    UpdateOperator::Increment => "increment",
+                    UpdateOperator::Decrement => "decrement",
+                };
+                if let Some(name) = self.constant_root(target) {
+                    return Err(at_target(target, anyhow!("Cannot {} constant `{}`", action, name)));
+                }
+                if let Some(name) = self.immutable_root(target) {
+                    return Err(at_target(target, anyhow!(
+                        "Cannot {} immutable variable `{}`, declare it with `let mut {}`",
+                        action, name, name
+                    )));
+                }
+                self.check_expression(target)
+            }
+        }
+    }
+
+    /// Accessing a member of a nullable variable needs `?.`
+    fn check_nullable_access(&self, base: &Expression, suffixes: &[PostfixSuffix]) -> Result<()> {
+        let Expression::Variable { name, .. } = base else {
+            return Ok(());
+        };
+        if !self.lookup(name).is_some_and(|binding| binding.nullable) {
+            return Ok(());
+        }
+
+        let member = match suffixes.first() {
+            Some(PostfixSuffix::Property(property)) => property,
+            Some(PostfixSuffix::MethodCall(call)) => &call.name,
+            _ => return Ok(()),
+        };
+
+        Err(anyhow!(
+            "`{}` may be null, use `?.` to access `{}` or check it against null first",
+            name, member
+        ))
+    }
+
+    /// Name of the constant an lvalue writes into, if any (`MAX`, `LIMITS[0]`)
+    fn constant_root<'a>(&self, target: &'a Expression) -> Option<&'a str> {
+        let name = match target {
+            Expression::Variable { name, .. } | Expression::Constant { name, .. } => name,
+            Expression::PostfixChain { base, .. } => match base.as_ref() {
+                Expression::Variable { name, .. } | Expression::Constant { name, .. } => name,
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        (self.lookup(name).map(|binding| binding.kind) == Some(BindingKind::Constant)).then_some(name.as_str())
+    }
+
+    /// Variable an lvalue writes into when `let mut` is required and the variable isn't
+    /// mutable. Properties are left out, objects are mutable through any variable
+    fn immutable_root<'a>(&self, target: &'a Expression) -> Option<&'a str> {
+        if !self.require_let_mut {
+            return None;
+        }
+
+        let name = match target {
+            Expression::Variable { name, .. } => name,
+            Expression::PostfixChain { base, suffixes }
+                if suffixes.iter().all(|suffix| matches!(suffix, PostfixSuffix::Index(_))) =>
+            {
+                match base.as_ref() {
+                    Expression::Variable { name, .. } => name,
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+
+        self.lookup(name)
+            .is_some_and(|binding| binding.kind == BindingKind::Variable && !binding.mutable)
+            .then_some(name.as_str())
+    }
+
+    fn declare(&mut self, name: &str, kind: BindingKind, declared_type: Option<&str>, mutable: bool) -> Result<()> {
+        let nullable = declared_type.is_some_and(is_declared_nullable);
+        let Some(scope) = self.scopes.last_mut() else {
+            return Ok(());
+        };
+
+        // Constants can only be shadowed from a nested scope
+        if scope.get(name).is_some_and(|binding| binding.kind == BindingKind::Constant) {
+            return Err(anyhow!("Constant `{}` is already declared in this scope", name));
+        }
+        scope.insert(name.to_string(), Binding { kind, nullable, mutable });
+        Ok(())
+    }
+
+    fn lookup(&self, name: &str) -> Option<Binding> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name).copied())
+    }
+}
+
+impl ScopedWalk for SemanticAnalyzer {
+    type Error = anyhow::Error;
+
+    fn walk_node(&mut self, node: &AstNode) -> Result<()> {
+        self.check_node(node)
+    }
+
+    fn walk_expression(&mut self, expr: &Expression) -> Result<()> {
+        self.check_expression(expr)
+    }
+
+    fn walk_block(&mut self, block: &Block) -> Result<()> {
+        self.check_block(block, &[])
+    }
+
+    fn with_scope(&mut self, walk: impl FnOnce(&mut Self) -> Result<()>) -> Result<()> {
+        SemanticAnalyzer::with_scope(self, &[], walk)
+    }
+
+    /// Narrows the nullable variables in `names` to non-null
+    fn with_non_null(&mut self, names: &[String], walk: impl FnOnce(&mut Self) -> Result<()>) -> Result<()> {
+        let scope: HashMap<String, Binding> = names.iter()
+            .filter_map(|name| {
+                let binding = self.lookup(name)?;
+                Some((name.clone(), Binding { nullable: false, ..binding }))
+            })
+            .collect();
+
+        self.scopes.push(scope);
+        let result = walk(self);
+        self.scopes.pop();
+
+        result
+    }
+}
+
+/// Variables that can't be null when `condition` evaluates to `outcome`:
+/// `x != null` (joined with `&&`) when true, `x == null` (joined with `||`) when false
+pub(crate) fn non_null_when(condition: &Expression, outcome: bool) -> Vec<String> {
+    match condition {
+        Expression::Parenthesized(inner) => non_null_when(inner, outcome),
+        Expression::BinaryOp { left, op, right } => {
+            let join = if outcome { BinaryOperator::And } else { BinaryOperator::Or };
+            let null_check = if outcome { BinaryOperator::NotEqual } else { BinaryOperator::Equal };
+
+            if *op == join {
+                let mut names = non_null_when(left, outcome);
+                names.extend(non_null_when(right, outcome));
+                return names;
+            }
+
+            if *op == null_check {
+                match (left.as_ref(), right.as_ref()) {
+                    (Expression::Variable { name, .. }, Expression::Literal(Literal::Null))
+                    | (Expression::Literal(Literal::Null), Expression::Variable { name, .. }) => {
+                        return vec![name.clone()];
+                    }
+                    _ => {}
+                }
+            }
+
+            Vec::new()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Variables that can't be null while the right operand of `op` runs,
+/// since the right side of `&&` only runs when the left side holds
+pub(crate) fn non_null_in_right(left: &Expression, op: BinaryOperator) -> Vec<String> {
+    match op {
+        BinaryOperator::And => non_null_when(left, true),
+        _ => Vec::new(),
+    }
+}
+
+/// Only an explicit `T?` is checked, `any` is left to the developer
+fn is_declared_nullable(type_str: &str) -> bool {
+    split_top_level(type_str, '|').into_iter().any(|member| member.ends_with('?'))
+}
+
+/// Points `error` to the written name rather than the statement, when its position is known
+fn at_target(target: &Expression, error: anyhow::Error) -> anyhow::Error {
+    match target.span() {
+        Some(span) => locate(span, error),
+        None => error,
+    }
+}
+
+/// Value of a literal regardless of how it is written, strings without their quotes
+fn literal_value(literal: &Literal) -> String {
+    match literal {
+        Literal::String(StringType::Interpolated(content) | StringType::Raw(content)) => content.clone(),
+        other => other.to_php(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{semantic::SemanticAnalyzer, utils::test_utils::parse_with_error};
+
+    fn analyze(code: &str) -> anyhow::Result<()> {
+        let ast = parse_with_error(code)?;
+        SemanticAnalyzer::new().analyze(&ast)
+    }
+
+    #[test]
+    fn test_increment_variable_is_allowed() {
+        assert!(analyze("let i = 0; i++; --i;").is_ok());
+    }
+
+    #[test]
+    fn test_increment_constant_is_rejected() {
+        let err = analyze("const MAX = 10; MAX++;").unwrap_err();
+        assert_eq!(err.to_string(), "1:17: Cannot increment constant `MAX`");
+
+        let err = analyze("const MAX = 10; fn f() { --MAX; }").unwrap_err();
+        assert_eq!(err.to_string(), "1:28: Cannot decrement constant `MAX`");
+
+        let err = analyze("const MAX = 10; print(MAX++);").unwrap_err();
+        assert_eq!(err.to_string(), "1:23: Cannot increment constant `MAX`");
+    }
+
+    #[test]
+    fn test_this_outside_class_is_rejected() {
+        let err = analyze("fn f() { return this.name; }").unwrap_err();
+        assert_eq!(err.to_string(), "1:10: `this` can only be used inside class methods");
+
+        assert!(analyze("class User { name: string; fn get(): string { return this.name; } }").is_ok());
+    }
+
+    #[test]
+    fn test_parent_requires_parent_class() {
+        let err = analyze("fn f() { return parent.f(); }").unwrap_err();
+        assert_eq!(err.to_string(), "1:10: `parent` can only be used inside class methods");
+
+        let err = analyze("class User { constructor() { super(); } }").unwrap_err();
+        assert_eq!(err.to_string(), "1:30: `parent` is used in class `User` which does not extend another class");
+
+        assert!(analyze("class Admin extends User { constructor() { super(); } }").is_ok());
+        assert!(analyze("trait Greets { fn greet() { parent.greet(); } }").is_ok());
+    }
+
+    #[test]
+    fn test_abstract_method_requires_abstract_class() {
+        let err = analyze("class Shape { abstract fn area(): float; }").unwrap_err();
+        assert_eq!(err.to_string(), "1:15: Class `Shape` declares abstract method `area` but is not abstract");
+
+        assert!(analyze("abstract class Shape { abstract fn area(): float; }").is_ok());
+    }
+
+    #[test]
+    fn test_nullable_dereference_needs_nullsafe() {
+        let err = analyze("fn f(user: User?) { return user.name; }").unwrap_err();
+        assert_eq!(err.to_string(), "1:21: `user` may be null, use `?.` to access `name` or check it against null first");
+
+        let err = analyze("let name: string? = null; name.trim();").unwrap_err();
+        assert_eq!(err.to_string(), "1:27: `name` may be null, use `?.` to access `trim` or check it against null first");
+
+        assert!(analyze("fn f(user: User?) { return user?.address.city ?? 'none'; }").is_ok());
+        assert!(analyze("fn f(user: any) { return user.name; }").is_ok(), "`any` is not checked");
+        assert!(analyze("fn f(users: Map<string, User?>) { return users.count(); }").is_ok());
+    }
+
+    #[test]
+    fn test_null_check_narrows_nullable() {
+        assert!(analyze("fn f(user: User?) { if (user != null) { println(user.name); } }").is_ok());
+        assert!(analyze("fn f(user: User?) { if (user == null) { return; } else { user.save(); } }").is_ok());
+        assert!(analyze("fn f(user: User?) { return user != null && user.active; }").is_ok());
+        assert!(analyze("fn f(user: User?) { while (null != user) { user = user.next; } }").is_ok());
+
+        assert!(analyze("fn f(user: User?) { if (user == null) { user.save(); } }").is_err());
+        assert!(analyze("fn f(user: User?) { return user != null || user.active; }").is_err());
+    }
+
+    #[test]
+    fn test_wildcard_must_be_last_arm() {
+        let err = analyze("let x = match y { _ => 1, 2 => 2 };").unwrap_err();
+        assert_eq!(err.to_string(), "1:1: Unreachable match arm after `_`");
+    }
+
+    #[test]
+    fn test_duplicate_enum_cases() {
+        let err = analyze("enum Suit { Hearts, Hearts }").unwrap_err();
+        assert_eq!(err.to_string(), "1:21: Duplicate case `Hearts` in enum `Suit`");
+
+        let err = analyze("enum Status: string { Active = 'on', Enabled = 'on' }").unwrap_err();
+        assert_eq!(err.to_string(), "1:38: Duplicate value 'on' in enum `Status`");
+
+        let err = analyze("enum Status: string { Active = 'on', Enabled = \"on\" }").unwrap_err();
+        assert_eq!(err.to_string(), "1:38: Duplicate value \"on\" in enum `Status`");
+
+        assert!(analyze("enum Status: int { Active = 1, Inactive = 0; fn on(): bool { return this == Status::Active; } }").is_ok());
+    }
+
+    #[test]
+    fn test_constant_reassignment_is_rejected() {
+        let err = analyze("const MAX = 10; MAX = 11;").unwrap_err();
+        assert_eq!(err.to_string(), "1:17: Cannot assign to constant `MAX`");
+
+        let err = analyze("const MAX = 10; fn f() { MAX += 1; }").unwrap_err();
+        assert_eq!(err.to_string(), "1:26: Cannot assign to constant `MAX`");
+
+        let err = analyze("const MAX = 10; let MAX = 11;").unwrap_err();
+        assert_eq!(err.to_string(), "1:17: Constant `MAX` is already declared in this scope");
+
+        assert!(analyze("const MAX = 10; fn f() { let MAX = 11; MAX = 12; }").is_ok());
+    }
+
+    #[test]
+    fn test_let_mut_is_optional_by_default() {
+        assert!(analyze("let total = 0; total += 1; total++;").is_ok());
+    }
+
+    #[test]
+    fn test_let_mut_required_for_reassignment() {
+        let analyze_strict = |code: &str| {
+            let ast = parse_with_error(code)?;
+            SemanticAnalyzer::new().require_let_mut(true).analyze(&ast)
+        };
+
+        let err = analyze_strict("let total = 0; total = 1;").unwrap_err();
+        assert_eq!(err.to_string(), "1:16: Cannot assign twice to immutable variable `total`, declare it with `let mut total`");
+
+        let err = analyze_strict("for (let i = 0; i < 3; i++) {}").unwrap_err();
+        assert_eq!(err.to_string(), "1:24: Cannot increment immutable variable `i`, declare it with `let mut i`");
+
+        let err = analyze_strict("let ids = load(); ids[0] = 1;").unwrap_err();
+        assert_eq!(err.to_string(), "1:19: Cannot assign twice to immutable variable `ids`, declare it with `let mut ids`");
+
+        assert!(analyze_strict("let mut total = 0; total += 1; for (let mut i = 0; i < 3; i++) {}").is_ok());
+        assert!(analyze_strict("let user = load(); user.name = 'Ana'; fn f(n: int) { n = 2; }").is_ok());
+    }
+
+    #[test]
+    fn test_shadowed_constant_can_be_incremented() {
+        assert!(analyze("const N = 1; fn f(N: int) { N++; }").is_ok());
+        assert!(analyze("const N = 1; for (let N = 0; N < 3; N++) {}").is_ok());
+    }
+}
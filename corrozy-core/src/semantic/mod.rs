@@ -0,0 +1,8 @@
+pub mod analyzer;
+pub mod project;
+pub mod resolver;
+pub mod walk;
+
+pub use analyzer::{EnumMatch, SemanticAnalyzer};
+pub use project::ProjectAnalyzer;
+pub use resolver::{Resolver, SymbolTable};
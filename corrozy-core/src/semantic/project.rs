@@ -0,0 +1,600 @@
+use std::{collections::HashMap, path::{Path, PathBuf}};
+
+use anyhow::{Ok, Result};
+
+use crate::{diagnostics::{Diagnostic, Diagnostics, Label, codes}, semantic::analyzer::EnumMatch, language::{AstNode, Parameter, Span, features::{class::ast::{ClassMember, ClassModifier, MethodDeclaration, Visibility}, interface::ast::MethodSignature, types::generator::class_name}}};
+
+struct InterfaceInfo {
+    methods: Vec<MethodSignature>,
+}
+
+struct TraitInfo {
+    name: String,
+    namespace: Option<String>,
+    uses: Vec<String>,
+    methods: Vec<MethodDeclaration>,
+}
+
+struct ClassInfo {
+    /// `class` or `enum`, used in messages
+    kind: &'static str,
+    modifier: Option<ClassModifier>,
+    name: String,
+    namespace: Option<String>,
+    file: PathBuf,
+    span: Span,
+    extends: Option<String>,
+    implements: Vec<String>,
+    uses: Vec<String>,
+    methods: Vec<MethodDeclaration>,
+}
+
+impl ClassInfo {
+    fn qualified_name(&self) -> String {
+        qualify(self.namespace.as_deref(), &self.name)
+    }
+}
+
+/// A method together with the class or trait that declares it
+struct ResolvedMethod<'a> {
+    owner: &'a str,
+    method: &'a MethodDeclaration,
+}
+
+/// Checks that need the declarations of every file in the project at once.
+/// Declarations are keyed by their name qualified with the namespace of their file
+pub struct ProjectAnalyzer {
+    interfaces: HashMap<String, InterfaceInfo>,
+    traits: HashMap<String, TraitInfo>,
+    classes: Vec<ClassInfo>,
+    class_index: HashMap<String, usize>,
+    enum_cases: HashMap<String, Vec<String>>,
+    enum_matches: Vec<(PathBuf, Option<String>, EnumMatch)>,
+}
+
+impl Default for ProjectAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            interfaces: HashMap::new(),
+            traits: HashMap::new(),
+            classes: Vec::new(),
+            class_index: HashMap::new(),
+            enum_cases: HashMap::new(),
+            enum_matches: Vec::new(),
+        }
+    }
+
+    /// Collects the top level declarations of a parsed file whose PHP is in `namespace`
+    pub fn add_file(&mut self, file: &Path, namespace: Option<&str>, ast: &[AstNode]) {
+        for node in ast {
+            match node {
+                AstNode::Program { statements, .. } => {
+                    self.add_file(file, namespace, statements);
+                }
+                AstNode::InterfaceDeclaration { name, methods, .. } => {
+                    self.interfaces.insert(qualify(namespace, name), InterfaceInfo {
+                        methods: methods.clone(),
+                    });
+                }
+                AstNode::TraitDeclaration { name, members, .. } => {
+                    let (uses, methods) = self.split_members(members);
+                    self.traits.insert(qualify(namespace, name), TraitInfo {
+                        name: name.clone(),
+                        namespace: namespace.map(str::to_string),
+                        uses,
+                        methods,
+                    });
+                }
+                AstNode::EnumDeclaration { name, implements, cases, members, span, .. } => {
+                    let (uses, methods) = self.split_members(members);
+                    self.enum_cases.insert(qualify(namespace, name), cases.iter().map(|case| case.name.clone()).collect());
+
+                    // Enums behave like final classes that can implement interfaces
+                    self.class_index.insert(qualify(namespace, name), self.classes.len());
+                    self.classes.push(ClassInfo {
+                        kind: "enum",
+                        modifier: Some(ClassModifier::Final),
+                        name: name.clone(),
+                        namespace: namespace.map(str::to_string),
+                        file: file.to_path_buf(),
+                        span: *span,
+                        extends: None,
+                        implements: implements.iter().map(|interface| class_name(interface).to_string()).collect(),
+                        uses,
+                        methods,
+                    });
+                }
+                AstNode::ClassDeclaration { modifier, name, extends, implements, members, span, .. } => {
+                    let (uses, methods) = self.split_members(members);
+
+                    self.class_index.insert(qualify(namespace, name), self.classes.len());
+                    self.classes.push(ClassInfo {
+                        kind: "class",
+                        modifier: *modifier,
+                        name: name.clone(),
+                        namespace: namespace.map(str::to_string),
+                        file: file.to_path_buf(),
+                        span: *span,
+                        // Type arguments of generic parents only matter to PHPDoc
+                        extends: extends.as_deref().map(|parent| class_name(parent).to_string()),
+                        implements: implements.iter().map(|interface| class_name(interface).to_string()).collect(),
+                        uses,
+                        methods,
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Registers the enum matches found by the semantic analyzer of a file in `namespace`
+    pub fn add_enum_matches(&mut self, file: &Path, namespace: Option<&str>, matches: &[EnumMatch]) {
+        self.enum_matches.extend(matches.iter()
+            .map(|enum_match| (file.to_path_buf(), namespace.map(str::to_string), enum_match.clone())));
+    }
+
+    /// Returns the used traits and the methods declared in a class or trait body
+    fn split_members(&self, members: &[ClassMember]) -> (Vec<String>, Vec<MethodDeclaration>) {
+        let mut uses = Vec::new();
+        let mut methods = Vec::new();
+
+        for member in members {
+            match member {
+                ClassMember::TraitUse(traits) => uses.extend(traits.iter().cloned()),
+                ClassMember::Method(method) => methods.push(method.clone()),
+                _ => {}
+            }
+        }
+
+        (uses, methods)
+    }
+
+    /// Fails with every error found
+    pub fn check(&self) -> Result<()> {
+        let mut errors = Vec::new();
+
+        for class in &self.classes {
+            if let Some(cycle) = self.inheritance_cycle(class) {
+                // Every class of the cycle sees it, report it once from the first name
+                if cycle.iter().map(|member| member.qualified_name()).min() == Some(class.qualified_name()) {
+                    let names: Vec<&str> = cycle.iter().map(|member| member.name.as_str()).collect();
+                    errors.push(error(class, class.span, format!("inheritance cycle detected: {}", names.join(" -> "))));
+                }
+                continue;
+            }
+
+            errors.extend(self.check_extends(class));
+
+            if class.modifier != Some(ClassModifier::Abstract) {
+                errors.extend(self.check_abstract_methods(class));
+                errors.extend(self.check_implements(class));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Diagnostics(errors).into())
+        }
+    }
+
+    /// Warnings of the files added so far, they don't depend on `check` passing
+    pub fn warnings(&self) -> Vec<Diagnostic> {
+        let mut warnings = Vec::new();
+
+        for (file, namespace, enum_match) in &self.enum_matches {
+            // Constants of classes or enums from PHP code can't be checked
+            let Some(cases) = self.enum_cases.get(&qualify(namespace.as_deref(), &enum_match.enum_name)) else {
+                continue;
+            };
+
+            let missing: Vec<String> = cases.iter()
+                .filter(|case| !enum_match.cases.contains(case))
+                .map(|case| format!("`{}`", case))
+                .collect();
+
+            if !missing.is_empty() {
+                let warning = Diagnostic::warning(format!(
+                    "match on enum `{}` is not exhaustive, missing {}",
+                    enum_match.enum_name, missing.join(", ")
+                ));
+                warnings.push(warning
+                    .with_code(codes::NON_EXHAUSTIVE_MATCH)
+                    .with_label(Label::primary(enum_match.span, "this match"))
+                    .with_help("add an arm for each missing case or a `_` arm")
+                    .in_file(file));
+            }
+        }
+
+        warnings
+    }
+
+    /// Returns the classes of the cycle when following `extends` leads back to the class
+    fn inheritance_cycle<'a>(&'a self, class: &'a ClassInfo) -> Option<Vec<&'a ClassInfo>> {
+        let mut path = vec![class];
+        let mut current = class;
+
+        while let Some(parent) = self.parent_of(current) {
+            path.push(parent);
+            if std::ptr::eq(parent, class) {
+                return Some(path);
+            }
+            // A cycle further up the chain is reported from its own classes
+            if path[..path.len() - 1].iter().any(|known| std::ptr::eq(*known, parent)) {
+                return None;
+            }
+            current = parent;
+        }
+
+        None
+    }
+
+    fn parent_of(&self, class: &ClassInfo) -> Option<&ClassInfo> {
+        let parent = class.extends.as_ref()?;
+        self.class_index.get(&qualify(class.namespace.as_deref(), parent)).map(|index| &self.classes[*index])
+    }
+
+    /// The class followed by its ancestors declared in the project
+    fn ancestors<'a>(&'a self, class: &'a ClassInfo) -> Vec<&'a ClassInfo> {
+        let mut chain = vec![class];
+
+        while let Some(parent) = self.parent_of(chain[chain.len() - 1]) {
+            if chain.iter().any(|known| std::ptr::eq(*known, parent)) {
+                break;
+            }
+            chain.push(parent);
+        }
+
+        chain
+    }
+
+    fn check_extends(&self, class: &ClassInfo) -> Vec<Diagnostic> {
+        let mut errors = Vec::new();
+
+        if let Some(parent) = self.parent_of(class)
+            && parent.modifier == Some(ClassModifier::Final)
+        {
+            errors.push(error(class, class.span, format!("class `{}` cannot extend final class `{}`", class.name, parent.name)));
+        }
+
+        let inherited = &self.ancestors(class)[1..];
+        for method in &class.methods {
+            let final_method = inherited.iter().find(|ancestor| {
+                ancestor.methods.iter()
+                    .any(|parent_method| parent_method.name == method.name && parent_method.modifier == Some(ClassModifier::Final))
+            });
+
+            if let Some(ancestor) = final_method {
+                errors.push(error(class, method.span, format!(
+                    "method `{}::{}` cannot override final method of `{}`",
+                    class.name, method.name, ancestor.name
+                )));
+            }
+        }
+
+        errors
+    }
+
+    fn check_abstract_methods(&self, class: &ClassInfo) -> Vec<Diagnostic> {
+        let mut errors = Vec::new();
+        let methods = self.class_methods(class);
+
+        for (index, resolved) in methods.iter().enumerate() {
+            // Only the closest declaration of a method counts
+            let is_first = !methods[..index].iter().any(|other| other.method.name == resolved.method.name);
+
+            if is_first && resolved.method.is_abstract() {
+                errors.push(error(class, class.span, format!(
+                    "class `{}` does not implement abstract method `{}` of `{}`",
+                    class.name, resolved.method.name, resolved.owner
+                )));
+            }
+        }
+
+        errors
+    }
+
+    fn check_implements(&self, class: &ClassInfo) -> Vec<Diagnostic> {
+        let mut errors = Vec::new();
+        let methods = self.class_methods(class);
+
+        let interfaces = self.ancestors(class).into_iter()
+            .flat_map(|ancestor| ancestor.implements.iter().map(move |name| (ancestor, name)));
+
+        for (ancestor, interface_name) in interfaces {
+            // Interfaces that are not declared in the project may come from PHP code
+            let Some(interface) = self.interfaces.get(&qualify(ancestor.namespace.as_deref(), interface_name)) else {
+                continue;
+            };
+
+            for signature in &interface.methods {
+                let method = methods.iter()
+                    .map(|resolved| resolved.method)
+                    .find(|method| method.name == signature.name && !method.is_abstract());
+
+                match method {
+                    None => errors.push(error(class, class.span, format!(
+                        "{} `{}` does not implement method `{}` of interface `{}`",
+                        class.kind, class.name, signature.name, interface_name
+                    ))),
+                    Some(method) if !self.matches_signature(method, signature) => errors.push(error(class, class.span, format!(
+                        "method `{}::{}` does not match `{}` declared in interface `{}`",
+                        class.name, method.name, self.format_signature(signature), interface_name
+                    ))),
+                    Some(_) => {}
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Methods available on a class, closest declaration first: the class itself,
+    /// its traits, then the same for every ancestor
+    fn class_methods<'a>(&'a self, class: &'a ClassInfo) -> Vec<ResolvedMethod<'a>> {
+        let mut methods = Vec::new();
+
+        for ancestor in self.ancestors(class) {
+            methods.extend(ancestor.methods.iter()
+                .map(|method| ResolvedMethod { owner: &ancestor.name, method }));
+
+            let mut pending: Vec<String> = ancestor.uses.iter()
+                .map(|name| qualify(ancestor.namespace.as_deref(), name))
+                .collect();
+            let mut visited = Vec::new();
+
+            while let Some(trait_name) = pending.pop() {
+                if visited.contains(&trait_name) {
+                    continue;
+                }
+
+                // Traits that are not declared in the project may come from PHP code
+                if let Some(trait_info) = self.traits.get(&trait_name) {
+                    methods.extend(trait_info.methods.iter()
+                        .map(|method| ResolvedMethod { owner: &trait_info.name, method }));
+                    pending.extend(trait_info.uses.iter()
+                        .map(|name| qualify(trait_info.namespace.as_deref(), name)));
+                }
+                visited.push(trait_name);
+            }
+        }
+
+        methods
+    }
+
+    fn matches_signature(&self, method: &MethodDeclaration, signature: &MethodSignature) -> bool {
+        method.visibility == Visibility::Public
+            && method.params.len() == signature.params.len()
+            && method.params.iter().zip(&signature.params)
+                .all(|(param, expected)| param.param_type.is_none() || param.param_type == expected.param_type)
+            && (signature.return_type.is_none() || method.return_type == signature.return_type)
+    }
+
+    fn format_signature(&self, signature: &MethodSignature) -> String {
+        let params: Vec<String> = signature.params.iter()
+            .map(|param: &Parameter| match &param.param_type {
+                Some(param_type) => format!("{}: {}", param.name, param_type),
+                None => param.name.clone(),
+            })
+            .collect();
+
+        let mut result = format!("fn {}({})", signature.name, params.join(", "));
+        if let Some(return_type) = &signature.return_type {
+            result.push_str(&format!(": {}", return_type));
+        }
+        result
+    }
+}
+
+/// An error about `class`, pointing to `span` in its file
+fn error(class: &ClassInfo, span: Span, message: String) -> Diagnostic {
+    Diagnostic::error(message)
+        .with_code(codes::PROJECT)
+        .with_label(Label::primary(span, ""))
+        .in_file(&class.file)
+}
+
+/// The name PHP resolves `name` to when it is written in `namespace`, class
+/// names don't fall back to the global namespace
+fn qualify(namespace: Option<&str>, name: &str) -> String {
+    match namespace {
+        Some(namespace) => format!("{}\\{}", namespace, name),
+        None => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{semantic::{ProjectAnalyzer, SemanticAnalyzer}, utils::test_utils::parse_with_error};
+
+    fn check_files(files: &[(&str, &str)]) -> anyhow::Result<Vec<String>> {
+        let mut analyzer = ProjectAnalyzer::new();
+        for (path, code) in files {
+            let ast = parse_with_error(code).unwrap();
+            let mut file_analyzer = SemanticAnalyzer::new();
+            file_analyzer.analyze(&ast).unwrap();
+
+            // Like auto namespaces, the directory of a file is its namespace
+            let namespace = Path::new(path).parent().and_then(|dir| dir.to_str()).filter(|dir| !dir.is_empty());
+            analyzer.add_file(Path::new(path), namespace, &ast);
+            analyzer.add_enum_matches(Path::new(path), namespace, file_analyzer.enum_matches());
+        }
+        analyzer.check()?;
+        Ok(analyzer.warnings().iter().map(|warning| warning.to_string()).collect())
+    }
+
+    #[test]
+    fn test_class_implementing_interface_from_other_file() {
+        let result = check_files(&[
+            ("shapes/shape.crz", "interface Shape { fn area(): float; }"),
+            ("shapes/circle.crz", "class Circle implements Shape { fn area(): float { return 3.14; } }"),
+        ]);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_same_name_in_other_namespace() {
+        let files = [
+            ("a/shape.crz", "interface Shape { fn area(): float; }"),
+            ("b/shape.crz", "interface Shape { fn name(): string; }"),
+            ("b/circle.crz", "class Circle implements Shape { fn name(): string { return 'circle'; } }"),
+        ];
+        assert!(check_files(&files).is_ok());
+
+        // The order files are added in doesn't change which one is meant
+        let reversed: Vec<_> = files.iter().rev().copied().collect();
+        assert!(check_files(&reversed).is_ok());
+
+        let err = check_files(&[
+            ("a/shape.crz", "interface Shape { fn area(): float; }"),
+            ("b/circle.crz", "class Circle implements Shape { fn name(): string { return 'circle'; } }"),
+            ("a/square.crz", "class Square implements Shape { fn name(): string { return 'square'; } }"),
+        ]).unwrap_err();
+        assert_eq!(err.to_string(), "a/square.crz:1:1: class `Square` does not implement method `area` of interface `Shape`");
+    }
+
+    #[test]
+    fn test_missing_interface_method() {
+        let err = check_files(&[
+            ("shapes/shape.crz", "interface Shape { fn area(): float; fn name(): string; }"),
+            ("shapes/circle.crz", "class Circle implements Shape { fn area(): float { return 3.14; } }"),
+        ]).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "shapes/circle.crz:1:1: class `Circle` does not implement method `name` of interface `Shape`"
+        );
+    }
+
+    #[test]
+    fn test_mismatched_interface_method() {
+        let err = check_files(&[
+            ("shape.crz", "interface Shape { fn scale(factor: float): Shape; }"),
+            ("circle.crz", "class Circle implements Shape { fn scale(factor: int): Shape { return this; } }"),
+        ]).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "circle.crz:1:1: method `Circle::scale` does not match `fn scale(factor: float): Shape` declared in interface `Shape`"
+        );
+    }
+
+    #[test]
+    fn test_private_method_does_not_implement_interface() {
+        let result = check_files(&[
+            ("main.crz", "interface Named { fn name(): string; } class User implements Named { private fn name(): string { return 'x'; } }"),
+        ]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_interface_method_provided_by_trait() {
+        let result = check_files(&[
+            ("shape.crz", "interface Shape { fn area(): float; fn name(): string; }"),
+            ("named.crz", "trait Named { use Unknown; fn name(): string { return 'shape'; } }"),
+            ("area.crz", "trait HasArea { use Named; fn area(): float { return 1.0; } }"),
+            ("square.crz", "class Square implements Shape { use HasArea; }"),
+        ]);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_trait_of_other_namespace() {
+        let err = check_files(&[
+            ("shapes/shape.crz", "interface Shape { fn area(): float; }"),
+            ("shapes/named.crz", "trait Named { fn name(): string { return 'shape'; } }"),
+            ("other/named.crz", "trait Named { fn area(): float { return 1.0; } }"),
+            ("shapes/square.crz", "class Square implements Shape { use Named; }"),
+        ]).unwrap_err();
+
+        assert_eq!(err.to_string(), "shapes/square.crz:1:1: class `Square` does not implement method `area` of interface `Shape`");
+    }
+
+    #[test]
+    fn test_interface_method_inherited_from_parent() {
+        let result = check_files(&[
+            ("shape.crz", "interface Shape { fn area(): float; }"),
+            ("base.crz", "abstract class Base implements Shape { abstract fn name(): string; fn area(): float { return 0.0; } }"),
+            ("square.crz", "class Square extends Base { fn name(): string { return 'square'; } }"),
+        ]);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_missing_abstract_method() {
+        let err = check_files(&[
+            ("shape.crz", "abstract class Shape { abstract fn area(): float; }"),
+            ("square.crz", "abstract class Square extends Shape { }"),
+            ("tile.crz", "class Tile extends Square { }"),
+        ]).unwrap_err();
+
+        assert_eq!(err.to_string(), "tile.crz:1:1: class `Tile` does not implement abstract method `area` of `Shape`");
+    }
+
+    #[test]
+    fn test_inheritance_cycle() {
+        let err = check_files(&[
+            ("a.crz", "class A extends C { }"),
+            ("b.crz", "class B extends A { }"),
+            ("c.crz", "class C extends B { }"),
+            ("d.crz", "class D extends C { }"),
+        ]).unwrap_err();
+
+        assert_eq!(err.to_string(), "a.crz:1:1: inheritance cycle detected: A -> C -> B -> A");
+    }
+
+    #[test]
+    fn test_final_class_and_method() {
+        let err = check_files(&[
+            ("main.crz", "final class Token { } class Jwt extends Token { }"),
+        ]).unwrap_err();
+        assert_eq!(err.to_string(), "main.crz:1:23: class `Jwt` cannot extend final class `Token`");
+
+        let err = check_files(&[
+            ("main.crz", "class Model { final fn save() { } } class User extends Model { fn save() { } }"),
+        ]).unwrap_err();
+        assert_eq!(err.to_string(), "main.crz:1:64: method `User::save` cannot override final method of `Model`");
+    }
+
+    #[test]
+    fn test_enum_implements_interface() {
+        let err = check_files(&[
+            ("labels.crz", "interface HasLabel { fn label(): string; }"),
+            ("status.crz", "enum Status: string implements HasLabel { Active = 'active' }"),
+        ]).unwrap_err();
+
+        assert_eq!(err.to_string(), "status.crz:1:1: enum `Status` does not implement method `label` of interface `HasLabel`");
+    }
+
+    #[test]
+    fn test_non_exhaustive_enum_match_warns() {
+        let warnings = check_files(&[
+            ("status.crz", "enum Status { Active, Inactive, Banned }"),
+            ("main.crz", "let status = Status::Active;\nlet label = match status { Status::Active => 'on', Status::Inactive => 'off' };"),
+            ("other.crz", "let label = match status { Status::Active => 'on', _ => 'off' };"),
+        ]).unwrap();
+
+        assert_eq!(warnings, vec!["main.crz:2:1: match on enum `Status` is not exhaustive, missing `Banned`"]);
+    }
+
+    #[test]
+    fn test_unknown_interface_is_ignored() {
+        let result = check_files(&[
+            ("main.crz", "class Items implements Countable { fn count(): int { return 0; } }"),
+        ]);
+
+        assert!(result.is_ok());
+    }
+}
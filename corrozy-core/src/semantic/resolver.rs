@@ -0,0 +1,619 @@
+use std::convert::Infallible;
+
+use anyhow::Result;
+
+use crate::{diagnostics::{Diagnostic, Diagnostics, Label, codes}, language::{AstNode, Parameter, Span, features::{block::ast::Block, class::ast::ClassMember, closure::ast::ClosureBody, expression::ast::Expression, pattern_match::ast::{MatchArmBody, MatchPattern}, postfix::ast::PostfixSuffix}}, semantic::walk::ScopedWalk};
+
+pub type ScopeId = usize;
+pub type SymbolId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScopeKind {
+    Program,
+    /// Top-level functions and methods, which can't see the variables around them
+    Function,
+    /// Class, trait or enum body, only constants are visible inside
+    Class,
+    Block,
+    /// Closures and nested functions, which capture the variables around them
+    Closure,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SymbolKind {
+    Variable,
+    Constant,
+    Parameter,
+}
+
+#[derive(Debug, Clone)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    pub parent: Option<ScopeId>,
+    pub symbols: Vec<SymbolId>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub declared_type: Option<String>,
+    pub scope: ScopeId,
+    /// Name of a parameter, the statement declaring anything else
+    pub span: Span,
+}
+
+/// A use of a variable, `symbol` is `None` when it isn't declared
+#[derive(Debug, Clone)]
+pub struct Reference {
+    pub name: String,
+    pub scope: ScopeId,
+    pub symbol: Option<SymbolId>,
+    /// Assigned or incremented rather than only read
+    pub write: bool,
+    /// Where the name is written
+    pub span: Span,
+}
+
+/// Scopes, declarations and variable uses of a file, in source order
+#[derive(Debug, Default)]
+pub struct SymbolTable {
+    scopes: Vec<Scope>,
+    symbols: Vec<Symbol>,
+    references: Vec<Reference>,
+}
+
+impl SymbolTable {
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id]
+    }
+
+    pub fn symbol(&self, id: SymbolId) -> &Symbol {
+        &self.symbols[id]
+    }
+
+    pub fn references_to(&self, symbol: SymbolId) -> impl Iterator<Item = &Reference> {
+        self.references.iter().filter(move |reference| reference.symbol == Some(symbol))
+    }
+
+    /// Variables a closure scope reads from the scopes around it, in order of first use
+    pub fn captures(&self, closure: ScopeId) -> Vec<&Symbol> {
+        let mut captured: Vec<&Symbol> = Vec::new();
+
+        for reference in &self.references {
+            let Some(symbol) = reference.symbol.map(|id| self.symbol(id)) else {
+                continue;
+            };
+            let declared_outside = !self.is_within(symbol.scope, closure);
+            if declared_outside
+                && symbol.kind != SymbolKind::Constant
+                && self.is_within(reference.scope, closure)
+                && !captured.iter().any(|known| known.name == symbol.name)
+            {
+                captured.push(symbol);
+            }
+        }
+
+        captured
+    }
+
+    /// Whether `scope` is `ancestor` or nested inside it
+    pub fn is_within(&self, scope: ScopeId, ancestor: ScopeId) -> bool {
+        let mut current = Some(scope);
+        while let Some(id) = current {
+            if id == ancestor {
+                return true;
+            }
+            current = self.scopes[id].parent;
+        }
+        false
+    }
+}
+
+/// Resolves every variable to its declaration, reporting the undefined ones
+pub struct Resolver {
+    table: SymbolTable,
+    stack: Vec<ScopeId>,
+    errors: Vec<Diagnostic>,
+    /// Statement being resolved, where errors point to
+    span: Span,
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        let mut resolver = Self {
+            table: SymbolTable::default(),
+            stack: Vec::new(),
+            errors: Vec::new(),
+            span: Span::default(),
+        };
+        resolver.push_scope(ScopeKind::Program, &[]);
+        resolver
+    }
+
+    pub fn symbols(&self) -> &SymbolTable {
+        &self.table
+    }
+
+    pub fn into_symbols(self) -> SymbolTable {
+        self.table
+    }
+
+    /// Resolves the whole file, reporting every undefined variable at once
+    pub fn resolve(&mut self, ast: &[AstNode]) -> Result<()> {
+        self.resolve_statements(ast);
+
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Diagnostics(self.errors.clone()).into())
+        }
+    }
+
+    fn resolve_statements(&mut self, statements: &[AstNode]) {
+        for statement in statements {
+            self.resolve_node(statement);
+        }
+    }
+
+    fn resolve_node(&mut self, node: &AstNode) {
+        let outer = std::mem::replace(&mut self.span, node.span());
+        self.resolve_node_kind(node);
+        self.span = outer;
+    }
+
+    fn resolve_node_kind(&mut self, node: &AstNode) {
+        match node {
+            AstNode::Program { statements, .. } => self.resolve_statements(statements),
+            AstNode::ExpressionStatement { expression, .. } | AstNode::PrintStatement { expression, .. } => {
+                self.resolve_expression(expression);
+            }
+            AstNode::VariableDeclaration { name, var_type, value, .. } => {
+                self.resolve_expression(value);
+                self.declare(name, SymbolKind::Variable, var_type.clone(), self.span);
+            }
+            AstNode::ConstantDeclaration { name, const_type, value, .. } => {
+                self.resolve_expression(value);
+                self.declare(name, SymbolKind::Constant, const_type.clone(), self.span);
+            }
+            AstNode::FunctionDeclaration { params, body, .. } => {
+                // Functions nested in another body are generated as closures
+                let kind = if self.current_kind() == ScopeKind::Program {
+                    ScopeKind::Function
+                } else {
+                    ScopeKind::Closure
+                };
+                self.resolve_block(kind, body, params);
+            }
+            AstNode::ClassDeclaration { members, .. }
+            | AstNode::TraitDeclaration { members, .. }
+            | AstNode::EnumDeclaration { members, .. } => {
+                self.push_scope(ScopeKind::Class, &[]);
+                self.resolve_members(members);
+                self.stack.pop();
+            }
+            AstNode::InterfaceDeclaration { .. } => {}
+            AstNode::IfStatement { .. } | AstNode::WhileLoop { .. } | AstNode::ForLoop { .. } => {
+                let Ok(()) = self.walk_control_flow(node);
+            }
+        }
+    }
+
+    fn resolve_members(&mut self, members: &[ClassMember]) {
+        for member in members {
+            match member {
+                ClassMember::TraitUse(_) => {}
+                ClassMember::Property(property) => {
+                    if let Some(default) = &property.default {
+                        self.span = property.span;
+                        self.resolve_expression(default);
+                    }
+                }
+                ClassMember::Constructor(constructor) => {
+                    self.span = constructor.span;
+                    let params: Vec<Parameter> = constructor.params.iter()
+                        .map(|param| param.param.clone())
+                        .collect();
+                    self.resolve_block(ScopeKind::Function, &constructor.body, &params);
+                }
+                ClassMember::Method(method) => {
+                    if let Some(body) = &method.body {
+                        self.span = method.span;
+                        self.resolve_block(ScopeKind::Function, body, &method.params);
+                    }
+                }
+            }
+        }
+    }
+
+    fn resolve_block(&mut self, kind: ScopeKind, block: &Block, params: &[Parameter]) {
+        self.push_scope(kind, params);
+        self.resolve_statements(&block.statements);
+        if let Some(return_stmt) = &block.return_statement
+            && let Some(expr) = &return_stmt.expression
+        {
+            let outer = std::mem::replace(&mut self.span, return_stmt.span);
+            self.resolve_expression(expr);
+            self.span = outer;
+        }
+        self.stack.pop();
+    }
+
+    fn resolve_expression(&mut self, expr: &Expression) {
+        match expr {
+            Expression::Literal(_) | Expression::This | Expression::StaticConstant { .. } => {}
+            Expression::Variable { name, span } => self.reference(name, *span, false),
+            // Constants are only told apart by the type checker, after the checks of the resolver.
+            // Code generation resolves closures again, where they mustn't become captures
+            Expression::Constant { name, span } => self.reference_constant(name, *span),
+            Expression::FunctionCall(call) => self.resolve_all(&call.args),
+            Expression::Parenthesized(inner) => self.resolve_expression(inner),
+            Expression::Closure { params, body, .. } => match body {
+                ClosureBody::Block(block) => self.resolve_block(ScopeKind::Closure, block, params),
+                ClosureBody::Expression(expr) => {
+                    self.push_scope(ScopeKind::Closure, params);
+                    self.resolve_expression(expr);
+                    self.stack.pop();
+                }
+            },
+            Expression::ArrayLiteral { elements } => self.resolve_all(elements),
+            Expression::PostfixChain { base, suffixes } => {
+                self.resolve_expression(base);
+                for suffix in suffixes {
+                    match suffix {
+                        PostfixSuffix::Index(index) => self.resolve_expression(index),
+                        PostfixSuffix::MethodCall(call) | PostfixSuffix::NullsafeMethodCall(call) => {
+                            self.resolve_all(&call.args);
+                        }
+                        PostfixSuffix::Property(_) | PostfixSuffix::NullsafeProperty(_) => {}
+                    }
+                }
+            }
+            Expression::BinaryOp { left, right, .. } => {
+                self.resolve_expression(left);
+                self.resolve_expression(right);
+            }
+            Expression::UnaryOp { operand, .. } => self.resolve_expression(operand),
+            Expression::Assignment { target, value, .. } => {
+                self.resolve_target(target);
+                self.resolve_expression(value);
+            }
+            Expression::Update { target, .. } => self.resolve_target(target),
+            Expression::New { args, .. }
+            | Expression::StaticCall { args, .. }
+            | Expression::ParentCall { args, .. } => self.resolve_all(args),
+            Expression::Match { subject, arms } => {
+                self.resolve_expression(subject);
+                for arm in arms {
+                    for pattern in &arm.patterns {
+                        if let MatchPattern::Value(value) = pattern {
+                            self.resolve_expression(value);
+                        }
+                    }
+                    match &arm.body {
+                        MatchArmBody::Expression(body) => self.resolve_expression(body),
+                        MatchArmBody::Block(block) => self.resolve_block(ScopeKind::Block, block, &[]),
+                    }
+                }
+            }
+        }
+    }
+
+    /// A variable being assigned, the object or array around a property or
+    /// index being assigned is only read
+    fn resolve_target(&mut self, target: &Expression) {
+        match target {
+            Expression::Variable { name, span } => self.reference(name, *span, true),
+            other => self.resolve_expression(other),
+        }
+    }
+
+    fn resolve_all(&mut self, exprs: &[Expression]) {
+        for expr in exprs {
+            self.resolve_expression(expr);
+        }
+    }
+
+    fn push_scope(&mut self, kind: ScopeKind, params: &[Parameter]) {
+        let id = self.table.scopes.len();
+        self.table.scopes.push(Scope { kind, parent: self.stack.last().copied(), symbols: Vec::new() });
+        self.stack.push(id);
+
+        for param in params {
+            self.declare(&param.name, SymbolKind::Parameter, param.param_type.clone(), param.span);
+        }
+    }
+
+    fn current_kind(&self) -> ScopeKind {
+        self.stack.last().map_or(ScopeKind::Program, |id| self.table.scope(*id).kind)
+    }
+
+    fn declare(&mut self, name: &str, kind: SymbolKind, declared_type: Option<String>, span: Span) {
+        let Some(&scope) = self.stack.last() else {
+            return;
+        };
+        if kind == SymbolKind::Variable && self.declared_in_enclosing_block(name) {
+            let diagnostic = Diagnostic::error(format!("`{}` is already declared in an enclosing block of the same function", name))
+                .with_code(codes::SEMANTIC);
+            self.error(self.span, diagnostic);
+        }
+
+        let id = self.table.symbols.len();
+        self.table.symbols.push(Symbol { name: name.to_string(), kind, declared_type, scope, span });
+        self.table.scopes[scope].symbols.push(id);
+    }
+
+    /// Blocks don't start a new scope in PHP, so a variable shadowing one of an
+    /// enclosing block of the same function would overwrite it
+    fn declared_in_enclosing_block(&self, name: &str) -> bool {
+        let mut scopes = self.stack.iter().rev().map(|id| self.table.scope(*id));
+        if scopes.next().is_none_or(|current| current.kind != ScopeKind::Block) {
+            return false;
+        }
+
+        for scope in scopes {
+            let declared = scope.symbols.iter()
+                .map(|id| self.table.symbol(*id))
+                .any(|symbol| symbol.name == name && symbol.kind != SymbolKind::Constant);
+
+            if declared {
+                return true;
+            }
+            if scope.kind != ScopeKind::Block {
+                return false;
+            }
+        }
+        false
+    }
+
+    fn reference(&mut self, name: &str, span: Span, write: bool) {
+        let Some(&scope) = self.stack.last() else {
+            return;
+        };
+
+        let symbol = match self.lookup(name) {
+            Lookup::Found(symbol) => Some(symbol),
+            Lookup::OutsideFunction => {
+                let diagnostic = Diagnostic::error(format!(
+                    "Undefined variable `{}`, functions can't read variables declared outside of them",
+                    name
+                ));
+                let diagnostic = diagnostic.with_help(format!("pass `{}` as a parameter or declare it with `const`", name));
+                self.error(span, diagnostic.with_code(codes::UNDEFINED_VARIABLE));
+                None
+            }
+            Lookup::Missing => {
+                self.error(span, Diagnostic::error(format!("Undefined variable `{}`", name)).with_code(codes::UNDEFINED_VARIABLE));
+                None
+            }
+        };
+
+        self.table.references.push(Reference { name: name.to_string(), scope, symbol, write, span });
+    }
+
+    /// A use of a constant the checks already found, recorded when its declaration is in view
+    fn reference_constant(&mut self, name: &str, span: Span) {
+        if let (Some(&scope), Lookup::Found(symbol)) = (self.stack.last(), self.lookup(name)) {
+            self.table.references.push(Reference { name: name.to_string(), scope, symbol: Some(symbol), write: false, span });
+        }
+    }
+
+    /// Finds the latest declaration of `name` visible from the current scope, only
+    /// constants are visible across a function or class boundary
+    fn lookup(&self, name: &str) -> Lookup {
+        let mut crossed_boundary = false;
+
+        for &scope_id in self.stack.iter().rev() {
+            let scope = self.table.scope(scope_id);
+            let found = scope.symbols.iter().rev()
+                .copied()
+                .find(|id| self.table.symbol(*id).name == name);
+
+            if let Some(id) = found {
+                if crossed_boundary && self.table.symbol(id).kind != SymbolKind::Constant {
+                    return Lookup::OutsideFunction;
+                }
+                return Lookup::Found(id);
+            }
+            if matches!(scope.kind, ScopeKind::Function | ScopeKind::Class) {
+                crossed_boundary = true;
+            }
+        }
+
+        Lookup::Missing
+    }
+
+    fn error(&mut self, span: Span, diagnostic: Diagnostic) {
+        self.errors.push(diagnostic.with_label(Label::primary(span, "")));
+    }
+}
+
+impl ScopedWalk for Resolver {
+    type Error = Infallible;
+
+    fn walk_node(&mut self, node: &AstNode) -> Result<(), Infallible> {
+        self.resolve_node(node);
+        Ok(())
+    }
+
+    fn walk_expression(&mut self, expr: &Expression) -> Result<(), Infallible> {
+        self.resolve_expression(expr);
+        Ok(())
+    }
+
+    fn walk_block(&mut self, block: &Block) -> Result<(), Infallible> {
+        self.resolve_block(ScopeKind::Block, block, &[]);
+        Ok(())
+    }
+
+    fn with_scope(&mut self, walk: impl FnOnce(&mut Self) -> Result<(), Infallible>) -> Result<(), Infallible> {
+        self.push_scope(ScopeKind::Block, &[]);
+        let result = walk(self);
+        self.stack.pop();
+        result
+    }
+}
+
+enum Lookup {
+    Found(SymbolId),
+    /// Declared, but outside the function being resolved
+    OutsideFunction,
+    Missing,
+}
+
+/// A variable of the `use` clause of a closure
+#[derive(Debug, Clone, PartialEq)]
+pub struct Capture {
+    pub name: String,
+    /// Written inside the closure, so the change has to reach the variable outside
+    pub by_reference: bool,
+}
+
+/// Variables a closure with `params` and `body` uses from the scopes around it,
+/// in order of first use. These are the variables of its `use` clause
+pub fn captured_variables(params: &[Parameter], body: &Block) -> Vec<Capture> {
+    let mut resolver = Resolver {
+        table: SymbolTable::default(),
+        stack: Vec::new(),
+        errors: Vec::new(),
+        span: Span::default(),
+    };
+    resolver.resolve_block(ScopeKind::Closure, body, params);
+
+    let mut captured: Vec<Capture> = Vec::new();
+    for reference in resolver.table.references.iter().filter(|reference| reference.symbol.is_none()) {
+        match captured.iter_mut().find(|capture| capture.name == reference.name) {
+            Some(capture) => capture.by_reference |= reference.write,
+            None => captured.push(Capture { name: reference.name.clone(), by_reference: reference.write }),
+        }
+    }
+    captured
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::parse_with_error;
+
+    fn resolve(code: &str) -> Result<SymbolTable> {
+        let ast = parse_with_error(code)?;
+        let mut resolver = Resolver::new();
+        resolver.resolve(&ast)?;
+        Ok(resolver.into_symbols())
+    }
+
+    fn resolve_error(code: &str) -> String {
+        resolve(code).expect_err("Expected a resolution error").to_string()
+    }
+
+    #[test]
+    fn test_references_resolve_to_declarations() {
+        let table = resolve("let total = 1; fn add(total: int): int { return total + 1; } total++;").unwrap();
+
+        let targets: Vec<SymbolKind> = table.references().iter()
+            .map(|reference| table.symbol(reference.symbol.unwrap()).kind)
+            .collect();
+        assert_eq!(targets, [SymbolKind::Parameter, SymbolKind::Variable]);
+        assert_eq!(table.references_to(0).count(), 1);
+    }
+
+    #[test]
+    fn test_undefined_variables() {
+        assert_eq!(resolve_error("print(missing);"), "1:7: Undefined variable `missing`");
+        assert_eq!(resolve_error("let a = a + 1;"), "1:9: Undefined variable `a`");
+        assert_eq!(resolve_error("if (true) { let inner = 1; } inner = 2;"), "1:30: Undefined variable `inner`");
+        assert_eq!(
+            resolve_error("let x = 1; fn f() { return x; } fn g() { return y + y; }"),
+            "1:28: Undefined variable `x`, functions can't read variables declared outside of them\n1:49: Undefined variable `y`\n1:53: Undefined variable `y`"
+        );
+    }
+
+    #[test]
+    fn test_constants_are_visible_in_functions() {
+        assert!(resolve("const MAX = 10; fn f(): int { return MAX; } class Box { size: int = MAX; }").is_ok());
+        assert!(resolve("for (let i = 0; i < 3; i++) { print(i); }").is_ok());
+        assert!(resolve("let value = 1; let doubled = match value { 1 => value * 2, _ => 0 };").is_ok());
+    }
+
+    #[test]
+    fn test_block_cannot_shadow_enclosing_variable() {
+        assert_eq!(
+            resolve_error("fn f(flag: bool) { let x: int = 1; if (flag) { let x: string = 'a'; } }"),
+            "1:48: `x` is already declared in an enclosing block of the same function"
+        );
+        assert_eq!(
+            resolve_error("fn f(x: int) { while (x > 0) { let x = 'a'; } }"),
+            "1:32: `x` is already declared in an enclosing block of the same function"
+        );
+        assert!(resolve("let x = 1; fn f() { let x = 'a'; }").is_ok(), "functions have their own scope");
+        assert!(resolve("fn f(flag: bool) { if (flag) { let x = 1; } else { let x = 2; } }").is_ok());
+        assert!(resolve("fn f() { let x = 1; fn g() { let x = 2; } }").is_ok(), "nested functions have their own scope");
+    }
+
+    #[test]
+    fn test_nested_functions_capture_variables() {
+        let table = resolve(r#"
+            fn adder(a: int, b: int): int {
+                let first: int = a;
+                fn sum(): int {
+                    let result = first + b;
+                    return result;
+                }
+                return sum();
+            }
+        "#).unwrap();
+
+        let closure = table.scopes().iter().position(|scope| scope.kind == ScopeKind::Closure).unwrap();
+        let captured: Vec<&str> = table.captures(closure).iter().map(|symbol| symbol.name.as_str()).collect();
+        assert_eq!(captured, ["first", "b"]);
+    }
+
+    #[test]
+    fn test_captured_variables_of_closure_body() {
+        let ast = parse_with_error("fn outer() { fn inner(x: int) { let y = x + offset; if (y > limit) { return offset; } } }").unwrap();
+        let AstNode::FunctionDeclaration { body, .. } = &ast[0] else {
+            panic!("Expected FunctionDeclaration");
+        };
+        let AstNode::FunctionDeclaration { params, body, .. } = &body.statements[0] else {
+            panic!("Expected nested FunctionDeclaration");
+        };
+
+        let captured = captured_variables(params, body);
+        let names: Vec<&str> = captured.iter().map(|capture| capture.name.as_str()).collect();
+        assert_eq!(names, ["offset", "limit"]);
+    }
+
+    #[test]
+    fn test_written_captures_are_by_reference() {
+        let ast = parse_with_error("fn run(): int { let count = 0; fn inc() { count = count + 1; print(step); } inc(); return count; }").unwrap();
+        let AstNode::FunctionDeclaration { body, .. } = &ast[0] else {
+            panic!("Expected FunctionDeclaration");
+        };
+        let AstNode::FunctionDeclaration { params, body, .. } = &body.statements[1] else {
+            panic!("Expected nested FunctionDeclaration");
+        };
+
+        assert_eq!(captured_variables(params, body), [
+            Capture { name: "count".to_string(), by_reference: true },
+            Capture { name: "step".to_string(), by_reference: false },
+        ]);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{language::{AstNode, features::{block::ast::Block, expression::ast::Expression, if_else::ast::ElseClause, loop_for::ast::ForInit}}, semantic::analyzer::non_null_when};
+
+/// Walk over the statements that open blocks, shared by the passes reading the AST.
+/// Each pass decides what a scope is and whether null checks narrow anything
+pub trait ScopedWalk {
+    type Error;
+
+    fn walk_node(&mut self, node: &AstNode) -> Result<(), Self::Error>;
+
+    fn walk_expression(&mut self, expr: &Expression) -> Result<(), Self::Error>;
+
+    /// Walks a block inside a scope of its own
+    fn walk_block(&mut self, block: &Block) -> Result<(), Self::Error>;
+
+    /// Runs `walk` inside a new scope
+    fn with_scope(&mut self, walk: impl FnOnce(&mut Self) -> Result<(), Self::Error>) -> Result<(), Self::Error>;
+
+    /// Runs `walk` knowing the variables in `names` aren't null
+    fn with_non_null(
+        &mut self,
+        _names: &[String],
+        walk: impl FnOnce(&mut Self) -> Result<(), Self::Error>,
+    ) -> Result<(), Self::Error> {
+        walk(self)
+    }
+
+    /// `if`, `while` and `for` statements, other nodes are left to `walk_node`
+    fn walk_control_flow(&mut self, node: &AstNode) -> Result<(), Self::Error> {
+        match node {
+            AstNode::IfStatement { condition, then_block, else_clause, .. } => {
+                self.walk_expression(condition)?;
+                self.with_non_null(&non_null_when(condition, true), |walker| walker.walk_block(then_block))?;
+                if let Some(else_clause) = else_clause {
+                    self.with_non_null(&non_null_when(condition, false), |walker| {
+                        match else_clause.as_ref() {
+                            ElseClause::ElseIf(if_node) => walker.walk_node(if_node),
+                            ElseClause::Else(block) => walker.walk_block(block),
+                        }
+                    })?;
+                }
+                Ok(())
+            }
+            AstNode::WhileLoop { condition, body, .. } => {
+                self.walk_expression(condition)?;
+                self.with_non_null(&non_null_when(condition, true), |walker| walker.walk_block(body))
+            }
+            AstNode::ForLoop { init, condition, update, body, .. } => {
+                // The init binding lives in its own scope around the body
+                self.with_scope(|walker| {
+                    if let Some(init) = init {
+                        match init.as_ref() {
+                            ForInit::VariableDeclaration(declaration) => walker.walk_node(declaration)?,
+                            ForInit::Expression(expr) => walker.walk_expression(expr)?,
+                        }
+                    }
+                    for expr in [condition, update].into_iter().flatten() {
+                        walker.walk_expression(expr)?;
+                    }
+                    walker.walk_block(body)
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -3,52 +3,144 @@ use std::{path::Path, rc::Rc};
 use anyhow::{anyhow, Ok, Result};
 use walkdir::WalkDir;
 
-use crate::{codegen::CodeGenerator, config::{Config, NamespaceMode}, language::{AstNode, parser::CorrozyParserImpl}};
+use crate::{CorrozyError, codegen::CodeGenerator, config::{Config, NamespaceMode}, diagnostics::{Diagnostic, Diagnostics, codes}, language::{AstNode, parser::CorrozyParserImpl}, semantic::{ProjectAnalyzer, Resolver, SemanticAnalyzer}, typeck::TypeChecker};
 
 pub struct Transpiler {
     config: Config,
+    warnings: Vec<Diagnostic>,
 }
 
 impl Transpiler {
     pub fn new(config: Config) -> Self {
-        Self { config }
+        Self { config, warnings: Vec::new() }
+    }
+
+    /// Warnings found by the last `transpile_project`, kept when it fails
+    pub fn warnings(&self) -> &[Diagnostic] {
+        &self.warnings
     }
 
     pub fn transpile_project(
         &mut self,
         project_path: &Path
     ) -> Result<()> {
+        self.warnings.clear();
         let output_dir = project_path.join(&self.config.transpiler.output_dir);
-        std::fs::create_dir_all(&output_dir)?;
-        
+        std::fs::create_dir_all(&output_dir)
+            .map_err(|e| CorrozyError::io(&output_dir, e))?;
+
+        // Every file is parsed first so declarations can be checked across files
+        let mut files = Vec::new();
+        let mut errors = Vec::new();
+        let mut project_analyzer = ProjectAnalyzer::new();
+
         for entry in WalkDir::new(project_path) {
           let entry = entry?;
           if let Some(ext) = entry.path().extension() {
             if ext == "crz" {
-                self.transpile_file(entry.path(), &output_dir, &project_path)?;
+                let relative_path = entry.path()
+                    .strip_prefix(project_path)
+                    .map_err(|_| anyhow!("Input path is not within project path"))?
+                    .to_path_buf();
+                // The errors of every file are reported together
+                match self.parse_file(entry.path(), &relative_path, &mut project_analyzer) {
+                    Err(error) => errors.extend(Diagnostics::from_error(error)),
+                    ast => files.push((relative_path, ast?)),
+                }
             }
           }
         }
+
+        // Files that were analyzed still have their warnings reported
+        self.warnings = project_analyzer.warnings();
+        if !errors.is_empty() {
+            return Err(Diagnostics(errors).into());
+        }
+
+        project_analyzer.check()?;
+
+        for (relative_path, ast) in &files {
+            self.write_file(relative_path, ast, &output_dir)?;
+        }
         
         Ok(())
     }
 
-    /// Transpile a single file from Corrozy to PHP
-    fn transpile_file(
+    /// Parse and analyze a single Corrozy file, registering it for the project checks
+    fn parse_file(
         &self,
         input_path: &Path,
+        relative_path: &Path,
+        project_analyzer: &mut ProjectAnalyzer,
+    ) -> Result<Vec<AstNode>> {
+        let content = std::fs::read_to_string(input_path)
+            .map_err(|e| CorrozyError::io(input_path, e))?;
+
+        let (ast, analyzer) = self.analyze(relative_path, &content)?;
+
+        let namespace = self.file_namespace(self.output_relative_path(relative_path));
+        project_analyzer.add_file(relative_path, namespace.as_deref(), &ast);
+        project_analyzer.add_enum_matches(relative_path, namespace.as_deref(), analyzer.enum_matches());
+
+        Ok(ast)
+    }
+
+    /// Runs the checks of a single file on statements the caller parsed, like an editor
+    /// buffer that isn't saved yet. The checks across files of `transpile_project` are left out
+    pub fn check_source(&self, relative_path: &Path, ast: &mut [AstNode]) -> Result<()> {
+        self.check(relative_path, ast).map(|_| ())
+    }
+
+    fn analyze(&self, relative_path: &Path, content: &str) -> Result<(Vec<AstNode>, SemanticAnalyzer)> {
+        let mut parser = CorrozyParserImpl::new();
+        let mut ast = parser.parse(content)
+            .map_err(|e| in_file(relative_path, codes::SYNTAX, e))?;
+
+        let analyzer = self.check(relative_path, &mut ast)?;
+        Ok((ast, analyzer))
+    }
+
+    fn check(&self, relative_path: &Path, ast: &mut [AstNode]) -> Result<SemanticAnalyzer> {
+        Resolver::new().resolve(ast)
+            .map_err(|e| in_file(relative_path, codes::UNDEFINED_VARIABLE, e))?;
+
+        let mut analyzer = SemanticAnalyzer::new()
+            .require_let_mut(self.config.transpiler.require_let_mut);
+        analyzer.analyze(ast)
+            .map_err(|e| in_file(relative_path, codes::SEMANTIC, e))?;
+        TypeChecker::new().check(ast)
+            .map_err(|e| in_file(relative_path, codes::TYPE_MISMATCH, e))?;
+
+        Ok(analyzer)
+    }
+
+    /// Write the PHP code of a parsed file to the output directory
+    fn write_file(
+        &self,
+        relative_path: &Path,
+        ast: &[AstNode],
         output_dir: &Path,
-        project_path: &Path
     ) -> Result<()> {
-        let content = std::fs::read_to_string(input_path)?;
-        
-        let mut parser = CorrozyParserImpl::new();
-        let ast = parser.parse(&content)?;
+        let output_relative_path = self.output_relative_path(relative_path);
 
-        let relative_path = input_path
-            .strip_prefix(project_path)
-            .map_err(|_| anyhow!("Input path is not within project path"))?;
+        let php_code = self.generate_php(output_relative_path, ast)
+            .map_err(|e| in_file(relative_path, codes::CODEGEN, e))?;
         
+        let output_file_path = output_dir.join(output_relative_path).with_extension("php");
+        
+        if let Some(parent_dir) = output_file_path.parent() {
+            std::fs::create_dir_all(parent_dir)
+                .map_err(|e| CorrozyError::io(parent_dir, e))?;
+        }
+
+        std::fs::write(&output_file_path, php_code)
+            .map_err(|e| CorrozyError::io(&output_file_path, e))?;
+
+        Ok(())
+    }
+
+    /// Path of a file inside the output directory, without the source directory
+    fn output_relative_path<'a>(&self, relative_path: &'a Path) -> &'a Path {
         let src_dir_path = Path::new(&self.config.transpiler.src_dir);
         let src_dir_clean = if src_dir_path.starts_with("./") {
             src_dir_path.strip_prefix("./").unwrap()
@@ -56,24 +148,12 @@ impl Transpiler {
             src_dir_path
         };
         
-        let output_relative_path = if relative_path.starts_with(src_dir_clean) {
+        if relative_path.starts_with(src_dir_clean) {
             relative_path.strip_prefix(src_dir_clean)
                 .unwrap_or(relative_path)
         } else {
             relative_path
-        };
-
-        let php_code = self.generate_php(output_relative_path, &ast)?;
-        
-        let output_file_path = output_dir.join(output_relative_path).with_extension("php");
-        
-        if let Some(parent_dir) = output_file_path.parent() {
-            std::fs::create_dir_all(parent_dir)?;
         }
-
-        std::fs::write(output_file_path, php_code)?;
-
-        Ok(())
     }
 
     fn generate_php(
@@ -89,11 +169,8 @@ impl Transpiler {
             output.push_str("declare(strict_types=1);\n\n");
         }
         
-        if !self.config.namespace.base_namespace.is_empty() {
-            let namespace = self.generate_namespace(relative_path);
-            if let Some(ns) = namespace {
-                output.push_str(&format!("namespace {};\n\n", ns));
-            }
+        if let Some(ns) = self.file_namespace(relative_path) {
+            output.push_str(&format!("namespace {};\n\n", ns));
         }
 
         let code_gen = CodeGenerator::new(Rc::new(self.config.clone()));
@@ -103,6 +180,15 @@ impl Transpiler {
         Ok(output)
     }
 
+    /// Namespace declared at the top of the PHP of a file, `relative_path` is
+    /// relative to the output directory
+    fn file_namespace(&self, relative_path: &Path) -> Option<String> {
+        if self.config.namespace.base_namespace.is_empty() {
+            return None;
+        }
+        self.generate_namespace(relative_path)
+    }
+
     fn generate_namespace(&self, relative_path: &Path) -> Option<String> {
         match self.config.namespace.mode {
             NamespaceMode::None => None,
@@ -162,11 +248,23 @@ impl Transpiler {
     }
 }
 
+/// Turns the error of a file into diagnostics pointing into `path`, with the
+/// `code` of the stage that failed unless they carry a more precise one
+fn in_file(path: &Path, code: &'static str, error: anyhow::Error) -> anyhow::Error {
+    let diagnostics = Diagnostics::from_error(error).into_iter()
+        .map(|diagnostic| {
+            let code = diagnostic.code.unwrap_or(code);
+            diagnostic.with_code(code).in_file(path)
+        })
+        .collect();
+    Diagnostics(diagnostics).into()
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::Path;
 
-    use crate::{Transpiler, utils::test_utils::default_corrozy_config};
+    use crate::{Transpiler, diagnostics::{Diagnostics, codes}, utils::test_utils::{default_corrozy_config, parse_with_error}};
 
     #[test]
     fn test_auto_namespace_root_file() {
@@ -189,4 +287,53 @@ mod tests {
 
         assert_eq!(ns.unwrap(), "MyApp\\Utils\\Math");
     }
+
+    #[test]
+    fn test_file_errors_point_into_file() {
+        let error = crate::semantic::Resolver::new()
+            .resolve(&parse_with_error("print(a);\nprint(b);").unwrap())
+            .unwrap_err();
+        let error = super::in_file(Path::new("src/main.crz"), codes::SEMANTIC, error);
+
+        assert_eq!(
+            error.to_string(),
+            "src/main.crz:1:7: Undefined variable `a`\nsrc/main.crz:2:7: Undefined variable `b`"
+        );
+
+        let diagnostics = Diagnostics::from_error(error);
+        assert!(diagnostics.iter().all(|diagnostic| diagnostic.code == Some(codes::UNDEFINED_VARIABLE)));
+
+        let error = super::in_file(Path::new("src/main.crz"), codes::CODEGEN, anyhow::anyhow!("Unsupported node"));
+        assert_eq!(Diagnostics::from_error(error)[0].code, Some(codes::CODEGEN));
+    }
+
+    #[test]
+    fn test_warnings_are_kept_when_transpiling_fails() {
+        let directory = std::env::temp_dir().join(format!("corrozy-warnings-{}", std::process::id()));
+        std::fs::create_dir_all(directory.join("src")).unwrap();
+        std::fs::write(directory.join("src/status.crz"), "enum Status { Active, Banned }").unwrap();
+        std::fs::write(directory.join("src/main.crz"), "let label = match Status::Active { Status::Active => 'on' };").unwrap();
+        std::fs::write(directory.join("src/broken.crz"), "let = 1;").unwrap();
+
+        let mut transpiler = Transpiler::new(default_corrozy_config());
+        let error = transpiler.transpile_project(&directory).unwrap_err();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(Diagnostics::from_error(error).len(), 1);
+        assert_eq!(transpiler.warnings().len(), 1);
+        assert_eq!(transpiler.warnings()[0].code, Some(codes::NON_EXHAUSTIVE_MATCH));
+    }
+
+    #[test]
+    fn test_class_inside_auto_namespace() {
+        let transpiler = Transpiler::new(default_corrozy_config());
+        let ast = parse_with_error("class User { name: string; }").unwrap();
+
+        let php = transpiler.generate_php(
+            Path::new("models/user.crz"),
+            &ast
+        ).unwrap();
+
+        assert!(php.contains("namespace MyApp\\Models;\n\nclass User {\n    public string $name;\n}\n"));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,926 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Ok, Result};
+
+use crate::{diagnostics::{Diagnostic, Diagnostics, Label, codes}, language::{AstNode, Parameter, Span, features::{block::ast::Block, class::ast::ClassMember, closure::ast::ClosureBody, assignment::ast::AssignmentOperator, expression::ast::{BinaryOperator, Expression, Literal, UnaryOperator}, function_call::ast::FunctionCallExp, if_else::ast::ElseClause, loop_for::ast::ForInit, pattern_match::ast::{MatchArmBody, MatchPattern}, postfix::ast::PostfixSuffix}}, semantic::analyzer::{non_null_in_right, non_null_when}, typeck::types::Type};
+
+/// Declared signature of a function or method
+#[derive(Debug, Clone)]
+struct Signature {
+    /// Generic parameters in scope, the method ones plus those of its class
+    type_params: Vec<String>,
+    params: Vec<Parameter>,
+    return_type: Option<String>,
+}
+
+/// What the checker knows about a class, trait or enum declared in the file
+#[derive(Debug, Default)]
+struct ClassInfo {
+    type_params: Vec<String>,
+    properties: HashMap<String, Type>,
+    methods: HashMap<String, Signature>,
+    constructor: Option<Vec<Parameter>>,
+    is_enum: bool,
+}
+
+#[derive(Debug, Default)]
+struct Scope {
+    variables: HashMap<String, Type>,
+    functions: HashMap<String, Signature>,
+    /// Types inferred for the unannotated declarations of the scope, in order.
+    /// They are written into the AST once the scope ends, after later assignments widened them
+    inferred: Vec<(String, Option<Type>)>,
+    /// Only narrows variables of the enclosing scopes, declares nothing
+    narrowing: bool,
+    /// Names of `variables` declared with `const`
+    constants: HashSet<String>,
+}
+
+/// Function, method or closure whose body is being checked
+struct FunctionContext {
+    name: String,
+    return_type: Option<Type>,
+}
+
+/// Checks declared types against the types inferred for expressions,
+/// running after the semantic analysis and before code generation
+pub struct TypeChecker {
+    scopes: Vec<Scope>,
+    classes: HashMap<String, ClassInfo>,
+    current_class: Vec<String>,
+    /// Generic parameters in scope, erased to `any` while checking
+    type_params: Vec<String>,
+    functions: Vec<FunctionContext>,
+    errors: Vec<Diagnostic>,
+    /// Statement being checked, where errors point to
+    span: Span,
+}
+
+impl Default for TypeChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeChecker {
+    pub fn new() -> Self {
+        Self {
+            scopes: vec![Scope::default()],
+            classes: HashMap::new(),
+            current_class: Vec::new(),
+            type_params: Vec::new(),
+            functions: Vec::new(),
+            errors: Vec::new(),
+            span: Span::default(),
+        }
+    }
+
+    /// Checks the whole file, reporting every type error at once. Declarations without
+    /// an annotation get the type inferred from their value
+    pub fn check(&mut self, ast: &mut [AstNode]) -> Result<()> {
+        self.collect_classes(ast);
+        self.check_statements(ast);
+        self.settle_inferred(ast);
+
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Diagnostics(self.errors.clone()).into())
+        }
+    }
+
+    fn collect_classes(&mut self, ast: &[AstNode]) {
+        for node in ast {
+            let (name, type_params, members, is_enum) = match node {
+                AstNode::Program { statements, .. } => {
+                    self.collect_classes(statements);
+                    continue;
+                }
+                AstNode::ClassDeclaration { name, type_params, members, .. } => (name, type_params.clone(), members, false),
+                AstNode::TraitDeclaration { name, members, .. } => (name, Vec::new(), members, false),
+                AstNode::EnumDeclaration { name, members, .. } => (name, Vec::new(), members, true),
+                _ => continue,
+            };
+
+            let mut info = ClassInfo { type_params, is_enum, ..ClassInfo::default() };
+            for member in members {
+                match member {
+                    ClassMember::TraitUse(_) => {}
+                    ClassMember::Property(property) => {
+                        if let Some(prop_type) = &property.prop_type {
+                            let prop_type = Type::from_annotation(prop_type, &info.type_params);
+                            info.properties.insert(property.name.clone(), prop_type);
+                        }
+                    }
+                    ClassMember::Constructor(constructor) => {
+                        for param in constructor.params.iter().filter(|param| param.promoted.is_some()) {
+                            if let Some(param_type) = &param.param.param_type {
+                                let param_type = Type::from_annotation(param_type, &info.type_params);
+                                info.properties.insert(param.param.name.clone(), param_type);
+                            }
+                        }
+                        info.constructor = Some(constructor.params.iter().map(|param| param.param.clone()).collect());
+                    }
+                    ClassMember::Method(method) => {
+                        let signature = Signature {
+                            type_params: [info.type_params.as_slice(), &method.type_params].concat(),
+                            params: method.params.clone(),
+                            return_type: method.return_type.clone(),
+                        };
+                        info.methods.insert(method.name.clone(), signature);
+                    }
+                }
+            }
+
+            self.classes.insert(name.clone(), info);
+        }
+    }
+
+    fn check_statements(&mut self, statements: &mut [AstNode]) {
+        // Functions can be called before the statement declaring them
+        for statement in statements.iter() {
+            if let AstNode::FunctionDeclaration { name, type_params, params, return_type, .. } = statement {
+                let signature = Signature {
+                    type_params: type_params.clone(),
+                    params: params.clone(),
+                    return_type: return_type.clone(),
+                };
+                if let Some(scope) = self.scopes.last_mut() {
+                    scope.functions.insert(name.clone(), signature);
+                }
+            }
+        }
+
+        for statement in statements {
+            self.check_node(statement);
+        }
+    }
+
+    fn check_node(&mut self, node: &mut AstNode) {
+        let outer = std::mem::replace(&mut self.span, node.span());
+        self.check_node_kind(node);
+        self.span = outer;
+    }
+
+    fn check_node_kind(&mut self, node: &mut AstNode) {
+        let is_constant = matches!(node, AstNode::ConstantDeclaration { .. });
+        match node {
+            AstNode::Program { statements, .. } => {
+                self.check_statements(statements);
+                self.settle_inferred(statements);
+            }
+            AstNode::ExpressionStatement { expression, .. } => {
+                self.infer(expression);
+            }
+            AstNode::VariableDeclaration { name, var_type, value, .. }
+            | AstNode::ConstantDeclaration { name, const_type: var_type, value, .. } => {
+                let value_type = self.infer(value);
+
+                match var_type.as_deref().map(|var_type| self.annotation(var_type)) {
+                    Some(declared) => {
+                        if !value_type.is_assignable_to(&declared) {
+                            self.error(format!("Cannot assign `{}` to `{}` of type `{}`", value_type, name, declared));
+                        }
+                        self.declare(name, declared);
+                    }
+                    // The inferred type is checked like an annotation and documented with `@var`.
+                    // Closures are left alone, they are generated as functions
+                    None => {
+                        let inferred = (value_type.is_known() && !matches!(value.as_ref(), Expression::Closure { .. }))
+                            .then_some(value_type);
+                        self.declare(name, inferred.clone().unwrap_or(Type::Unknown));
+                        if let Some(scope) = self.scopes.last_mut() {
+                            scope.inferred.push((name.clone(), inferred));
+                        }
+                    }
+                }
+                if let Some(scope) = self.scopes.last_mut() {
+                    if is_constant {
+                        scope.constants.insert(name.clone());
+                    } else {
+                        scope.constants.remove(name.as_str());
+                    }
+                }
+            }
+            AstNode::PrintStatement { expression, .. } => {
+                self.infer(expression);
+            }
+            AstNode::FunctionDeclaration { name, type_params, params, return_type, body, .. } => {
+                self.check_function(name, type_params, params, return_type.as_deref(), body);
+            }
+            AstNode::ClassDeclaration { name, type_params, members, .. } => {
+                self.check_members(name, type_params, members);
+            }
+            AstNode::TraitDeclaration { name, members, .. } | AstNode::EnumDeclaration { name, members, .. } => {
+                self.check_members(name, &[], members);
+            }
+            AstNode::InterfaceDeclaration { .. } => {}
+            AstNode::IfStatement { condition, then_block, else_clause, .. } => {
+                self.infer(condition);
+                self.with_non_null(&non_null_when(condition, true), |checker| {
+                    checker.check_block(then_block, &[]);
+                });
+                if let Some(else_clause) = else_clause {
+                    self.with_non_null(&non_null_when(condition, false), |checker| {
+                        match else_clause.as_mut() {
+                            ElseClause::ElseIf(if_node) => checker.check_node(if_node),
+                            ElseClause::Else(block) => checker.check_block(block, &[]),
+                        }
+                    });
+                }
+            }
+            AstNode::WhileLoop { condition, body, .. } => {
+                self.infer(condition);
+                self.with_non_null(&non_null_when(condition, true), |checker| {
+                    checker.check_block(body, &[]);
+                });
+            }
+            AstNode::ForLoop { init, condition, update, body, .. } => {
+                self.scopes.push(Scope::default());
+                if let Some(init) = init {
+                    match init.as_mut() {
+                        ForInit::VariableDeclaration(declaration) => self.check_node(declaration),
+                        ForInit::Expression(expr) => {
+                            self.infer(expr);
+                        }
+                    }
+                }
+                for expr in [condition, update].into_iter().flatten() {
+                    self.infer(expr);
+                }
+                self.check_block(body, &[]);
+                if let Some(ForInit::VariableDeclaration(declaration)) = init.as_deref_mut() {
+                    self.settle_inferred(std::slice::from_mut(declaration.as_mut()));
+                }
+                self.scopes.pop();
+            }
+        }
+    }
+
+    fn check_members(&mut self, class_name: &str, type_params: &[String], members: &mut [ClassMember]) {
+        self.current_class.push(class_name.to_string());
+        let saved_type_params = self.type_params.len();
+        self.type_params.extend_from_slice(type_params);
+
+        for member in members {
+            match member {
+                ClassMember::TraitUse(_) => {}
+                ClassMember::Property(property) => {
+                    if let Some(default) = &mut property.default {
+                        self.span = property.span;
+                        let value_type = self.infer(default);
+                        if let Some(prop_type) = &property.prop_type {
+                            let declared = self.annotation(prop_type);
+                            if !value_type.is_assignable_to(&declared) {
+                                self.error(format!(
+                                    "Cannot assign `{}` to property `{}` of type `{}`",
+                                    value_type, property.name, declared
+                                ));
+                            }
+                        }
+                    }
+                }
+                ClassMember::Constructor(constructor) => {
+                    self.span = constructor.span;
+                    let params: Vec<Parameter> = constructor.params.iter()
+                        .map(|param| param.param.clone())
+                        .collect();
+                    let name = format!("{}::__construct", class_name);
+                    self.check_function(&name, &[], &params, None, &mut constructor.body);
+                }
+                ClassMember::Method(method) => {
+                    if let Some(body) = &mut method.body {
+                        self.span = method.span;
+                        let name = format!("{}::{}", class_name, method.name);
+                        self.check_function(&name, &method.type_params, &method.params, method.return_type.as_deref(), body);
+                    }
+                }
+            }
+        }
+
+        self.type_params.truncate(saved_type_params);
+        self.current_class.pop();
+    }
+
+    fn check_function(
+        &mut self,
+        name: &str,
+        type_params: &[String],
+        params: &[Parameter],
+        return_type: Option<&str>,
+        body: &mut Block,
+    ) {
+        let saved_type_params = self.type_params.len();
+        self.type_params.extend_from_slice(type_params);
+
+        let return_type = return_type.map(|return_type| self.annotation(return_type));
+        self.functions.push(FunctionContext { name: name.to_string(), return_type });
+        self.check_block(body, params);
+        self.functions.pop();
+
+        self.type_params.truncate(saved_type_params);
+    }
+
+    fn check_block(&mut self, block: &mut Block, params: &[Parameter]) {
+        self.push_scope(params);
+        self.check_statements(&mut block.statements);
+        if let Some(return_stmt) = &mut block.return_statement {
+            let outer = std::mem::replace(&mut self.span, return_stmt.span);
+            self.check_return(return_stmt.expression.as_deref_mut());
+            self.span = outer;
+        }
+        self.settle_inferred(&mut block.statements);
+        self.scopes.pop();
+    }
+
+    fn check_return(&mut self, expr: Option<&mut Expression>) {
+        let returned = expr.map(|expr| self.infer(expr));
+
+        let Some(FunctionContext { name, return_type: Some(expected) }) = self.functions.last() else {
+            return;
+        };
+
+        let error = match returned {
+            None if !Type::Null.is_assignable_to(expected) => {
+                format!("`{}` must return `{}`, found an empty `return`", name, expected)
+            }
+            Some(returned) if !returned.is_assignable_to(expected) => {
+                format!("`{}` must return `{}`, found `{}`", name, expected, returned)
+            }
+            _ => return,
+        };
+        self.error(error);
+    }
+
+    fn infer(&mut self, expr: &mut Expression) -> Type {
+        match expr {
+            Expression::Literal(literal) => match literal {
+                Literal::Integer(_) => Type::Int,
+                Literal::Float(_) => Type::Float,
+                Literal::String(_) => Type::String,
+                Literal::Boolean(_) => Type::Bool,
+                Literal::Null => Type::Null,
+            },
+            Expression::Variable { name, span } => {
+                let variable_type = self.lookup(name);
+                // Constants are generated without the `$` of a variable
+                if self.is_constant(name) {
+                    *expr = Expression::Constant { name: std::mem::take(name), span: *span };
+                }
+                variable_type
+            }
+            Expression::Constant { name, .. } => self.lookup(name),
+            Expression::FunctionCall(call) => {
+                match self.lookup_function(&call.name) {
+                    Some(signature) => self.check_call(&call.name, call.span, &signature, &mut call.args),
+                    None => self.infer_args(&mut call.args),
+                }
+            }
+            Expression::Parenthesized(inner) => self.infer(inner),
+            Expression::Closure { params, return_type, body } => {
+                match body {
+                    ClosureBody::Block(block) => self.check_function("closure", &[], params, return_type.as_deref(), block),
+                    ClosureBody::Expression(expr) => {
+                        self.push_scope(params);
+                        let returned = self.infer(expr);
+                        self.scopes.pop();
+
+                        if let Some(return_type) = return_type {
+                            let expected = self.annotation(return_type);
+                            if !returned.is_assignable_to(&expected) {
+                                self.error(format!("`closure` must return `{}`, found `{}`", expected, returned));
+                            }
+                        }
+                    }
+                }
+                Type::Named("Closure".to_string())
+            }
+            Expression::ArrayLiteral { elements } => {
+                let element_types = elements.iter_mut().map(|element| self.infer(element)).collect();
+                Type::Array(Box::new(Type::union(element_types)))
+            }
+            Expression::PostfixChain { base, suffixes } => self.infer_postfix(base, suffixes),
+            Expression::BinaryOp { left, op, right } => self.infer_binary(left, *op, right),
+            Expression::UnaryOp { op, operand } => {
+                let operand_type = self.infer(operand);
+                match op {
+                    UnaryOperator::Not => Type::Bool,
+                    UnaryOperator::Negate | UnaryOperator::Plus => {
+                        self.expect_numeric(op.to_php(), &operand_type);
+                        operand_type
+                    }
+                }
+            }
+            Expression::Assignment { target, op, value } => self.infer_assignment(target, *op, value),
+            Expression::Update { op, target, .. } => {
+                let target_type = self.infer(target);
+                self.expect_numeric(op.to_php(), &target_type);
+                target_type
+            }
+            Expression::New { class_name, args } => {
+                let constructor = self.classes.get(class_name)
+                    .and_then(|class| class.constructor.clone());
+                match constructor {
+                    Some(params) => {
+                        let signature = Signature { type_params: Vec::new(), params, return_type: None };
+                        self.check_call(&format!("{}::__construct", class_name), self.span, &signature, args);
+                    }
+                    None => {
+                        self.infer_args(args);
+                    }
+                }
+                Type::Named(class_name.clone())
+            }
+            Expression::This => match self.current_class.last() {
+                Some(class_name) => Type::Named(class_name.clone()),
+                None => Type::Unknown,
+            },
+            Expression::StaticConstant { class_name, .. } => {
+                if self.classes.get(class_name).is_some_and(|class| class.is_enum) {
+                    Type::Named(class_name.clone())
+                } else {
+                    Type::Unknown
+                }
+            }
+            Expression::StaticCall { args, .. } | Expression::ParentCall { args, .. } => self.infer_args(args),
+            Expression::Match { subject, arms } => {
+                self.infer(subject);
+
+                let mut arm_types = Vec::new();
+                for arm in arms {
+                    for pattern in &mut arm.patterns {
+                        if let MatchPattern::Value(value) = pattern {
+                            self.infer(value);
+                        }
+                    }
+                    match &mut arm.body {
+                        MatchArmBody::Expression(body) => arm_types.push(self.infer(body)),
+                        MatchArmBody::Block(block) => {
+                            self.check_block(block, &[]);
+                            arm_types.push(Type::Unknown);
+                        }
+                    }
+                }
+                Type::union(arm_types)
+            }
+        }
+    }
+
+    /// Infers the arguments of a call the checker knows nothing about
+    fn infer_args(&mut self, args: &mut [Expression]) -> Type {
+        for arg in args.iter_mut() {
+            self.infer(arg);
+        }
+        Type::Unknown
+    }
+
+    /// Checks the arguments of a call to `name`, errors point to `span`
+    fn check_call(&mut self, name: &str, span: Span, signature: &Signature, args: &mut [Expression]) -> Type {
+        let arg_types: Vec<Type> = args.iter_mut().map(|arg| self.infer(arg)).collect();
+
+        if arg_types.len() != signature.params.len() {
+            let plural = if signature.params.len() == 1 { "" } else { "s" };
+            self.error_at(span, format!(
+                "`{}` expects {} argument{}, found {}",
+                name, signature.params.len(), plural, arg_types.len()
+            ));
+        }
+
+        for (position, (param, arg_type)) in signature.params.iter().zip(&arg_types).enumerate() {
+            let Some(param_type) = &param.param_type else {
+                continue;
+            };
+            let expected = Type::from_annotation(param_type, &signature.type_params);
+            if !arg_type.is_assignable_to(&expected) {
+                self.error_at(span, format!(
+                    "Argument {} of `{}` expects `{}`, found `{}`",
+                    position + 1, name, expected, arg_type
+                ));
+            }
+        }
+
+        signature.return_type.as_deref()
+            .map(|return_type| Type::from_annotation(return_type, &signature.type_params))
+            .unwrap_or(Type::Unknown)
+    }
+
+    fn infer_postfix(&mut self, base: &mut Expression, suffixes: &mut [PostfixSuffix]) -> Type {
+        let mut current = self.infer(base);
+
+        for suffix in suffixes {
+            current = match suffix {
+                PostfixSuffix::Index(index) => {
+                    self.infer(index);
+                    match current {
+                        Type::Array(element) => *element,
+                        _ => Type::Unknown,
+                    }
+                }
+                PostfixSuffix::Property(name) => self.property_type(&current, name),
+                PostfixSuffix::MethodCall(call) => self.method_call_type(&current, call),
+                PostfixSuffix::NullsafeProperty(name) => {
+                    let property = self.property_type(&current.without_null(), name);
+                    Type::union(vec![property, Type::Null])
+                }
+                PostfixSuffix::NullsafeMethodCall(call) => {
+                    let returned = self.method_call_type(&current.without_null(), call);
+                    Type::union(vec![returned, Type::Null])
+                }
+            };
+        }
+
+        current
+    }
+
+    fn property_type(&self, receiver: &Type, property: &str) -> Type {
+        match receiver {
+            Type::Named(class_name) => self.classes.get(class_name)
+                .and_then(|class| class.properties.get(property))
+                .cloned()
+                .unwrap_or(Type::Unknown),
+            _ => Type::Unknown,
+        }
+    }
+
+    fn method_call_type(&mut self, receiver: &Type, call: &mut FunctionCallExp) -> Type {
+        let (class_name, signature) = match receiver {
+            Type::Named(class_name) => {
+                let signature = self.classes.get(class_name)
+                    .and_then(|class| class.methods.get(&call.name))
+                    .cloned();
+                (class_name.clone(), signature)
+            }
+            _ => (String::new(), None),
+        };
+
+        match signature {
+            Some(signature) => self.check_call(&format!("{}::{}", class_name, call.name), call.span, &signature, &mut call.args),
+            None => self.infer_args(&mut call.args),
+        }
+    }
+
+    fn infer_binary(&mut self, left: &mut Expression, op: BinaryOperator, right: &mut Expression) -> Type {
+        let left_type = self.infer(left);
+        let mut right_type = Type::Unknown;
+        self.with_non_null(&non_null_in_right(left, op), |checker| {
+            right_type = checker.infer(right);
+        });
+
+        match op {
+            BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply | BinaryOperator::Divide => {
+                let valid = self.expect_numeric(op.to_php(), &left_type) & self.expect_numeric(op.to_php(), &right_type);
+                if !valid {
+                    return Type::Unknown;
+                }
+                arithmetic_type(op, &left_type, &right_type)
+            }
+            BinaryOperator::Coalesce => match left_type {
+                Type::Unknown => Type::Unknown,
+                left_type => Type::union(vec![left_type.without_null(), right_type]),
+            },
+            _ => Type::Bool,
+        }
+    }
+
+    fn infer_assignment(&mut self, target: &mut Expression, op: AssignmentOperator, value: &mut Expression) -> Type {
+        let value_type = self.infer(value);
+        let target_type = self.infer(target);
+
+        let arithmetic = match op {
+            AssignmentOperator::Assign => None,
+            AssignmentOperator::AddAssign => Some(BinaryOperator::Add),
+            AssignmentOperator::SubtractAssign => Some(BinaryOperator::Subtract),
+            AssignmentOperator::MultiplyAssign => Some(BinaryOperator::Multiply),
+            AssignmentOperator::DivideAssign => Some(BinaryOperator::Divide),
+            AssignmentOperator::ConcatAssign => return value_type,
+        };
+        let stored = match arithmetic {
+            Some(arithmetic) => {
+                let valid = self.expect_numeric(op.to_php(), &target_type) & self.expect_numeric(op.to_php(), &value_type);
+                if !valid {
+                    return value_type;
+                }
+                arithmetic_type(arithmetic, &target_type, &value_type)
+            }
+            None => value_type.clone(),
+        };
+
+        if stored.is_assignable_to(&target_type) {
+            return value_type;
+        }
+        if let Expression::Variable { name, .. } = target
+            && self.widen_inferred(name, &stored)
+        {
+            return value_type;
+        }
+        if op == AssignmentOperator::Assign {
+            self.error_at(target.span().unwrap_or(self.span), format!(
+                "Cannot assign `{}` to {} of type `{}`",
+                value_type, describe_target(target), target_type
+            ));
+        }
+
+        value_type
+    }
+
+    /// An inferred number takes the other numbers assigned later:
+    /// after `let x = 10; x = x / 2;` the variable is an `int|float`
+    fn widen_inferred(&mut self, name: &str, assigned: &Type) -> bool {
+        let is_number = |value_type: &Type| value_type.is_known() && value_type.is_numeric();
+        let Some(index) = self.scopes.iter().rposition(|scope| !scope.narrowing && scope.variables.contains_key(name)) else {
+            return false;
+        };
+        let Some(Some(inferred)) = self.scopes[index].inferred.iter_mut().rev()
+            .find_map(|(declared, inferred)| (declared == name).then_some(inferred))
+        else {
+            return false;
+        };
+        if !is_number(inferred) || !is_number(assigned) {
+            return false;
+        }
+
+        *inferred = Type::union(vec![inferred.clone(), assigned.clone()]);
+        let widened = inferred.clone();
+        for scope in &mut self.scopes[index..] {
+            if let Some(variable) = scope.variables.get_mut(name) {
+                *variable = widened.clone();
+            }
+        }
+        true
+    }
+
+    /// Writes the inferred types of the scope being closed into its declarations
+    fn settle_inferred(&mut self, statements: &mut [AstNode]) {
+        let Some(scope) = self.scopes.last_mut() else {
+            return;
+        };
+        let declarations = statements.iter_mut().filter_map(|statement| match statement {
+            AstNode::VariableDeclaration { var_type, .. }
+            | AstNode::ConstantDeclaration { const_type: var_type, .. } if var_type.is_none() => Some(var_type),
+            _ => None,
+        });
+
+        for (var_type, (_, inferred)) in declarations.zip(scope.inferred.drain(..)) {
+            *var_type = inferred.map(|inferred| inferred.to_string());
+        }
+    }
+
+    /// Reports arithmetic on a type that isn't a number, returning whether it was valid
+    fn expect_numeric(&mut self, operator: &str, operand: &Type) -> bool {
+        if operand.is_numeric() {
+            return true;
+        }
+        self.error(format!("Operator `{}` expects numbers, found `{}`", operator, operand));
+        false
+    }
+
+    /// Runs `check` with the variables in `names` narrowed to non-null
+    fn with_non_null(&mut self, names: &[String], check: impl FnOnce(&mut Self)) {
+        let mut scope = Scope { narrowing: true, ..Scope::default() };
+        for name in names {
+            let narrowed = self.lookup(name).without_null();
+            scope.variables.insert(name.clone(), narrowed);
+        }
+
+        self.scopes.push(scope);
+        check(self);
+        self.scopes.pop();
+    }
+
+    fn push_scope(&mut self, params: &[Parameter]) {
+        let mut scope = Scope::default();
+        for param in params {
+            let param_type = match &param.param_type {
+                Some(param_type) => self.annotation(param_type),
+                None => Type::Unknown,
+            };
+            scope.variables.insert(param.name.clone(), param_type);
+        }
+        self.scopes.push(scope);
+    }
+
+    fn annotation(&self, type_str: &str) -> Type {
+        Type::from_annotation(type_str, &self.type_params)
+    }
+
+    fn declare(&mut self, name: &str, declared: Type) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.variables.insert(name.to_string(), declared);
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Type {
+        self.scopes.iter().rev()
+            .find_map(|scope| scope.variables.get(name))
+            .cloned()
+            .unwrap_or(Type::Unknown)
+    }
+
+    fn is_constant(&self, name: &str) -> bool {
+        self.scopes.iter().rev()
+            .find(|scope| !scope.narrowing && scope.variables.contains_key(name))
+            .is_some_and(|scope| scope.constants.contains(name))
+    }
+
+    fn lookup_function(&self, name: &str) -> Option<Signature> {
+        self.scopes.iter().rev()
+            .find_map(|scope| scope.functions.get(name))
+            .cloned()
+    }
+
+    /// Reports an error at the statement being checked
+    fn error(&mut self, message: String) {
+        self.error_at(self.span, message);
+    }
+
+    fn error_at(&mut self, span: Span, message: String) {
+        let diagnostic = Diagnostic::error(message)
+            .with_code(codes::TYPE_MISMATCH)
+            .with_label(Label::primary(span, ""));
+        self.errors.push(diagnostic);
+    }
+}
+
+/// Result of arithmetic on two numbers, `int` only stays `int` when nothing can make it a float
+fn arithmetic_type(op: BinaryOperator, left: &Type, right: &Type) -> Type {
+    match (left, right) {
+        (Type::Int, Type::Int) if op == BinaryOperator::Divide => Type::union(vec![Type::Int, Type::Float]),
+        (Type::Int, Type::Int) => Type::Int,
+        (Type::Unknown | Type::Mixed, _) | (_, Type::Unknown | Type::Mixed) => Type::Unknown,
+        (Type::Float, _) | (_, Type::Float) => Type::Float,
+        _ => Type::union(vec![Type::Int, Type::Float]),
+    }
+}
+
+/// How an assignment target is named in errors
+fn describe_target(target: &Expression) -> String {
+    match target {
+        Expression::Variable { name, .. } => format!("`{}`", name),
+        Expression::PostfixChain { suffixes, .. } => match suffixes.last() {
+            Some(PostfixSuffix::Property(name)) => format!("property `{}`", name),
+            _ => "element".to_string(),
+        },
+        _ => "target".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TypeChecker;
+    use crate::{language::AstNode, utils::test_utils::parse_with_error};
+
+    fn check(code: &str) -> anyhow::Result<()> {
+        let mut ast = parse_with_error(code)?;
+        TypeChecker::new().check(&mut ast)
+    }
+
+    fn check_error(code: &str) -> String {
+        check(code).expect_err("Expected a type error").to_string()
+    }
+
+    #[test]
+    fn test_declaration_types() {
+        assert_eq!(check_error(r#"let x: int = "hi";"#), "1:1: Cannot assign `string` to `x` of type `int`");
+        assert_eq!(check_error("const LIMIT: string = 10;"), "1:1: Cannot assign `int` to `LIMIT` of type `string`");
+        assert!(check("let price: float = 10; let name: string? = null; let anything: any = true;").is_ok());
+    }
+
+    #[test]
+    fn test_binary_operator_types() {
+        assert_eq!(check_error("let total: int = 1.5 * 2;"), "1:1: Cannot assign `float` to `total` of type `int`");
+        assert_eq!(check_error("let ratio: int = 4 / 2;"), "1:1: Cannot assign `int|float` to `ratio` of type `int`");
+        assert_eq!(check_error(r#"let label = "a" - 1;"#), "1:1: Operator `-` expects numbers, found `string`");
+        assert!(check("let sum: int = 1 + 2 * 3; let done: bool = sum > 3 && sum != 10;").is_ok());
+    }
+
+    #[test]
+    fn test_function_arguments() {
+        let code = "fn add(a: int, b: int): int { return a + b; }";
+
+        assert!(check(&format!("{} let sum: int = add(1, 2);", code)).is_ok());
+        assert_eq!(check_error(&format!("{} add(1);", code)), "1:47: `add` expects 2 arguments, found 1");
+        assert_eq!(
+            check_error(&format!(r#"{} add(1, "2");"#, code)),
+            "1:47: Argument 2 of `add` expects `int`, found `string`"
+        );
+        assert_eq!(
+            check_error(&format!("{} let label: string = add(1, 2);", code)),
+            "1:47: Cannot assign `int` to `label` of type `string`"
+        );
+    }
+
+    #[test]
+    fn test_errors_point_to_expressions() {
+        let code = "fn add(a: int, b: int): int { return a + b; }";
+
+        assert_eq!(
+            check_error(&format!(r#"{} let sum: int = 1 + add(1, "2");"#, code)),
+            "1:66: Argument 2 of `add` expects `int`, found `string`"
+        );
+        assert_eq!(
+            check_error("class Box { fn fill(size: int) {} } let box = new Box(); print(box.fill(true));"),
+            "1:68: Argument 1 of `Box::fill` expects `int`, found `bool`"
+        );
+        assert_eq!(check_error("let count = 1; if (true) { count = 'many'; }"), "1:28: Cannot assign `string` to `count` of type `int`");
+    }
+
+    #[test]
+    fn test_return_types() {
+        assert_eq!(check_error(r#"fn name(): int { return "x"; }"#), "1:18: `name` must return `int`, found `string`");
+        assert_eq!(check_error("fn name(): int { return; }"), "1:18: `name` must return `int`, found an empty `return`");
+        assert_eq!(
+            check_error("fn pick(flag: bool): string { if (flag) { return 1; } return 'x'; }"),
+            "1:43: `pick` must return `string`, found `int`"
+        );
+        assert!(check("fn name(): string? { return null; }").is_ok());
+        assert!(check("fn first<T>(items: T[]): T { return items[0]; }").is_ok());
+    }
+
+    #[test]
+    fn test_class_member_types() {
+        let code = r#"
+            class User {
+                visits: int = "none";
+
+                constructor(private name: string) {}
+
+                fn rename(name: string): string {
+                    this.name = 3;
+                    return this.name;
+                }
+            }
+            let user = new User(42);
+        "#;
+
+        assert_eq!(check_error(code), [
+            "3:17: Cannot assign `string` to property `visits` of type `int`",
+            "8:21: Cannot assign `int` to property `name` of type `string`",
+            "12:13: Argument 1 of `User::__construct` expects `string`, found `int`",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn test_nullable_narrowing() {
+        assert_eq!(
+            check_error("fn f(name: string?): string { return name; }"),
+            "1:31: `f` must return `string`, found `string?`"
+        );
+        assert!(check("fn f(name: string?): string { if (name != null) { return name; } return name ?? 'guest'; }").is_ok());
+    }
+
+    #[test]
+    fn test_declaration_inference() {
+        let mut ast = parse_with_error(r#"
+            fn half(value: int): int|float { return value / 2; }
+            let count = 3;
+            let ratio = half(count);
+            let many = count > 2;
+            let unknown = load();
+        "#).unwrap();
+        TypeChecker::new().check(&mut ast).unwrap();
+
+        let inferred: Vec<Option<String>> = ast.iter()
+            .filter_map(|node| match node {
+                AstNode::VariableDeclaration { var_type, .. } => Some(var_type.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(inferred, [Some("int".to_string()), Some("int|float".to_string()), Some("bool".to_string()), None]);
+    }
+
+    #[test]
+    fn test_inferred_types_are_checked() {
+        assert_eq!(check_error("let count = 1; count = 'many';"), "1:16: Cannot assign `string` to `count` of type `int`");
+        assert_eq!(
+            check_error("fn name(): string { return 'a'; } let size: int = name();"),
+            "1:35: Cannot assign `string` to `size` of type `int`"
+        );
+        assert!(check("let empty = null; empty = 'x';").is_ok());
+    }
+
+    #[test]
+    fn test_inferred_numbers_are_widened() {
+        assert!(check("let x = 10; x = x / 2;").is_ok());
+        assert!(check("let total = 1; total += 2.5;").is_ok());
+        assert_eq!(
+            check_error("fn half(): int { let x = 10; x = x / 2; return x; }"),
+            "1:41: `half` must return `int`, found `int|float`"
+        );
+        assert_eq!(check_error("let x: int = 10; x = x / 2;"), "1:18: Cannot assign `int|float` to `x` of type `int`");
+
+        let mut ast = parse_with_error("let x = 10; if (x > 5) { x = x / 2; } let y = x;").unwrap();
+        TypeChecker::new().check(&mut ast).unwrap();
+        let inferred: Vec<Option<String>> = ast.iter()
+            .filter_map(|node| match node {
+                AstNode::VariableDeclaration { var_type, .. } => Some(var_type.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(inferred, [Some("int|float".to_string()), Some("int|float".to_string())]);
+    }
+
+    #[test]
+    fn test_untyped_code_is_accepted() {
+        assert!(check("let value = load(); let total: int = value; value = 'x';").is_ok());
+    }
+}
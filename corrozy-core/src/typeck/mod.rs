@@ -0,0 +1,5 @@
+pub mod checker;
+pub mod types;
+
+pub use checker::TypeChecker;
+pub use types::Type;
@@ -0,0 +1,194 @@
+use std::fmt;
+
+use crate::language::features::types::generator::{split_generic, split_top_level};
+
+/// Static type of a Corrozy expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Int,
+    Float,
+    String,
+    Bool,
+    Null,
+    Array(Box<Type>),
+    /// Classes, interfaces and enums
+    Named(String),
+    Union(Vec<Type>),
+    /// Declared as `any`, or a generic parameter
+    Mixed,
+    /// Couldn't be inferred, accepted everywhere
+    Unknown,
+}
+
+impl Type {
+    /// Builds the type of an annotation, `type_params` are the generic parameters in scope
+    pub fn from_annotation(type_str: &str, type_params: &[String]) -> Self {
+        if let members @ [_, _, ..] = split_top_level(type_str, '|').as_slice() {
+            return Self::union(members.iter().map(|member| Self::from_annotation(member, type_params)).collect());
+        }
+
+        // Intersections can't be checked without the class hierarchy
+        if let [_, _, ..] = split_top_level(type_str, '&').as_slice() {
+            return Self::Unknown;
+        }
+
+        if let Some(inner) = type_str.strip_suffix('?') {
+            return Self::union(vec![Self::from_annotation(inner, type_params), Self::Null]);
+        }
+
+        if let Some(element) = type_str.strip_suffix("[]") {
+            return Self::Array(Box::new(Self::from_annotation(element, type_params)));
+        }
+
+        if let Some((name, arguments)) = split_generic(type_str) {
+            return match (name, arguments.as_slice()) {
+                ("Array", [element]) => Self::Array(Box::new(Self::from_annotation(element, type_params))),
+                ("Array", _) => Self::Array(Box::new(Self::Mixed)),
+                _ => Self::Named(name.to_string()),
+            };
+        }
+
+        match type_str {
+            "int" => Self::Int,
+            "float" => Self::Float,
+            "string" => Self::String,
+            "bool" => Self::Bool,
+            "any" => Self::Mixed,
+            name if type_params.iter().any(|param| param == name) => Self::Mixed,
+            name => Self::Named(name.to_string()),
+        }
+    }
+
+    /// Flattens nested unions and removes duplicates, a single member is returned as is
+    pub fn union(members: Vec<Type>) -> Self {
+        let mut flat: Vec<Type> = Vec::new();
+
+        for member in members {
+            let nested = match member {
+                Self::Union(nested) => nested,
+                Self::Unknown | Self::Mixed => return member,
+                other => vec![other],
+            };
+            for member in nested {
+                if !flat.contains(&member) {
+                    flat.push(member);
+                }
+            }
+        }
+
+        match flat.len() {
+            0 => Self::Unknown,
+            1 => flat.remove(0),
+            _ => Self::Union(flat),
+        }
+    }
+
+    /// Whether a value of this type can be stored where `target` is expected
+    pub fn is_assignable_to(&self, target: &Type) -> bool {
+        match (self, target) {
+            (Self::Unknown | Self::Mixed, _) | (_, Self::Unknown | Self::Mixed) => true,
+            (Self::Union(members), _) => members.iter().all(|member| member.is_assignable_to(target)),
+            (_, Self::Union(members)) => members.iter().any(|member| self.is_assignable_to(member)),
+            // PHP widens ints to floats even with strict types
+            (Self::Int, Self::Float) => true,
+            (Self::Array(element), Self::Array(target_element)) => element.is_assignable_to(target_element),
+            // The class hierarchy may span other files, so any class is accepted for another
+            (Self::Named(_), Self::Named(_)) => true,
+            _ => self == target,
+        }
+    }
+
+    /// Whether the type says something about the value, `Unknown` parts and a lone `null`
+    /// aren't worth declaring
+    pub fn is_known(&self) -> bool {
+        match self {
+            Self::Unknown | Self::Null => false,
+            Self::Array(element) => element.is_known(),
+            Self::Union(members) => !members.is_empty() && members.iter().all(|member| *member == Self::Null || member.is_known()),
+            _ => true,
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        match self {
+            Self::Int | Self::Float | Self::Unknown | Self::Mixed => true,
+            Self::Union(members) => members.iter().all(Self::is_numeric),
+            _ => false,
+        }
+    }
+
+    /// The type once `null` has been ruled out, nothing is left of a lone `null`:
+    /// the empty union, which disappears in the union with another type
+    pub fn without_null(&self) -> Self {
+        match self {
+            Self::Union(members) => Self::union(
+                members.iter().filter(|member| **member != Self::Null).cloned().collect()
+            ),
+            Self::Null => Self::Union(Vec::new()),
+            other => other.clone(),
+        }
+    }
+}
+
+/// Types are shown with the Corrozy spelling: `int?`, `int|float`, `User[]`
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int => write!(f, "int"),
+            Self::Float => write!(f, "float"),
+            Self::String => write!(f, "string"),
+            Self::Bool => write!(f, "bool"),
+            Self::Null => write!(f, "null"),
+            Self::Array(element) => match element.as_ref() {
+                Self::Union(_) => write!(f, "Array<{}>", element),
+                _ => write!(f, "{}[]", element),
+            },
+            Self::Named(name) => write!(f, "{}", name),
+            Self::Union(members) if members.is_empty() => write!(f, "never"),
+            // `null` is spelled as a trailing `?`: `int|float?`
+            Self::Union(members) => {
+                let shown: Vec<String> = members.iter()
+                    .filter(|member| **member != Self::Null)
+                    .map(|member| member.to_string())
+                    .collect();
+                write!(f, "{}", shown.join("|"))?;
+                if members.contains(&Self::Null) {
+                    write!(f, "?")?;
+                }
+                Ok(())
+            }
+            Self::Mixed => write!(f, "any"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Type;
+
+    #[test]
+    fn test_annotation_types() {
+        assert_eq!(Type::from_annotation("int?", &[]), Type::Union(vec![Type::Int, Type::Null]));
+        assert_eq!(Type::from_annotation("User[]", &[]), Type::Array(Box::new(Type::Named("User".to_string()))));
+        assert_eq!(Type::from_annotation("Array<T>", &["T".to_string()]), Type::Array(Box::new(Type::Mixed)));
+        assert_eq!(Type::from_annotation("int|float|int", &[]).to_string(), "int|float");
+        assert_eq!(Type::from_annotation("string?", &[]).to_string(), "string?");
+        assert_eq!(Type::from_annotation("int|float?", &[]).to_string(), "int|float?");
+    }
+
+    #[test]
+    fn test_assignability() {
+        let nullable_string = Type::from_annotation("string?", &[]);
+
+        assert!(Type::Int.is_assignable_to(&Type::Float));
+        assert!(!Type::Float.is_assignable_to(&Type::Int));
+        assert!(Type::Null.is_assignable_to(&nullable_string));
+        assert!(!nullable_string.is_assignable_to(&Type::String));
+        assert!(nullable_string.without_null().is_assignable_to(&Type::String));
+        assert_eq!(Type::union(vec![Type::Null.without_null(), Type::Int]), Type::Int);
+        assert!(Type::String.is_assignable_to(&Type::Mixed));
+        assert!(Type::Unknown.is_assignable_to(&Type::Int));
+        assert!(!Type::Named("User".to_string()).is_assignable_to(&Type::Int));
+    }
+}
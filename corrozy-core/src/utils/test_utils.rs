@@ -1,6 +1,8 @@
+use std::rc::Rc;
+
 use anyhow::Result;
 
-use crate::{Config, config::{NamespaceConfig, NamespaceMode, TranspilerConfig}, language::{AstNode, features::expression::ast::Expression, parser::CorrozyParserImpl}};
+use crate::{Config, codegen::CodeGenerator, config::{NamespaceConfig, NamespaceMode, TranspilerConfig}, language::{AstNode, features::expression::ast::Expression, parser::CorrozyParserImpl}};
 
 pub fn default_corrozy_config() -> Config {
     Config {
@@ -9,6 +11,8 @@ pub fn default_corrozy_config() -> Config {
             output_dir: "out".to_string(),
             strict_types: true,
             include_comments: false,
+            php_version: "8.1".to_string(),
+            require_let_mut: false,
         },
         namespace: NamespaceConfig {
             base_namespace: "MyApp".to_string(),
@@ -30,6 +34,13 @@ pub fn parse_with_error(code: &str) -> Result<Vec<AstNode>> {
     parser.parse(code)
 }
 
+pub fn generate_php_snippet(code: &str) -> String {
+    let mut parser: CorrozyParserImpl = CorrozyParserImpl::new();
+    let ast = parser.parse(code).unwrap();
+    let code_gen = CodeGenerator::new(Rc::new(default_corrozy_config()));
+    code_gen.generate(&ast).unwrap()
+}
+
 pub fn extract_literal_value(expr: &Expression) -> String {
     match expr {
         Expression::Literal(lit) => lit.to_php(),
@@ -0,0 +1,340 @@
+use std::path::Path;
+
+use corrozy_core::{Transpiler, diagnostics::{Diagnostic, Diagnostics, Severity}, language::{AstNode, Parameter, Span, parser::CorrozyParserImpl}, semantic::{Resolver, resolver::{Symbol, SymbolKind, SymbolTable}}, typeck::TypeChecker};
+use lsp_types as lsp;
+
+use crate::position::{offset, range};
+
+/// An open file, analyzed as it is in the editor
+pub struct Document {
+    text: String,
+    /// Statements that parsed, even when others have syntax errors
+    ast: Vec<AstNode>,
+    symbols: SymbolTable,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// A name under the cursor and where it is declared
+enum Target<'a> {
+    Variable(&'a Symbol),
+    Function { name: &'a str, type_params: &'a [String], params: &'a [Parameter], return_type: &'a Option<String>, span: Span },
+}
+
+impl Document {
+    pub fn new(path: &Path, text: String, transpiler: &Transpiler) -> Self {
+        let (mut ast, syntax_errors) = CorrozyParserImpl::new().parse_recovering(&text);
+
+        let diagnostics = if !syntax_errors.is_empty() {
+            syntax_errors
+        } else {
+            match transpiler.check_source(path, &mut ast) {
+                Ok(()) => Vec::new(),
+                Err(error) => Diagnostics::from_error(error),
+            }
+        };
+        // The checks stop at the first stage with errors, navigation keeps what it can
+        if !diagnostics.is_empty() {
+            let _ = TypeChecker::new().check(&mut ast);
+        }
+        let mut resolver = Resolver::new();
+        let _ = resolver.resolve(&ast);
+
+        Self { text, ast, symbols: resolver.into_symbols(), diagnostics }
+    }
+
+    pub fn diagnostics(&self, uri: &lsp::Url) -> Vec<lsp::Diagnostic> {
+        self.diagnostics.iter().map(|diagnostic| {
+            let mut message = diagnostic.message.clone();
+            for help in &diagnostic.help {
+                message.push_str(&format!("\nhelp: {}", help));
+            }
+
+            let related: Vec<lsp::DiagnosticRelatedInformation> = diagnostic.labels.iter()
+                .filter(|label| !label.primary)
+                .map(|label| lsp::DiagnosticRelatedInformation {
+                    location: lsp::Location::new(uri.clone(), range(&self.text, label.span)),
+                    message: label.message.clone(),
+                })
+                .collect();
+
+            lsp::Diagnostic {
+                range: range(&self.text, diagnostic.span().unwrap_or_default()),
+                severity: Some(match diagnostic.severity {
+                    Severity::Error => lsp::DiagnosticSeverity::ERROR,
+                    Severity::Warning => lsp::DiagnosticSeverity::WARNING,
+                }),
+                code: diagnostic.code.map(|code| lsp::NumberOrString::String(code.to_string())),
+                source: Some("corrozy".to_string()),
+                message,
+                related_information: (!related.is_empty()).then_some(related),
+                ..lsp::Diagnostic::default()
+            }
+        }).collect()
+    }
+
+    /// Functions and constants of the file, with the functions declared inside functions
+    pub fn symbols(&self) -> Vec<lsp::DocumentSymbol> {
+        self.symbols_in(&self.ast)
+    }
+
+    fn symbols_in(&self, statements: &[AstNode]) -> Vec<lsp::DocumentSymbol> {
+        statements.iter().filter_map(|statement| {
+            let (name, detail, kind, children) = match statement {
+                AstNode::FunctionDeclaration { name, type_params, params, return_type, body, .. } => (
+                    name,
+                    signature(name, type_params, params, return_type),
+                    lsp::SymbolKind::FUNCTION,
+                    Some(self.symbols_in(&body.statements)),
+                ),
+                AstNode::ConstantDeclaration { name, const_type, .. } => (
+                    name,
+                    declaration("const", name, const_type),
+                    lsp::SymbolKind::CONSTANT,
+                    None,
+                ),
+                _ => return None,
+            };
+
+            let span = statement.span();
+            #[allow(deprecated)]
+            Some(lsp::DocumentSymbol {
+                name: name.clone(),
+                detail: Some(detail),
+                kind,
+                tags: None,
+                deprecated: None,
+                range: range(&self.text, span),
+                selection_range: range(&self.text, self.name_span(span, name)),
+                children: children.filter(|children| !children.is_empty()),
+            })
+        }).collect()
+    }
+
+    /// The declaration of the name under the cursor, as Corrozy code
+    pub fn hover(&self, position: lsp::Position) -> Option<lsp::Hover> {
+        let (word, name) = self.word_at(position)?;
+
+        let code = match self.target(word, name)? {
+            Target::Variable(symbol) => match symbol.kind {
+                SymbolKind::Variable => declaration("let", &symbol.name, &symbol.declared_type),
+                SymbolKind::Constant => declaration("const", &symbol.name, &symbol.declared_type),
+                SymbolKind::Parameter => declaration("", &symbol.name, &symbol.declared_type),
+            },
+            Target::Function { name, type_params, params, return_type, .. } => {
+                signature(name, type_params, params, return_type)
+            }
+        };
+
+        Some(lsp::Hover {
+            contents: lsp::HoverContents::Markup(lsp::MarkupContent {
+                kind: lsp::MarkupKind::Markdown,
+                value: format!("```corrozy\n{}\n```", code),
+            }),
+            range: Some(range(&self.text, word)),
+        })
+    }
+
+    /// Where the variable or function under the cursor is declared
+    pub fn definition(&self, uri: &lsp::Url, position: lsp::Position) -> Option<lsp::Location> {
+        let (word, name) = self.word_at(position)?;
+        let span = match self.target(word, name)? {
+            Target::Variable(symbol) => self.declared_name(symbol),
+            Target::Function { name, span, .. } => self.name_span(span, name),
+        };
+        Some(lsp::Location::new(uri.clone(), range(&self.text, span)))
+    }
+
+    /// What the identifier `name` written at `word` stands for
+    fn target(&self, word: Span, name: &str) -> Option<Target<'_>> {
+        // On the name of a declaration
+        let declared = self.symbols.symbols().iter()
+            .find(|symbol| symbol.name == name && self.declared_name(symbol).start == word.start);
+        if let Some(symbol) = declared {
+            return Some(Target::Variable(symbol));
+        }
+
+        // A use of a variable
+        let used = self.symbols.references().iter()
+            .find(|reference| reference.span.start == word.start)
+            .and_then(|reference| reference.symbol);
+        if let Some(symbol) = used {
+            return Some(Target::Variable(self.symbols.symbol(symbol)));
+        }
+
+        find_function(&self.ast, name)
+    }
+
+    /// Where the name of `symbol` is written in its declaration
+    fn declared_name(&self, symbol: &Symbol) -> Span {
+        match symbol.kind {
+            SymbolKind::Parameter => symbol.span,
+            SymbolKind::Variable | SymbolKind::Constant => self.name_span(symbol.span, &symbol.name),
+        }
+    }
+
+    /// Span of the identifier under the cursor and its text
+    fn word_at(&self, position: lsp::Position) -> Option<(Span, &str)> {
+        let offset = offset(&self.text, position);
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+        let start = self.text[..offset].char_indices().rev()
+            .take_while(|(_, c)| is_word(*c))
+            .last()
+            .map_or(offset, |(index, _)| index);
+        let end = self.text[offset..].find(|c: char| !is_word(c)).map_or(self.text.len(), |index| offset + index);
+
+        let word = &self.text[start..end];
+        (!word.is_empty() && !word.starts_with(|c: char| c.is_ascii_digit()))
+            .then(|| (Span::at(&self.text, start, end), word))
+    }
+
+    /// The first time `name` appears as a whole word in the declaration at `span`: the
+    /// name right after `let`, `const` or `fn`. The whole statement when it can't be found
+    fn name_span(&self, span: Span, name: &str) -> Span {
+        let statement = &self.text[span.start..span.end];
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+        statement.match_indices(name)
+            .map(|(index, _)| span.start + index)
+            .find(|&start| {
+                let end = start + name.len();
+                !self.text[..start].ends_with(is_word) && !self.text[end..].starts_with(is_word)
+            })
+            .map_or(span, |start| Span::at(&self.text, start, start + name.len()))
+    }
+}
+
+/// The function named `name`, searching nested functions after the ones around them
+fn find_function<'a>(statements: &'a [AstNode], name: &str) -> Option<Target<'a>> {
+    let functions = statements.iter().filter_map(|statement| match statement {
+        AstNode::FunctionDeclaration { name, type_params, params, return_type, body, span } => {
+            Some((name, type_params, params, return_type, body, *span))
+        }
+        _ => None,
+    });
+
+    let mut nested = Vec::new();
+    for (function, type_params, params, return_type, body, span) in functions {
+        if function == name {
+            return Some(Target::Function { name: function, type_params, params, return_type, span });
+        }
+        nested.push(body);
+    }
+    nested.into_iter().find_map(|body| find_function(&body.statements, name))
+}
+
+/// `fn first<T>(items: T[]): T`
+fn signature(name: &str, type_params: &[String], params: &[Parameter], return_type: &Option<String>) -> String {
+    let mut result = format!("fn {}", name);
+    if !type_params.is_empty() {
+        result.push_str(&format!("<{}>", type_params.join(", ")));
+    }
+
+    let params: Vec<String> = params.iter()
+        .map(|param| declaration("", &param.name, &param.param_type))
+        .collect();
+    result.push_str(&format!("({})", params.join(", ")));
+
+    if let Some(return_type) = return_type {
+        result.push_str(&format!(": {}", return_type));
+    }
+    result
+}
+
+/// `let total: int`, without the keyword for parameters
+fn declaration(keyword: &str, name: &str, declared_type: &Option<String>) -> String {
+    let mut result = if keyword.is_empty() { name.to_string() } else { format!("{} {}", keyword, name) };
+    if let Some(declared_type) = declared_type {
+        result.push_str(&format!(": {}", declared_type));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use corrozy_core::{Config, Transpiler};
+    use lsp_types::{HoverContents, Position, Range, Url};
+
+    use super::Document;
+
+    fn document(text: &str) -> Document {
+        Document::new(Path::new("main.crz"), text.to_string(), &Transpiler::new(Config::default()))
+    }
+
+    fn hover(document: &Document, line: u32, character: u32) -> Option<String> {
+        let hover = document.hover(Position::new(line, character))?;
+        let HoverContents::Markup(content) = hover.contents else {
+            panic!("Expected markup");
+        };
+        Some(content.value)
+    }
+
+    fn uri() -> Url {
+        Url::parse("file:///project/src/main.crz").unwrap()
+    }
+
+    const SOURCE: &str = "\
+const LIMIT: int = 10;
+fn add(total: int, step: int): int {
+    let next = total + step;
+    return next;
+}
+let result = add(1, LIMIT);
+";
+
+    #[test]
+    fn test_diagnostics_point_into_the_source() {
+        let diagnostics = document("let a = ;\nprint(b);\n").diagnostics(&uri());
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new(Position::new(0, 8), Position::new(0, 9)));
+        assert_eq!(diagnostics[0].message, "expected an expression after `=`");
+
+        assert!(document(SOURCE).diagnostics(&uri()).is_empty());
+    }
+
+    #[test]
+    fn test_symbols_of_functions_and_constants() {
+        let symbols = document(SOURCE).symbols();
+        let names: Vec<(&str, Option<&str>)> = symbols.iter()
+            .map(|symbol| (symbol.name.as_str(), symbol.detail.as_deref()))
+            .collect();
+
+        assert_eq!(names, [("LIMIT", Some("const LIMIT: int")), ("add", Some("fn add(total: int, step: int): int"))]);
+        assert_eq!(symbols[1].selection_range, Range::new(Position::new(1, 3), Position::new(1, 6)));
+    }
+
+    #[test]
+    fn test_hover_shows_declared_and_inferred_types() {
+        let document = document(SOURCE);
+
+        assert_eq!(hover(&document, 2, 16).unwrap(), "```corrozy\ntotal: int\n```");
+        assert_eq!(hover(&document, 3, 12).unwrap(), "```corrozy\nlet next: int\n```");
+        assert_eq!(hover(&document, 5, 14).unwrap(), "```corrozy\nfn add(total: int, step: int): int\n```");
+        assert_eq!(hover(&document, 5, 22).unwrap(), "```corrozy\nconst LIMIT: int\n```");
+        assert_eq!(hover(&document, 2, 4), None);
+    }
+
+    #[test]
+    fn test_definition_of_variables_and_functions() {
+        let document = document(SOURCE);
+        let definition = |line, character| document.definition(&uri(), Position::new(line, character)).map(|location| location.range);
+
+        assert_eq!(definition(3, 12), Some(Range::new(Position::new(2, 8), Position::new(2, 12))));
+        assert_eq!(definition(2, 24), Some(Range::new(Position::new(1, 19), Position::new(1, 23))));
+        assert_eq!(definition(5, 13), Some(Range::new(Position::new(1, 3), Position::new(1, 6))));
+        assert_eq!(definition(5, 20), Some(Range::new(Position::new(0, 6), Position::new(0, 11))));
+    }
+
+    #[test]
+    fn test_definition_of_parameter_named_like_its_function() {
+        let document = document("fn count(count: int): int {\n    return count + 1;\n}\n");
+        let definition = |line, character| document.definition(&uri(), Position::new(line, character)).map(|location| location.range);
+
+        assert_eq!(definition(1, 13), Some(Range::new(Position::new(0, 9), Position::new(0, 14))));
+        assert_eq!(hover(&document, 0, 10).unwrap(), "```corrozy\ncount: int\n```");
+        assert_eq!(hover(&document, 0, 4).unwrap(), "```corrozy\nfn count(count: int): int\n```");
+    }
+}
@@ -0,0 +1,19 @@
+use anyhow::Result;
+use lsp_server::Connection;
+
+use crate::server::Server;
+
+mod document;
+mod position;
+mod server;
+
+/// Language server of Corrozy, started by the editor and spoken to over stdio
+fn main() -> Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    connection.initialize(serde_json::to_value(server::capabilities())?)?;
+    Server::new(connection).run()?;
+
+    io_threads.join()?;
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use corrozy_core::language::Span;
+use lsp_types::{Position, Range};
+
+/// LSP position of the byte `offset` of `text`, its character counted in UTF-16 units
+pub fn position(text: &str, offset: usize) -> Position {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    let character = before[line_start..].encode_utf16().count();
+
+    Position::new(line as u32, character as u32)
+}
+
+/// Byte offset of `position` in `text`, clamped to the end of its line
+pub fn offset(text: &str, position: Position) -> usize {
+    let line_start = match position.line {
+        0 => 0,
+        line => text.match_indices('\n')
+            .nth(line as usize - 1)
+            .map_or(text.len(), |(index, _)| index + 1),
+    };
+    let line = text[line_start..].split('\n').next().unwrap_or("");
+
+    let mut units = 0;
+    for (index, c) in line.char_indices() {
+        if units >= position.character as usize {
+            return line_start + index;
+        }
+        units += c.len_utf16();
+    }
+    line_start + line.len()
+}
+
+pub fn range(text: &str, span: Span) -> Range {
+    Range::new(position(text, span.start), position(text, span.end))
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Position;
+
+    use super::{offset, position};
+
+    #[test]
+    fn test_positions_count_utf16_units() {
+        let text = "let a = 1;\nprint(\"ñ😀\", b);";
+
+        assert_eq!(position(text, 11), Position::new(1, 0));
+        assert_eq!(position(text, 27), Position::new(1, 13));
+        assert_eq!(offset(text, Position::new(1, 13)), 27);
+        assert_eq!(offset(text, Position::new(0, 99)), 10);
+    }
+}
@@ -0,0 +1,218 @@
+use std::{collections::HashMap, path::{Path, PathBuf}};
+
+use anyhow::Result;
+use corrozy_core::{Config, Transpiler};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{self as lsp, notification::{self, Notification as _}, request::{self, Request as _}};
+
+use crate::document::Document;
+
+pub fn capabilities() -> lsp::ServerCapabilities {
+    lsp::ServerCapabilities {
+        text_document_sync: Some(lsp::TextDocumentSyncCapability::Kind(lsp::TextDocumentSyncKind::FULL)),
+        hover_provider: Some(lsp::HoverProviderCapability::Simple(true)),
+        definition_provider: Some(lsp::OneOf::Left(true)),
+        document_symbol_provider: Some(lsp::OneOf::Left(true)),
+        ..lsp::ServerCapabilities::default()
+    }
+}
+
+/// Open documents, analyzed again on every change
+pub struct Server {
+    connection: Connection,
+    documents: HashMap<lsp::Url, Document>,
+}
+
+impl Server {
+    pub fn new(connection: Connection) -> Self {
+        Self { connection, documents: HashMap::new() }
+    }
+
+    /// Answers the editor until it asks to shut down
+    pub fn run(mut self) -> Result<()> {
+        let receiver = self.connection.receiver.clone();
+
+        for message in &receiver {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    self.handle_request(request)?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Result<()> {
+        match request.method.as_str() {
+            request::HoverRequest::METHOD => self.respond::<request::HoverRequest>(request, |server, params| {
+                let position = params.text_document_position_params;
+                server.documents.get(&position.text_document.uri)?.hover(position.position)
+            }),
+            request::GotoDefinition::METHOD => self.respond::<request::GotoDefinition>(request, |server, params| {
+                let position = params.text_document_position_params;
+                let uri = &position.text_document.uri;
+                let location = server.documents.get(uri)?.definition(uri, position.position)?;
+                Some(lsp::GotoDefinitionResponse::Scalar(location))
+            }),
+            request::DocumentSymbolRequest::METHOD => self.respond::<request::DocumentSymbolRequest>(request, |server, params| {
+                let symbols = server.documents.get(&params.text_document.uri)?.symbols();
+                Some(lsp::DocumentSymbolResponse::Nested(symbols))
+            }),
+            _ => {
+                let message = format!("Unsupported request `{}`", request.method);
+                self.send(Response::new_err(request.id, ErrorCode::MethodNotFound as i32, message).into())
+            }
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<()> {
+        match notification.method.as_str() {
+            notification::DidOpenTextDocument::METHOD => {
+                let Some(params) = notification_params::<notification::DidOpenTextDocument>(notification) else {
+                    return Ok(());
+                };
+                self.update(params.text_document.uri, params.text_document.text)
+            }
+            notification::DidChangeTextDocument::METHOD => {
+                let Some(params) = notification_params::<notification::DidChangeTextDocument>(notification) else {
+                    return Ok(());
+                };
+                // The sync is `FULL`, the last change has the whole text
+                match params.content_changes.into_iter().last() {
+                    Some(change) => self.update(params.text_document.uri, change.text),
+                    None => Ok(()),
+                }
+            }
+            notification::DidCloseTextDocument::METHOD => {
+                let Some(params) = notification_params::<notification::DidCloseTextDocument>(notification) else {
+                    return Ok(());
+                };
+                self.documents.remove(&params.text_document.uri);
+                self.publish(params.text_document.uri, Vec::new())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn update(&mut self, uri: lsp::Url, text: String) -> Result<()> {
+        let path = uri.to_file_path().unwrap_or_else(|_| PathBuf::from(uri.path()));
+        let transpiler = Transpiler::new(project_config(&path));
+        let document = Document::new(&path, text, &transpiler);
+
+        let diagnostics = document.diagnostics(&uri);
+        self.documents.insert(uri.clone(), document);
+        self.publish(uri, diagnostics)
+    }
+
+    fn publish(&self, uri: lsp::Url, diagnostics: Vec<lsp::Diagnostic>) -> Result<()> {
+        let params = lsp::PublishDiagnosticsParams { uri, diagnostics, version: None };
+        self.send(Notification::new(notification::PublishDiagnostics::METHOD.to_string(), params).into())
+    }
+
+    fn respond<R: lsp::request::Request>(
+        &self,
+        request: Request,
+        handler: impl FnOnce(&Self, R::Params) -> R::Result,
+    ) -> Result<()> {
+        let params = match serde_json::from_value(request.params) {
+            Ok(params) => params,
+            Err(error) => {
+                let message = format!("Invalid params for `{}`: {}", request.method, error);
+                return self.send(Response::new_err(request.id, ErrorCode::InvalidParams as i32, message).into());
+            }
+        };
+        self.send(Response::new_ok(request.id, handler(self, params)).into())
+    }
+
+    fn send(&self, message: Message) -> Result<()> {
+        self.connection.sender.send(message)?;
+        Ok(())
+    }
+}
+
+/// Params of a notification. There is no reply to send an error in, so a malformed
+/// one is logged to stderr, the editor's log of the server, and ignored
+fn notification_params<N: lsp::notification::Notification>(notification: Notification) -> Option<N::Params> {
+    match serde_json::from_value(notification.params) {
+        Ok(params) => Some(params),
+        Err(error) => {
+            eprintln!("Ignoring `{}` with invalid params: {}", notification.method, error);
+            None
+        }
+    }
+}
+
+/// Configuration of the project the file is in, the nearest `corrozy.toml` above it
+fn project_config(path: &Path) -> Config {
+    path.ancestors()
+        .map(|directory| directory.join("corrozy.toml"))
+        .find(|config_path| config_path.is_file())
+        .and_then(|config_path| Config::load(&config_path).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId};
+    use lsp_types::{self as lsp, notification::{self, Notification as _}, request::{self, Request as _}};
+    use serde_json::json;
+
+    use super::Server;
+
+    #[test]
+    fn test_publishes_diagnostics_and_answers_requests() {
+        let (server, client) = Connection::memory();
+        let thread = std::thread::spawn(move || Server::new(server).run());
+
+        let uri = "file:///project/src/main.crz";
+        let open = json!({ "textDocument": { "uri": uri, "languageId": "corrozy", "version": 1, "text": "let a = 1;\nprint(b);\n" } });
+        client.sender.send(Notification::new(notification::DidOpenTextDocument::METHOD.to_string(), open).into()).unwrap();
+
+        let Message::Notification(published) = client.receiver.recv().unwrap() else {
+            panic!("Expected the diagnostics");
+        };
+        let published: lsp::PublishDiagnosticsParams = serde_json::from_value(published.params).unwrap();
+        assert_eq!(published.diagnostics.len(), 1);
+        assert_eq!(published.diagnostics[0].message, "Undefined variable `b`");
+
+        let hover = json!({ "textDocument": { "uri": uri }, "position": { "line": 0, "character": 4 } });
+        client.sender.send(Request::new(RequestId::from(1), request::HoverRequest::METHOD.to_string(), hover).into()).unwrap();
+        let Message::Response(response) = client.receiver.recv().unwrap() else {
+            panic!("Expected the hover");
+        };
+        assert_eq!(response.result.unwrap()["contents"]["value"], "```corrozy\nlet a: int\n```");
+
+        client.sender.send(Request::new(RequestId::from(2), request::Shutdown::METHOD.to_string(), ()).into()).unwrap();
+        client.receiver.recv().unwrap();
+        client.sender.send(Notification::new(notification::Exit::METHOD.to_string(), ()).into()).unwrap();
+        thread.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_malformed_params_keep_the_server_running() {
+        let (server, client) = Connection::memory();
+        let thread = std::thread::spawn(move || Server::new(server).run());
+
+        let open = json!({ "textDocument": { "uri": 42 } });
+        client.sender.send(Notification::new(notification::DidOpenTextDocument::METHOD.to_string(), open).into()).unwrap();
+
+        let hover = json!({ "position": "start" });
+        client.sender.send(Request::new(RequestId::from(1), request::HoverRequest::METHOD.to_string(), hover).into()).unwrap();
+        let Message::Response(response) = client.receiver.recv().unwrap() else {
+            panic!("Expected the hover error");
+        };
+        assert_eq!(response.id, RequestId::from(1));
+        assert_eq!(response.error.unwrap().code, ErrorCode::InvalidParams as i32);
+
+        client.sender.send(Request::new(RequestId::from(2), request::Shutdown::METHOD.to_string(), ()).into()).unwrap();
+        client.receiver.recv().unwrap();
+        client.sender.send(Notification::new(notification::Exit::METHOD.to_string(), ()).into()).unwrap();
+        thread.join().unwrap().unwrap();
+    }
+}
@@ -0,0 +1,124 @@
+use corrozy_core::language::{AstNode, features::{assignment::ast::AssignmentOperator, expression::ast::Expression, postfix::ast::PostfixSuffix}};
+
+fn parse_assignment(code: &str) -> (Expression, AssignmentOperator, Expression) {
+    match corrozy_core::utils::test_utils::parse_single_statement(code) {
+        AstNode::ExpressionStatement { expression, .. } => match *expression {
+            Expression::Assignment { target, op, value } => (*target, op, *value),
+            other => panic!("Expected Assignment, got: {:?}", other),
+        },
+        other => panic!("Expected ExpressionStatement, got: {:?}", other),
+    }
+}
+
+#[cfg(test)]
+mod assignment_parsing_tests {
+    use super::*;
+
+    #[test]
+    /// Test plain reassignment of a variable
+    fn test_simple_assignment() {
+        let (target, op, value) = parse_assignment("x = x + 1;");
+
+        assert!(matches!(target, Expression::Variable { ref name, .. } if name == "x"));
+        assert_eq!(op, AssignmentOperator::Assign);
+        assert!(matches!(value, Expression::BinaryOp { .. }));
+    }
+
+    #[test]
+    /// Test every compound assignment operator
+    fn test_compound_assignment_operators() {
+        let cases = [
+            ("total += price;", AssignmentOperator::AddAssign),
+            ("total -= price;", AssignmentOperator::SubtractAssign),
+            ("total *= 2;", AssignmentOperator::MultiplyAssign),
+            ("total /= 2;", AssignmentOperator::DivideAssign),
+            ("label .= 'px';", AssignmentOperator::ConcatAssign),
+        ];
+
+        for (code, expected) in cases {
+            let (_, op, _) = parse_assignment(code);
+            assert_eq!(op, expected, "Wrong operator for `{}`", code);
+        }
+    }
+
+    #[test]
+    /// Test property and index targets
+    fn test_postfix_targets() {
+        let (target, _, _) = parse_assignment("user.name = 'Diego';");
+        assert!(matches!(target,
+            Expression::PostfixChain { ref suffixes, .. } if matches!(suffixes.last(), Some(PostfixSuffix::Property(_)))
+        ));
+
+        let (target, _, _) = parse_assignment("items[0] = 1;");
+        assert!(matches!(target,
+            Expression::PostfixChain { ref suffixes, .. } if matches!(suffixes.last(), Some(PostfixSuffix::Index(_)))
+        ));
+    }
+
+    #[test]
+    /// Test chained assignment is right associative
+    fn test_chained_assignment() {
+        let (target, _, value) = parse_assignment("a = b = 0;");
+
+        assert!(matches!(target, Expression::Variable { ref name, .. } if name == "a"));
+        assert!(matches!(value, Expression::Assignment { .. }));
+    }
+
+    #[test]
+    /// Test equality is not mistaken for assignment
+    fn test_equality_is_not_assignment() {
+        match corrozy_core::utils::test_utils::parse_single_statement("x == 1;") {
+            AstNode::ExpressionStatement { expression, .. } => {
+                assert!(matches!(*expression, Expression::BinaryOp { .. }));
+            },
+            other => panic!("Expected ExpressionStatement, got: {:?}", other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod assignment_generation_tests {
+    use corrozy_core::utils::test_utils::generate_php_snippet;
+
+    #[test]
+    /// Test reassignment and compound assignment generation
+    fn test_assignment_generation() {
+        assert_eq!(generate_php_snippet("x = x + 1;"), "$x = $x + 1;");
+        assert_eq!(generate_php_snippet("total += price;"), "$total += $price;");
+        assert_eq!(generate_php_snippet("label .= 'px';"), "$label .= 'px';");
+    }
+
+    #[test]
+    /// Test property and index targets map to PHP accessors
+    fn test_postfix_target_generation() {
+        assert_eq!(generate_php_snippet("user.name = 'Diego';"), "$user->name = 'Diego';");
+        assert_eq!(generate_php_snippet("items[0] = 1;"), "$items[0] = 1;");
+    }
+
+    #[test]
+    /// Test assignment inside a for update clause
+    fn test_for_update_assignment() {
+        let php = generate_php_snippet("for (let i = 0; i < 3; i += 1) {}");
+        assert_eq!(php, "for ($i = 0; $i < 3; $i += 1) {\n}\n");
+    }
+}
+
+#[cfg(test)]
+mod assignment_syntax_error_tests {
+    use corrozy_core::utils::test_utils::parse_with_error;
+
+    #[test]
+    /// Test that non assignable targets cause parser error
+    fn test_invalid_targets() {
+        assert!(parse_with_error("1 = x;").is_err());
+        assert!(parse_with_error("a + b = c;").is_err());
+        assert!(parse_with_error("user.getName() = 'x';").is_err());
+    }
+
+    #[test]
+    /// Test that assignment without value causes parser error
+    fn test_missing_value() {
+        assert!(parse_with_error("x = ;").is_err());
+        assert!(parse_with_error("x += ;").is_err());
+    }
+}
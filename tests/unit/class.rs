@@ -0,0 +1,233 @@
+use corrozy_core::language::{AstNode, features::class::ast::{ClassMember, ClassModifier, Visibility}};
+
+#[cfg(test)]
+mod class_declaration_tests {
+    use corrozy_core::language::features::expression::ast::Expression;
+    use corrozy_core::utils::test_utils::{extract_literal_value, parse_single_statement};
+
+    use super::*;
+
+    #[test]
+    /// Test class with typed properties and visibility modifiers
+    fn test_class_properties() {
+        let ast = parse_single_statement("class User { public name: string; private visits: int = 0; email; }");
+
+        match ast {
+            AstNode::ClassDeclaration { name, members, .. } => {
+                assert_eq!(name, "User");
+                assert_eq!(members.len(), 3);
+
+                match &members[1] {
+                    ClassMember::Property(property) => {
+                        assert_eq!(property.visibility, Visibility::Private);
+                        assert_eq!(property.name, "visits");
+                        assert_eq!(property.prop_type.as_ref().unwrap(), "int");
+                        assert_eq!(extract_literal_value(property.default.as_ref().unwrap()), "0");
+                    },
+                    other => panic!("Expected Property, got: {:?}", other),
+                }
+
+                match &members[2] {
+                    ClassMember::Property(property) => {
+                        assert_eq!(property.visibility, Visibility::Public, "Visibility defaults to public");
+                        assert!(property.prop_type.is_none());
+                        assert!(property.default.is_none());
+                    },
+                    other => panic!("Expected Property, got: {:?}", other),
+                }
+            },
+            other => panic!("Expected ClassDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test constructor with promoted and plain parameters
+    fn test_class_constructor() {
+        let ast = parse_single_statement("class User { constructor(private email: string, name: string) { this.name = name; } }");
+
+        match ast {
+            AstNode::ClassDeclaration { members, .. } => match &members[0] {
+                ClassMember::Constructor(constructor) => {
+                    assert_eq!(constructor.visibility, Visibility::Public);
+                    assert_eq!(constructor.params.len(), 2);
+                    assert_eq!(constructor.params[0].promoted, Some(Visibility::Private));
+                    assert_eq!(constructor.params[0].param.name, "email");
+                    assert!(constructor.params[1].promoted.is_none());
+                    assert_eq!(constructor.body.statements.len(), 1);
+                },
+                other => panic!("Expected Constructor, got: {:?}", other),
+            },
+            other => panic!("Expected ClassDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test methods using `this` and `self`
+    fn test_class_methods() {
+        let ast = parse_single_statement(r#"
+            class Counter {
+                protected fn current(): int { return this.count; }
+                fn reset() { self.count = 0; }
+            }
+        "#);
+
+        match ast {
+            AstNode::ClassDeclaration { members, .. } => {
+                match &members[0] {
+                    ClassMember::Method(method) => {
+                        assert_eq!(method.visibility, Visibility::Protected);
+                        assert_eq!(method.name, "current");
+                        assert_eq!(method.return_type.as_ref().unwrap(), "int");
+                        let returned = method.body.as_ref().unwrap().return_statement.as_ref().unwrap().expression.as_ref().unwrap();
+                        assert!(matches!(returned.as_ref(),
+                            Expression::PostfixChain { base, .. } if matches!(**base, Expression::This)
+                        ));
+                    },
+                    other => panic!("Expected Method, got: {:?}", other),
+                }
+                assert!(matches!(&members[1], ClassMember::Method(method) if method.visibility == Visibility::Public));
+            },
+            other => panic!("Expected ClassDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test `new` expression with arguments
+    fn test_new_expression() {
+        let ast = parse_single_statement("let user = new User('Diego', 23);");
+
+        match ast {
+            AstNode::VariableDeclaration { value, .. } => match *value {
+                Expression::New { class_name, args } => {
+                    assert_eq!(class_name, "User");
+                    assert_eq!(args.len(), 2);
+                },
+                other => panic!("Expected New, got: {:?}", other),
+            },
+            other => panic!("Expected VariableDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test identifiers that start with a visibility keyword
+    fn test_keyword_prefixed_property_name() {
+        let ast = parse_single_statement("class Key { publicKey: string; }");
+
+        match ast {
+            AstNode::ClassDeclaration { members, .. } => {
+                assert!(matches!(&members[0], ClassMember::Property(property) if property.name == "publicKey"));
+            },
+            other => panic!("Expected ClassDeclaration, got: {:?}", other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod class_inheritance_tests {
+    use corrozy_core::language::features::expression::ast::Expression;
+    use corrozy_core::utils::test_utils::{parse_single_statement, parse_with_error};
+
+    use super::*;
+
+    #[test]
+    /// Test abstract class extending a parent with abstract and final methods
+    fn test_abstract_class_extends() {
+        let ast = parse_single_statement(
+            "abstract class Shape extends Model implements Drawable { abstract fn area(): float; public final fn save() { } }"
+        );
+
+        match ast {
+            AstNode::ClassDeclaration { modifier, name, extends, implements, members, .. } => {
+                assert_eq!(modifier, Some(ClassModifier::Abstract));
+                assert_eq!(name, "Shape");
+                assert_eq!(extends.as_deref(), Some("Model"));
+                assert_eq!(implements, vec!["Drawable"]);
+
+                match &members[0] {
+                    ClassMember::Method(method) => {
+                        assert!(method.is_abstract());
+                        assert!(method.body.is_none(), "Abstract methods have no body");
+                    },
+                    other => panic!("Expected Method, got: {:?}", other),
+                }
+
+                match &members[1] {
+                    ClassMember::Method(method) => {
+                        assert_eq!(method.modifier, Some(ClassModifier::Final));
+                        assert!(method.body.is_some());
+                    },
+                    other => panic!("Expected Method, got: {:?}", other),
+                }
+            },
+            other => panic!("Expected ClassDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test `parent.method()` and `super(...)` calls
+    fn test_parent_calls() {
+        let ast = parse_with_error("class Admin extends User { constructor() { super(1); parent.boot(); } }").unwrap();
+
+        match &ast[0] {
+            AstNode::ClassDeclaration { members, .. } => match &members[0] {
+                ClassMember::Constructor(constructor) => {
+                    let calls: Vec<&Expression> = constructor.body.statements.iter()
+                        .map(|statement| match statement {
+                            AstNode::ExpressionStatement { expression, .. } => expression.as_ref(),
+                            other => panic!("Expected ExpressionStatement, got: {:?}", other),
+                        })
+                        .collect();
+
+                    assert!(matches!(calls[0], Expression::ParentCall { method: None, args } if args.len() == 1));
+                    assert!(matches!(calls[1], Expression::ParentCall { method: Some(method), .. } if method == "boot"));
+                },
+                other => panic!("Expected Constructor, got: {:?}", other),
+            },
+            other => panic!("Expected ClassDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test invalid inheritance syntax
+    fn test_invalid_inheritance_syntax() {
+        let invalid_cases = vec![
+            "class A extends { }",                          // Missing parent
+            "class A extends B, C { }",                     // Single inheritance only
+            "class A implements B extends C { }",           // Extends comes first
+            "abstract final class A { }",                   // Only one modifier
+            "abstract class A { abstract fn f() { } }",     // Abstract method with body
+            "class A { fn f(); }",                          // Method without body
+            "class A extends B { fn f() { parent.name; } }", // Parent properties are not accessible
+        ];
+
+        for code in invalid_cases {
+            assert!(parse_with_error(code).is_err(), "Should fail: {}", code);
+        }
+    }
+}
+
+#[cfg(test)]
+mod class_syntax_error_tests {
+    use corrozy_core::utils::test_utils::parse_with_error;
+
+    #[test]
+    /// Test that class names must start with an uppercase letter
+    fn test_lowercase_class_name() {
+        assert!(parse_with_error("class user {}").is_err());
+    }
+
+    #[test]
+    /// Test that malformed members cause parser error
+    fn test_invalid_members() {
+        assert!(parse_with_error("class User { name: string }").is_err());
+        assert!(parse_with_error("class User { public public name; }").is_err());
+        assert!(parse_with_error("class User { let name = 1; }").is_err());
+    }
+
+    #[test]
+    /// Test that reserved words can't be used as names
+    fn test_reserved_words() {
+        assert!(parse_with_error("let this = 1;").is_err());
+        assert!(parse_with_error("let new = 1;").is_err());
+    }
+}
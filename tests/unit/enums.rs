@@ -0,0 +1,92 @@
+use corrozy_core::language::{AstNode, features::expression::ast::Expression};
+
+#[cfg(test)]
+mod enum_declaration_tests {
+    use corrozy_core::utils::test_utils::{extract_literal_value, parse_single_statement};
+
+    use super::*;
+
+    #[test]
+    /// Test backed enum with cases and a method
+    fn test_backed_enum() {
+        let ast = parse_single_statement(
+            "enum Status: string { Active = 'active', Inactive = 'inactive', fn label(): string { return 'x'; } }"
+        );
+
+        match ast {
+            AstNode::EnumDeclaration { name, backing_type, implements, cases, members, .. } => {
+                assert_eq!(name, "Status");
+                assert_eq!(backing_type.as_deref(), Some("string"));
+                assert!(implements.is_empty());
+                assert_eq!(cases.len(), 2);
+                assert_eq!(cases[0].name, "Active");
+                assert_eq!(extract_literal_value(cases[0].value.as_ref().unwrap()), "'active'");
+                assert_eq!(members.len(), 1);
+            },
+            other => panic!("Expected EnumDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test pure enum with trailing comma
+    fn test_pure_enum() {
+        let ast = parse_single_statement("enum Suit { Hearts, Spades, }");
+
+        match ast {
+            AstNode::EnumDeclaration { backing_type, cases, .. } => {
+                assert!(backing_type.is_none());
+                assert_eq!(cases.len(), 2);
+                assert!(cases.iter().all(|case| case.value.is_none()));
+            },
+            other => panic!("Expected EnumDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test enum cases and static calls in expressions
+    fn test_static_access() {
+        let ast = parse_single_statement("let status: Status = Status::from('active');");
+
+        match ast {
+            AstNode::VariableDeclaration { var_type, value, .. } => {
+                assert_eq!(var_type.as_deref(), Some("Status"));
+                assert!(matches!(*value, Expression::StaticCall { ref class_name, ref method, .. } if class_name == "Status" && method == "from"));
+            },
+            other => panic!("Expected VariableDeclaration, got: {:?}", other),
+        }
+
+        let ast = parse_single_statement("let status = Status::Active;");
+
+        match ast {
+            AstNode::VariableDeclaration { value, .. } => {
+                assert!(matches!(*value, Expression::StaticConstant { ref name, .. } if name == "Active"));
+            },
+            other => panic!("Expected VariableDeclaration, got: {:?}", other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod enum_syntax_error_tests {
+    use corrozy_core::utils::test_utils::parse_with_error;
+
+    #[test]
+    /// Test invalid enum declarations
+    fn test_invalid_enums() {
+        let invalid_cases = vec![
+            "enum Status: float { Active = 1.0 }",           // Only int and string backing
+            "enum Status: string { Active }",                // Backed case without value
+            "enum Status: string { Active = 1 }",            // Value of the wrong type
+            "enum Status: int { Active = 1 + 1 }",           // Values must be literals
+            "enum Suit { Hearts = 'h' }",                    // Pure case with value
+            "enum Suit { hearts }",                          // Case names are capitalized
+            "enum Suit { Hearts; name: string; }",           // No properties in enums
+            "enum Suit { Hearts; constructor() { } }",       // No constructors in enums
+            "let enum = 1;",                                 // Reserved word
+        ];
+
+        for code in invalid_cases {
+            assert!(parse_with_error(code).is_err(), "Should fail: {}", code);
+        }
+    }
+}
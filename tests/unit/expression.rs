@@ -0,0 +1,127 @@
+use corrozy_core::language::{AstNode, features::expression::ast::{BinaryOperator, Expression, Literal, UnaryOperator}};
+
+/// Renders the tree with explicit parentheses so the nesting can be compared as a string
+fn shape(expr: &Expression) -> String {
+    match expr {
+        Expression::BinaryOp { left, op, right } => {
+            format!("({} {} {})", shape(left), op.to_php(), shape(right))
+        }
+        Expression::UnaryOp { op, operand } => format!("{}{}", op.to_php(), shape(operand)),
+        Expression::Variable { name, .. } => name.clone(),
+        Expression::Literal(lit) => lit.to_php(),
+        Expression::Parenthesized(inner) => shape(inner),
+        other => panic!("Unexpected expression in shape: {:?}", other),
+    }
+}
+
+fn parse_expression(code: &str) -> Expression {
+    match corrozy_core::utils::test_utils::parse_single_statement(code) {
+        AstNode::ExpressionStatement { expression, .. } => *expression,
+        other => panic!("Expected ExpressionStatement, got: {:?}", other),
+    }
+}
+
+#[cfg(test)]
+mod binary_precedence_tests {
+    use super::*;
+
+    #[test]
+    /// Test multiplication binds tighter than addition and logical or binds loosest
+    fn test_mixed_precedence() {
+        let expr = parse_expression("a + b * c || d;");
+        assert_eq!(shape(&expr), "((a + (b * c)) || d)");
+    }
+
+    #[test]
+    /// Test operators of the same precedence associate to the left
+    fn test_left_associativity() {
+        assert_eq!(shape(&parse_expression("a - b - c;")), "((a - b) - c)");
+        assert_eq!(shape(&parse_expression("a / b * c;")), "((a / b) * c)");
+    }
+
+    #[test]
+    /// Test comparison binds tighter than equality and logical operators
+    fn test_comparison_and_logical() {
+        let expr = parse_expression("a < b == c > d && e || f;");
+        assert_eq!(shape(&expr), "((((a < b) == (c > d)) && e) || f)");
+    }
+
+    #[test]
+    /// Test two-character comparison operators are not split
+    fn test_less_equal_operator() {
+        let expr = parse_expression("a <= b + 1;");
+
+        match expr {
+            Expression::BinaryOp { op, right, .. } => {
+                assert_eq!(op, BinaryOperator::LessEqual);
+                assert!(matches!(*right, Expression::BinaryOp { op: BinaryOperator::Add, .. }));
+            },
+            other => panic!("Expected BinaryOp, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test parentheses override precedence
+    fn test_parenthesized_override() {
+        let expr = parse_expression("(a + b) * c;");
+        assert_eq!(shape(&expr), "((a + b) * c)");
+        assert!(matches!(expr, Expression::BinaryOp { op: BinaryOperator::Multiply, .. }));
+    }
+}
+
+#[cfg(test)]
+mod unary_operator_tests {
+    use corrozy_core::utils::test_utils::{extract_literal_value, parse_single_statement};
+
+    use super::*;
+
+    #[test]
+    /// Test logical not on a variable
+    fn test_logical_not() {
+        match parse_expression("!isActive;") {
+            Expression::UnaryOp { op, operand } => {
+                assert_eq!(op, UnaryOperator::Not);
+                assert!(matches!(*operand, Expression::Variable { ref name, .. } if name == "isActive"));
+            },
+            other => panic!("Expected UnaryOp, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test numeric sign operators on variables
+    fn test_negation_and_plus() {
+        assert_eq!(shape(&parse_expression("-x;")), "-x");
+        assert_eq!(shape(&parse_expression("+y;")), "+y");
+        assert_eq!(shape(&parse_expression("!-x;")), "!-x");
+    }
+
+    #[test]
+    /// Test unary operators bind tighter than binary operators
+    fn test_unary_precedence() {
+        assert_eq!(shape(&parse_expression("-a * b;")), "(-a * b)");
+        assert_eq!(shape(&parse_expression("!a && b;")), "(!a && b)");
+        assert_eq!(shape(&parse_expression("a - -b;")), "(a - -b)");
+    }
+
+    #[test]
+    /// Test negative numeric literals are folded into the literal
+    fn test_negative_literals() {
+        assert!(matches!(parse_expression("-42;"), Expression::Literal(Literal::Integer(-42))));
+        assert!(matches!(parse_expression("-0.5;"), Expression::Literal(Literal::Float(f)) if f == -0.5));
+        assert!(matches!(parse_expression("-9223372036854775808;"), Expression::Literal(Literal::Integer(i64::MIN))));
+        assert_eq!(shape(&parse_expression("- -9223372036854775808;")), "-PHP_INT_MIN");
+    }
+
+    #[test]
+    /// Test negative numeric literal in a constant declaration
+    fn test_negative_constant_declaration() {
+        match parse_single_statement("const MIN: int = -100;") {
+            AstNode::ConstantDeclaration { name, const_type, value, .. } => {
+                assert_eq!(name, "MIN");
+                assert_eq!(const_type.as_ref().unwrap(), "int");
+                assert_eq!(extract_literal_value(&value), "-100");
+            },
+            other => panic!("Expected ConstantDeclaration, got: {:?}", other),
+        }
+    }
+}
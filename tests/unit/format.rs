@@ -0,0 +1,145 @@
+use std::path::{Path, PathBuf};
+
+use corrozy_core::{formatter::{comments, format_source}, utils::test_utils::generate_php_snippet};
+
+fn format(code: &str) -> String {
+    format_source(code).unwrap_or_else(|error| panic!("Failed to format `{}`: {}", code, error))
+}
+
+/// `.crz` files of the example project
+fn example_sources() -> Vec<PathBuf> {
+    fn collect(directory: &Path, files: &mut Vec<PathBuf>) {
+        for entry in std::fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                collect(&path, files);
+            } else if path.extension().is_some_and(|extension| extension == "crz") {
+                files.push(path);
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    collect(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../php-example/src"), &mut files);
+    files
+}
+
+#[cfg(test)]
+mod format_tests {
+    use super::*;
+
+    #[test]
+    /// Test that formatting the example project twice changes nothing the second time
+    fn test_php_example_is_idempotent() {
+        let files = example_sources();
+        assert!(!files.is_empty(), "The example project has no sources");
+
+        for file in files {
+            let source = std::fs::read_to_string(&file).unwrap();
+            let formatted = format(&source);
+
+            assert_eq!(format(&formatted), formatted, "Formatting {} again changed it", file.display());
+            assert_eq!(generate_php_snippet(&formatted), generate_php_snippet(&source), "Formatting {} changed its PHP", file.display());
+
+            let texts = |code: &str| comments::collect(code).iter().map(|comment| comment.text.to_string()).collect::<Vec<_>>();
+            assert_eq!(texts(&formatted), texts(&source), "Formatting {} lost comments", file.display());
+        }
+    }
+
+    #[test]
+    /// Test spacing, indentation and braces of the canonical style
+    fn test_canonical_style() {
+        let code = "let mut total=(a+b)*-c;\nif total>=10{println(total);}else if total==0{print('none');}else{total-=1;}\nfor(let i=0;i<3;i++){total++;}\nwhile(total>0){--total;}\nfn first<T>(items:T[]):T?{return items[0]?.value;}\n";
+
+        assert_eq!(format(code), "\
+let mut total = (a + b) * -c;
+if total >= 10 {
+    println(total);
+} else if total == 0 {
+    print('none');
+} else {
+    total -= 1;
+}
+for (let i = 0; i < 3; i++) {
+    total++;
+}
+while (total > 0) {
+    --total;
+}
+fn first<T>(items: T[]): T? {
+    return items[0]?.value;
+}
+");
+    }
+
+    #[test]
+    /// Test classes, interfaces, traits and enums with one member per line
+    fn test_declarations() {
+        let code = "interface Shape { fn area(): float; }\nabstract class Square extends Base implements Shape { use Greets; private side: float = 1.0; constructor(public name: string) { super(name); } abstract protected fn label(): string; fn area(): float { return this.side * this.side; } }\nenum Status: string { Active = 'active', Inactive = 'inactive'; fn label(): string { return match this { Status::Active => 'On', _ => 'Off' }; } }\nclass Empty {}\n";
+
+        assert_eq!(format(code), "\
+interface Shape {
+    fn area(): float;
+}
+abstract class Square extends Base implements Shape {
+    use Greets;
+    private side: float = 1.0;
+    constructor(public name: string) {
+        super(name);
+    }
+    abstract protected fn label(): string;
+    fn area(): float {
+        return this.side * this.side;
+    }
+}
+enum Status: string {
+    Active = 'active',
+    Inactive = 'inactive',
+    fn label(): string {
+        return match this {
+            Status::Active => 'On',
+            _ => 'Off',
+        };
+    }
+}
+class Empty {}
+");
+    }
+
+    #[test]
+    /// Test that comments keep their line, trailing comments stay on theirs and blank lines are kept once
+    fn test_comments_keep_their_place() {
+        let code = "// Header\n\n\n/* Totals */\nlet a = 1; // one\nfn main() {\n  // nothing yet\n}\nmatch a { _ => 1 } /* done */\n\n// end\n";
+
+        assert_eq!(format(code), "\
+// Header
+
+/* Totals */
+let a = 1; // one
+fn main() {
+    // nothing yet
+}
+match a {
+    _ => 1,
+} /* done */
+
+// end
+");
+    }
+
+    #[test]
+    /// Test that a comment inside an expression stops formatting rather than moving
+    fn test_comment_inside_expression_is_reported() {
+        let error = format_source("let total = add(1, /* first */ 2);\nlet b = 1; // fine\n").unwrap_err();
+        assert_eq!(error.to_string(), "1:20: comment inside an expression can't be kept in place");
+
+        let error = format_source("fn main() {\n    let a = 1 + // one\n        2;\n}\n").unwrap_err();
+        assert_eq!(error.to_string(), "2:17: comment inside an expression can't be kept in place");
+    }
+
+    #[test]
+    /// Test that a file with syntax errors is not formatted
+    fn test_syntax_error_is_reported() {
+        assert!(format_source("let = 1;").is_err());
+    }
+}
@@ -0,0 +1,144 @@
+use corrozy_core::language::{AstNode, features::class::ast::ClassMember};
+
+#[cfg(test)]
+mod generics_parsing_tests {
+    use corrozy_core::utils::test_utils::{parse_single_statement, parse_with_error};
+
+    use super::*;
+
+    #[test]
+    /// Test generic parameters on a function and their use in the signature
+    fn test_generic_function() {
+        let ast = parse_single_statement("fn first<T>(items: T[]): T? { return items[0]; }");
+
+        match ast {
+            AstNode::FunctionDeclaration { type_params, params, return_type, .. } => {
+                assert_eq!(type_params, vec!["T"]);
+                assert_eq!(params[0].param_type.as_deref(), Some("T[]"));
+                assert_eq!(return_type.as_deref(), Some("T?"));
+            },
+            other => panic!("Expected FunctionDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test generic classes, generic methods and nested type applications
+    fn test_generic_class() {
+        let ast = parse_single_statement(r#"
+            class Box<K, V> {
+                items: Map<K, Array<V>>;
+
+                fn map<U>(callback: any): Box<K, U>[] { return callback; }
+            }
+        "#);
+
+        match ast {
+            AstNode::ClassDeclaration { type_params, members, .. } => {
+                assert_eq!(type_params, vec!["K", "V"]);
+                match (&members[0], &members[1]) {
+                    (ClassMember::Property(property), ClassMember::Method(method)) => {
+                        assert_eq!(property.prop_type.as_deref(), Some("Map<K,Array<V>>"));
+                        assert_eq!(method.type_params, vec!["U"]);
+                        assert_eq!(method.return_type.as_deref(), Some("Box<K,U>[]"));
+                    },
+                    other => panic!("Expected property and method, got: {:?}", other),
+                }
+            },
+            other => panic!("Expected ClassDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test invalid generic syntax
+    fn test_invalid_generic_syntax() {
+        let invalid_cases = vec![
+            "fn first<>(items: any) {}",        // Empty parameter list
+            "fn first<t>(items: any) {}",       // Type parameters are capitalised
+            "fn first<T,>(items: any) {}",      // Trailing comma
+            "let x: Array<> = load();",         // Missing type argument
+            "let x: Array<int = load();",       // Unclosed argument list
+        ];
+
+        for code in invalid_cases {
+            assert!(parse_with_error(code).is_err(), "Should fail: {}", code);
+        }
+    }
+}
+
+#[cfg(test)]
+mod generics_generation_tests {
+    use corrozy_core::utils::test_utils::generate_php_snippet;
+
+    #[test]
+    /// Test generic functions always get a docblock with their templates
+    fn test_generic_function_docblock() {
+        let php = generate_php_snippet("fn first<T>(items: T[], fallback: T): T { return fallback; }");
+
+        assert_eq!(php, concat!(
+            "/**\n",
+            " * @template T\n",
+            " * @param array<T> $items\n",
+            " * @param T $fallback\n",
+            " * @return T\n",
+            " */\n",
+            "function first(array $items, mixed $fallback): mixed {\n",
+            "    return $fallback;\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    /// Test generic classes erase their parameters from native types
+    fn test_generic_class_generation() {
+        let php = generate_php_snippet(r#"
+            class Collection<T> {
+                items: T[];
+
+                fn add(item: T, position: int): Collection<T> { return self; }
+
+                fn map<U>(callback: any): Collection<U> { return self; }
+            }
+        "#);
+
+        assert_eq!(php, concat!(
+            "/**\n",
+            " * @template T\n",
+            " */\n",
+            "class Collection {\n",
+            "    /** @var array<T> */\n",
+            "    public array $items;\n",
+            "\n",
+            "    /**\n",
+            "     * @param T $item\n",
+            "     * @return Collection<T>\n",
+            "     */\n",
+            "    public function add(mixed $item, int $position): Collection {\n",
+            "        return $this;\n",
+            "    }\n",
+            "\n",
+            "    /**\n",
+            "     * @template U\n",
+            "     * @return Collection<U>\n",
+            "     */\n",
+            "    public function map(mixed $callback): Collection {\n",
+            "        return $this;\n",
+            "    }\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    /// Test type arguments passed to generic parents are only documented
+    fn test_generic_parent_generation() {
+        let php = generate_php_snippet("class Names extends Collection<string> implements Mapper<string, int>, Countable { }");
+
+        assert_eq!(php, concat!(
+            "/**\n",
+            " * @extends Collection<string>\n",
+            " * @implements Mapper<string, int>\n",
+            " */\n",
+            "class Names extends Collection implements Mapper, Countable {\n",
+            "}\n",
+        ));
+    }
+}
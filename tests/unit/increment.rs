@@ -0,0 +1,89 @@
+use corrozy_core::language::{AstNode, features::{expression::ast::Expression, postfix::ast::UpdateOperator}};
+
+fn parse_expression(code: &str) -> Expression {
+    match corrozy_core::utils::test_utils::parse_single_statement(code) {
+        AstNode::ExpressionStatement { expression, .. } => *expression,
+        other => panic!("Expected ExpressionStatement, got: {:?}", other),
+    }
+}
+
+#[cfg(test)]
+mod increment_parsing_tests {
+    use super::*;
+
+    #[test]
+    /// Test postfix and prefix increment / decrement
+    fn test_update_operators() {
+        let cases = [
+            ("i++;", UpdateOperator::Increment, false),
+            ("i--;", UpdateOperator::Decrement, false),
+            ("++i;", UpdateOperator::Increment, true),
+            ("--i;", UpdateOperator::Decrement, true),
+        ];
+
+        for (code, expected_op, expected_prefix) in cases {
+            match parse_expression(code) {
+                Expression::Update { op, is_prefix, target } => {
+                    assert_eq!(op, expected_op, "Wrong operator for `{}`", code);
+                    assert_eq!(is_prefix, expected_prefix, "Wrong position for `{}`", code);
+                    assert!(matches!(*target, Expression::Variable { ref name, .. } if name == "i"));
+                },
+                other => panic!("Expected Update for `{}`, got: {:?}", code, other),
+            }
+        }
+    }
+
+    #[test]
+    /// Test increment of a property and an index
+    fn test_update_postfix_targets() {
+        assert!(matches!(parse_expression("user.visits++;"),
+            Expression::Update { ref target, .. } if matches!(**target, Expression::PostfixChain { .. })
+        ));
+        assert!(matches!(parse_expression("--counts[0];"),
+            Expression::Update { is_prefix: true, .. }
+        ));
+    }
+
+    #[test]
+    /// Test increments inside binary expressions and double negation
+    fn test_update_in_expressions() {
+        assert!(matches!(parse_expression("i++ + 1;"), Expression::BinaryOp { .. }));
+        assert!(matches!(parse_expression("- -i;"),
+            Expression::UnaryOp { ref operand, .. } if matches!(**operand, Expression::UnaryOp { .. })
+        ));
+    }
+}
+
+#[cfg(test)]
+mod increment_generation_tests {
+    use corrozy_core::utils::test_utils::generate_php_snippet;
+
+    #[test]
+    /// Test increments keep their PHP operator and position
+    fn test_update_generation() {
+        assert_eq!(generate_php_snippet("i++;"), "$i++;");
+        assert_eq!(generate_php_snippet("--i;"), "--$i;");
+        assert_eq!(generate_php_snippet("user.visits++;"), "$user->visits++;");
+        assert_eq!(generate_php_snippet("-(--i);"), "-(--$i);");
+    }
+
+    #[test]
+    /// Test increment in a for loop update clause
+    fn test_for_loop_increment() {
+        let php = generate_php_snippet("for (let i = 0; i < 10; i++) {}");
+        assert_eq!(php, "for ($i = 0; $i < 10; $i++) {\n}\n");
+    }
+}
+
+#[cfg(test)]
+mod increment_syntax_error_tests {
+    use corrozy_core::utils::test_utils::parse_with_error;
+
+    #[test]
+    /// Test that non assignable targets cause parser error
+    fn test_invalid_update_targets() {
+        assert!(parse_with_error("5++;").is_err());
+        assert!(parse_with_error("++getCount();").is_err());
+        assert!(parse_with_error("user.reset()--;").is_err());
+    }
+}
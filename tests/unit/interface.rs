@@ -0,0 +1,63 @@
+use corrozy_core::language::AstNode;
+
+#[cfg(test)]
+mod interface_declaration_tests {
+    use corrozy_core::utils::test_utils::{parse_single_statement, parse_with_error};
+
+    use super::*;
+
+    #[test]
+    /// Test interface with method signatures
+    fn test_interface_declaration() {
+        let ast = parse_single_statement("interface Shape { fn area(): float; fn scale(factor: float, origin); }");
+
+        match ast {
+            AstNode::InterfaceDeclaration { name, methods, .. } => {
+                assert_eq!(name, "Shape");
+                assert_eq!(methods.len(), 2);
+
+                assert_eq!(methods[0].name, "area");
+                assert!(methods[0].params.is_empty());
+                assert_eq!(methods[0].return_type.as_ref().unwrap(), "float");
+
+                assert_eq!(methods[1].name, "scale");
+                assert_eq!(methods[1].params.len(), 2);
+                assert_eq!(methods[1].params[0].param_type.as_ref().unwrap(), "float");
+                assert!(methods[1].params[1].param_type.is_none());
+                assert!(methods[1].return_type.is_none());
+            },
+            other => panic!("Expected InterfaceDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test class implementing several interfaces
+    fn test_class_implements() {
+        let ast = parse_single_statement("class Circle implements Shape, Named { }");
+
+        match ast {
+            AstNode::ClassDeclaration { name, implements, members, .. } => {
+                assert_eq!(name, "Circle");
+                assert_eq!(implements, vec!["Shape", "Named"]);
+                assert!(members.is_empty());
+            },
+            other => panic!("Expected ClassDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Interface methods cannot have a body
+    fn test_interface_method_with_body() {
+        let result = parse_with_error("interface Shape { fn area(): float { return 1.0; } }");
+
+        assert!(result.is_err(), "Interface methods must be signatures only");
+    }
+
+    #[test]
+    /// Test implements without interface names
+    fn test_empty_implements() {
+        let result = parse_with_error("class Circle implements { }");
+
+        assert!(result.is_err(), "Implements needs at least one interface");
+    }
+}
@@ -0,0 +1,122 @@
+use corrozy_core::language::AstNode;
+
+#[cfg(test)]
+mod while_loop_tests {
+    use corrozy_core::language::features::expression::ast::{BinaryOperator, Expression};
+    use corrozy_core::utils::test_utils::parse_single_statement;
+
+    use super::*;
+
+    #[test]
+    /// Test while loop with condition and body
+    fn test_while_loop() {
+        let ast = parse_single_statement("while (count < 10) { println(count); }");
+
+        match ast {
+            AstNode::WhileLoop { condition, body, .. } => {
+                assert!(matches!(*condition, Expression::BinaryOp { op: BinaryOperator::Less, .. }));
+                assert_eq!(body.statements.len(), 1);
+                assert!(matches!(body.statements[0], AstNode::PrintStatement { .. }));
+            },
+            other => panic!("Expected WhileLoop, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test while loop with an empty body
+    fn test_while_loop_empty_body() {
+        let ast = parse_single_statement("while (running) {}");
+
+        match ast {
+            AstNode::WhileLoop { condition, body, .. } => {
+                assert!(matches!(*condition, Expression::Variable { ref name, .. } if name == "running"));
+                assert!(body.statements.is_empty());
+                assert!(body.return_statement.is_none());
+            },
+            other => panic!("Expected WhileLoop, got: {:?}", other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod for_loop_tests {
+    use corrozy_core::language::features::{expression::ast::{BinaryOperator, Expression}, loop_for::ast::ForInit};
+    use corrozy_core::utils::test_utils::{extract_literal_value, parse_single_statement};
+
+    use super::*;
+
+    #[test]
+    /// Test for loop with declaration, condition and update clauses
+    fn test_for_loop_all_clauses() {
+        let ast = parse_single_statement("for (let i: int = 0; i < 10; next(i)) { println(i); }");
+
+        match ast {
+            AstNode::ForLoop { init, condition, update, body, .. } => {
+                match init.as_deref() {
+                    Some(ForInit::VariableDeclaration(decl)) => match decl.as_ref() {
+                        AstNode::VariableDeclaration { name, var_type, value, .. } => {
+                            assert_eq!(name, "i");
+                            assert_eq!(var_type.as_ref().unwrap(), "int");
+                            assert_eq!(extract_literal_value(value), "0");
+                        },
+                        other => panic!("Expected VariableDeclaration, got: {:?}", other),
+                    },
+                    other => panic!("Expected declaration init, got: {:?}", other),
+                }
+                assert!(matches!(condition.as_deref(), Some(Expression::BinaryOp { op: BinaryOperator::Less, .. })));
+                assert!(matches!(update.as_deref(), Some(Expression::FunctionCall(call)) if call.name == "next"));
+                assert_eq!(body.statements.len(), 1);
+            },
+            other => panic!("Expected ForLoop, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test for loop with an expression as init clause
+    fn test_for_loop_expression_init() {
+        let ast = parse_single_statement("for (reset(); valid(); next()) {}");
+
+        match ast {
+            AstNode::ForLoop { init, condition, update, .. } => {
+                assert!(matches!(init.as_deref(), Some(ForInit::Expression(_))));
+                assert!(condition.is_some());
+                assert!(update.is_some());
+            },
+            other => panic!("Expected ForLoop, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test for loop with every clause omitted
+    fn test_for_loop_empty_clauses() {
+        let ast = parse_single_statement("for (;;) {}");
+
+        match ast {
+            AstNode::ForLoop { init, condition, update, .. } => {
+                assert!(init.is_none());
+                assert!(condition.is_none());
+                assert!(update.is_none());
+            },
+            other => panic!("Expected ForLoop, got: {:?}", other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod loop_syntax_error_tests {
+    use corrozy_core::utils::test_utils::parse_with_error;
+
+    #[test]
+    /// Test that loops without parentheses or block cause parser error
+    fn test_loop_missing_parts() {
+        assert!(parse_with_error("while x {}").is_err());
+        assert!(parse_with_error("for (;;);").is_err());
+    }
+
+    #[test]
+    /// Test that malformed for headers cause parser error
+    fn test_invalid_for_header() {
+        assert!(parse_with_error("for (let i = 0; i < 10) {}").is_err());
+        assert!(parse_with_error("for (let i = 0) {}").is_err());
+    }
+}
@@ -1,3 +1,20 @@
 pub mod variable;
 pub mod function;
-pub mod prints;
\ No newline at end of file
+pub mod prints;
+pub mod loops;
+pub mod expression;
+pub mod assignment;
+pub mod increment;
+pub mod class;
+pub mod interface;
+pub mod traits;
+pub mod enums;
+pub mod pattern_match;
+pub mod nullable;
+pub mod types;
+pub mod generics;
+pub mod typeck;
+pub mod resolver;
+pub mod span;
+pub mod recovery;
+pub mod format;
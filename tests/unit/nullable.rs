@@ -0,0 +1,77 @@
+use corrozy_core::language::{AstNode, features::{expression::ast::{BinaryOperator, Expression, Literal}, postfix::ast::PostfixSuffix}};
+
+#[cfg(test)]
+mod nullable_tests {
+    use corrozy_core::utils::test_utils::{parse_single_statement, parse_with_error};
+
+    use super::*;
+
+    #[test]
+    /// Test nullable type annotation with a null literal
+    fn test_nullable_declaration() {
+        let ast = parse_single_statement("let name: string? = null;");
+
+        match ast {
+            AstNode::VariableDeclaration { var_type, value, .. } => {
+                assert_eq!(var_type.as_deref(), Some("string?"));
+                assert!(matches!(*value, Expression::Literal(Literal::Null)));
+            },
+            other => panic!("Expected VariableDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test nullable custom types in function signatures
+    fn test_nullable_function_signature() {
+        let ast = parse_single_statement("fn find(id: int?): User? { return null; }");
+
+        match ast {
+            AstNode::FunctionDeclaration { params, return_type, .. } => {
+                assert_eq!(params[0].param_type.as_deref(), Some("int?"));
+                assert_eq!(return_type.as_deref(), Some("User?"));
+            },
+            other => panic!("Expected FunctionDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test optional chaining on properties and method calls, wrapped in `??`
+    fn test_optional_chaining_with_coalesce() {
+        let ast = parse_single_statement("let city = user?.address.city()?.name ?? 'none';");
+
+        match ast {
+            AstNode::VariableDeclaration { value, .. } => match *value {
+                Expression::BinaryOp { left, op, right } => {
+                    assert_eq!(op, BinaryOperator::Coalesce);
+                    assert!(matches!(*right, Expression::Literal(Literal::String(_))));
+                    match *left {
+                        Expression::PostfixChain { suffixes, .. } => {
+                            assert!(matches!(&suffixes[0], PostfixSuffix::NullsafeProperty(name) if name == "address"));
+                            assert!(matches!(&suffixes[1], PostfixSuffix::MethodCall(call) if call.name == "city"));
+                            assert!(matches!(&suffixes[2], PostfixSuffix::NullsafeProperty(name) if name == "name"));
+                        },
+                        other => panic!("Expected PostfixChain, got: {:?}", other),
+                    }
+                },
+                other => panic!("Expected BinaryOp, got: {:?}", other),
+            },
+            other => panic!("Expected VariableDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test invalid nullable syntax
+    fn test_invalid_nullable_syntax() {
+        let invalid_cases = vec![
+            "let x: ?string = null;",         // Marker goes after the type
+            "let x: string?? = null;",        // Single marker only
+            "let null = 1;",                  // Reserved word
+            "let x = user?.;",                // Missing member after `?.`
+            "let x = a ?? ;",                 // Missing right side
+        ];
+
+        for code in invalid_cases {
+            assert!(parse_with_error(code).is_err(), "Should fail: {}", code);
+        }
+    }
+}
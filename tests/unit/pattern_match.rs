@@ -0,0 +1,65 @@
+use corrozy_core::language::{AstNode, features::{expression::ast::Expression, pattern_match::ast::{MatchArmBody, MatchPattern}}};
+
+#[cfg(test)]
+mod match_tests {
+    use corrozy_core::utils::test_utils::{parse_single_statement, parse_with_error};
+
+    use super::*;
+
+    #[test]
+    /// Test match expression with alternatives and a wildcard
+    fn test_match_expression() {
+        let ast = parse_single_statement(r#"let label = match code { 1 | 2 => "low", "x" => "unknown", _ => "high", };"#);
+
+        match ast {
+            AstNode::VariableDeclaration { value, .. } => match *value {
+                Expression::Match { subject, arms } => {
+                    assert!(matches!(*subject, Expression::Variable { ref name, .. } if name == "code"));
+                    assert_eq!(arms.len(), 3);
+                    assert_eq!(arms[0].patterns.len(), 2);
+                    assert!(!arms[0].is_wildcard());
+                    assert!(matches!(arms[2].patterns[0], MatchPattern::Wildcard));
+                    assert!(arms.iter().all(|arm| matches!(arm.body, MatchArmBody::Expression(_))));
+                },
+                other => panic!("Expected Match, got: {:?}", other),
+            },
+            other => panic!("Expected VariableDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test match statement with block arms, no semicolon needed
+    fn test_match_statement() {
+        let ast = parse_with_error("match status { Status::Active => { go(); } Status::Banned => stop(), } let x = 1;").unwrap();
+
+        assert_eq!(ast.len(), 2);
+        match &ast[0] {
+            AstNode::ExpressionStatement { expression, .. } => match expression.as_ref() {
+                Expression::Match { arms, .. } => {
+                    assert!(matches!(arms[0].body, MatchArmBody::Block(_)));
+                    assert!(matches!(&arms[0].patterns[0], MatchPattern::Value(Expression::StaticConstant { .. })));
+                    assert!(matches!(arms[1].body, MatchArmBody::Expression(_)));
+                },
+                other => panic!("Expected Match, got: {:?}", other),
+            },
+            other => panic!("Expected ExpressionStatement, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test invalid match syntax
+    fn test_invalid_match_syntax() {
+        let invalid_cases = vec![
+            "match { 1 => 2 }",               // Missing subject
+            "match x { 1 => 2 3 => 4 }",      // Expression arms need commas
+            "match x { 1 2 => 3 }",           // Patterns need `|`
+            "match x { => 3 }",               // Missing pattern
+            "match x { 1 => }",               // Missing body
+            "let match = 1;",                 // Reserved word
+        ];
+
+        for code in invalid_cases {
+            assert!(parse_with_error(code).is_err(), "Should fail: {}", code);
+        }
+    }
+}
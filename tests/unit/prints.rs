@@ -10,7 +10,7 @@ mod prints_declaration_tests {
         let ast = parse_single_statement("println(\"Diego\");");
         
         match ast {
-            AstNode::PrintStatement { expression, newline } => {
+            AstNode::PrintStatement { expression, newline, .. } => {
                 assert_eq!(extract_literal_value(&expression), "\"Diego\"");
                 assert!(newline);
             }
@@ -23,7 +23,7 @@ mod prints_declaration_tests {
         let ast = parse_single_statement("print('Hello, World!');");
         
         match ast {
-            AstNode::PrintStatement { expression, newline } => {
+            AstNode::PrintStatement { expression, newline, .. } => {
                 assert_eq!(extract_literal_value(&expression), "'Hello, World!'");
                 assert!(!newline);
             }
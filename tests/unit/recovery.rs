@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod recovery_tests {
+    use corrozy_core::{language::{AstNode, parser::CorrozyParserImpl}, utils::test_utils::parse_with_error};
+
+    #[test]
+    /// Test every broken statement of a file is reported in one run
+    fn test_reports_every_syntax_error() {
+        let code = "let a = ;\nlet b = 2;\nprint(b +);\nfn f() {\n    return 1\n}\nlet c = 3;";
+        let error = parse_with_error(code).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "1:9: expected an expression after `=`\n\
+             3:10: expected an expression after `+`\n\
+             5:13: expected `;` after `1`"
+        );
+    }
+
+    #[test]
+    /// Test the statements around the errors are still parsed
+    fn test_partial_ast() {
+        let code = "let a = ;\nlet b = 2;\nclass User {\n    fn name(): string { return 'x' }\n}\nlet c = 3;";
+        let (ast, diagnostics) = CorrozyParserImpl::new().parse_recovering(code);
+
+        assert_eq!(diagnostics.len(), 2);
+        let names: Vec<&str> = ast.iter()
+            .map(|node| match node {
+                AstNode::VariableDeclaration { name, .. } => name.as_str(),
+                AstNode::ClassDeclaration { name, .. } => name.as_str(),
+                other => panic!("Unexpected node: {:?}", other),
+            })
+            .collect();
+        assert_eq!(names, ["b", "User", "c"]);
+    }
+
+    #[test]
+    /// Test a block that is never closed stops the recovery
+    fn test_unclosed_block() {
+        let (ast, diagnostics) = CorrozyParserImpl::new().parse_recovering("let a = 1;\nfn f() {\n    print(a);\n");
+
+        assert!(ast.is_empty());
+        assert_eq!(diagnostics.len(), 1);
+    }
+}
@@ -0,0 +1,60 @@
+use corrozy_core::semantic::{Resolver, resolver::{ScopeKind, SymbolKind}};
+
+#[cfg(test)]
+mod resolver_tests {
+    use corrozy_core::utils::test_utils::parse_with_error;
+
+    use super::*;
+
+    #[test]
+    /// Test scopes are created for the program, functions, blocks and nested functions
+    fn test_scope_tree() {
+        let ast = parse_with_error(r#"
+            const RATE = 2;
+            fn scale(value: int): int {
+                if (value > 0) {
+                    let scaled = value * RATE;
+                    fn report() { print(scaled); }
+                }
+                return value;
+            }
+        "#).unwrap();
+        let mut resolver = Resolver::new();
+        resolver.resolve(&ast).unwrap();
+        let table = resolver.symbols();
+
+        let kinds: Vec<ScopeKind> = table.scopes().iter().map(|scope| scope.kind).collect();
+        assert_eq!(kinds, [ScopeKind::Program, ScopeKind::Function, ScopeKind::Block, ScopeKind::Closure]);
+
+        let resolved: Vec<(&str, SymbolKind)> = table.references().iter()
+            .map(|reference| {
+                let symbol = table.symbol(reference.symbol.expect("Every variable is declared"));
+                (reference.name.as_str(), symbol.kind)
+            })
+            .collect();
+        assert_eq!(resolved, [
+            ("value", SymbolKind::Parameter),
+            ("value", SymbolKind::Parameter),
+            ("RATE", SymbolKind::Constant),
+            ("scaled", SymbolKind::Variable),
+            ("value", SymbolKind::Parameter),
+        ]);
+    }
+
+    #[test]
+    /// Test undefined variables are reported outside closures too
+    fn test_undefined_variables_everywhere() {
+        let invalid_cases = vec![
+            ("let total = count + 1;", "1:13: Undefined variable `count`"),
+            ("while (running) {}", "1:8: Undefined variable `running`"),
+            ("class User { fn name(): string { return name; } }", "1:41: Undefined variable `name`"),
+            ("let limit = 3; fn over(n: int): bool { return n > limit; }", "1:51: Undefined variable `limit`, functions can't read variables declared outside of them"),
+        ];
+
+        for (code, expected) in invalid_cases {
+            let ast = parse_with_error(code).unwrap();
+            let error = Resolver::new().resolve(&ast).expect_err(code);
+            assert_eq!(error.to_string(), expected, "{}", code);
+        }
+    }
+}
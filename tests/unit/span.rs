@@ -0,0 +1,62 @@
+use corrozy_core::{diagnostics::{Diagnostic, Diagnostics, codes}, language::{AstNode, Span}};
+
+#[cfg(test)]
+mod span_tests {
+    use corrozy_core::{semantic::SemanticAnalyzer, utils::test_utils::parse_with_error};
+
+    use super::*;
+
+    #[test]
+    /// Test every statement keeps the line and column where it starts
+    fn test_statement_spans() {
+        let code = "let x = 1;\n\nfn double(n: int): int {\n    println(n);\n    return n * 2;\n}\n";
+        let ast = parse_with_error(code).unwrap();
+
+        assert_eq!(ast[0].span(), Span { start: 0, end: 10, line: 1, column: 1 });
+        assert_eq!(ast[1].span().to_string(), "3:1");
+
+        match &ast[1] {
+            AstNode::FunctionDeclaration { body, .. } => {
+                assert_eq!(body.statements[0].span().to_string(), "4:5");
+                assert_eq!(body.return_statement.as_ref().unwrap().span.to_string(), "5:5");
+            }
+            other => panic!("Expected FunctionDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test syntax errors point to where the parser stopped
+    fn test_parse_error_location() {
+        let error = parse_with_error("let a = 1;\nlet b = ;").unwrap_err();
+        assert_eq!(error.to_string(), "2:9: expected an expression after `=`");
+
+        let diagnostic = &Diagnostics::from_error(error)[0];
+        assert_eq!(diagnostic.code, Some(codes::SYNTAX));
+        assert_eq!(diagnostic.span().map(|span| (span.line, span.column)), Some((2, 9)));
+    }
+
+    #[test]
+    /// Test semantic errors point to the innermost statement
+    fn test_semantic_error_location() {
+        let ast = parse_with_error("fn f() {\n    if (true) {\n        return this.name;\n    }\n}").unwrap();
+        let error = SemanticAnalyzer::new().analyze(&ast).unwrap_err();
+
+        assert_eq!(error.to_string(), "3:9: `this` can only be used inside class methods");
+    }
+
+    #[test]
+    /// Test a duplicate enum case also points to the first declaration
+    fn test_secondary_label() {
+        let ast = parse_with_error("enum Status {\n    Active,\n    Active\n}").unwrap();
+        let error = SemanticAnalyzer::new().analyze(&ast).unwrap_err();
+        let diagnostic = error.downcast_ref::<Diagnostic>().expect("Expected a diagnostic");
+
+        let labels: Vec<(String, bool, &str)> = diagnostic.labels.iter()
+            .map(|label| (label.span.to_string(), label.primary, label.message.as_str()))
+            .collect();
+        assert_eq!(labels, [
+            ("3:5".to_string(), true, "declared again here"),
+            ("2:5".to_string(), false, "first declared here"),
+        ]);
+    }
+}
@@ -0,0 +1,58 @@
+use corrozy_core::language::{AstNode, features::class::ast::ClassMember};
+
+#[cfg(test)]
+mod trait_declaration_tests {
+    use corrozy_core::utils::test_utils::{parse_single_statement, parse_with_error};
+
+    use super::*;
+
+    #[test]
+    /// Test trait with properties, methods and nested trait use
+    fn test_trait_declaration() {
+        let ast = parse_single_statement("trait HasTimestamps { use Clock; createdAt: int = 0; fn touch() { } }");
+
+        match ast {
+            AstNode::TraitDeclaration { name, members, .. } => {
+                assert_eq!(name, "HasTimestamps");
+                assert_eq!(members.len(), 3);
+                assert!(matches!(&members[0], ClassMember::TraitUse(traits) if traits == &vec!["Clock"]));
+                assert!(matches!(&members[1], ClassMember::Property(_)));
+                assert!(matches!(&members[2], ClassMember::Method(_)));
+            },
+            other => panic!("Expected TraitDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test class using several traits
+    fn test_class_trait_use() {
+        let ast = parse_single_statement("class Post { use HasTimestamps, SoftDeletes; }");
+
+        match ast {
+            AstNode::ClassDeclaration { members, .. } => {
+                match &members[0] {
+                    ClassMember::TraitUse(traits) => assert_eq!(traits, &vec!["HasTimestamps", "SoftDeletes"]),
+                    other => panic!("Expected TraitUse, got: {:?}", other),
+                }
+            },
+            other => panic!("Expected ClassDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test invalid trait syntax
+    fn test_invalid_trait_syntax() {
+        let invalid_cases = vec![
+            "trait { }",                        // Missing name
+            "trait lowercase { }",              // Trait names are custom types
+            "class Post { use; }",              // Missing trait name
+            "class Post { use HasTimestamps }", // Missing semicolon
+            "use HasTimestamps;",               // Trait use outside of a class
+            "let use = 1;",                     // Reserved word
+        ];
+
+        for code in invalid_cases {
+            assert!(parse_with_error(code).is_err(), "Should fail: {}", code);
+        }
+    }
+}
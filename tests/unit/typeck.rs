@@ -0,0 +1,106 @@
+use std::rc::Rc;
+
+use corrozy_core::{codegen::CodeGenerator, semantic::{Resolver, SemanticAnalyzer}, typeck::TypeChecker};
+
+#[cfg(test)]
+mod type_checker_tests {
+    use corrozy_core::utils::test_utils::{default_corrozy_config, parse_with_error};
+
+    use super::*;
+
+    fn check(code: &str) -> anyhow::Result<()> {
+        let mut ast = parse_with_error(code)?;
+        TypeChecker::new().check(&mut ast)
+    }
+
+    #[test]
+    /// Test well typed code passes the checker
+    fn test_valid_program() {
+        let code = r#"
+            fn greet(name: string, times: int): string {
+                let greeting: string = "Hello " . name;
+                return greeting;
+            }
+
+            class Counter {
+                private total: int = 0;
+
+                fn add(amount: int): int {
+                    this.total += amount;
+                    return this.total;
+                }
+            }
+
+            let counter = new Counter();
+            let total: float = counter.add(2) * 1.5;
+            print(greet("Ana", 2));
+        "#;
+
+        assert!(check(code).is_ok(), "{:?}", check(code));
+    }
+
+    #[test]
+    /// Test every type error of a file is reported together
+    fn test_errors_are_collected() {
+        let code = r#"
+            fn square(value: int): int { return value * value; }
+            let result: string = square(true);
+        "#;
+
+        let error = check(code).expect_err("Expected type errors").to_string();
+        assert_eq!(error, concat!(
+            "3:34: Argument 1 of `square` expects `int`, found `bool`\n",
+            "3:13: Cannot assign `int` to `result` of type `string`",
+        ));
+    }
+
+    #[test]
+    /// Test inferred declaration types are documented with `@var`
+    fn test_inferred_var_annotations() {
+        let mut config = default_corrozy_config();
+        config.transpiler.include_comments = true;
+
+        let mut ast = parse_with_error(r#"
+            fn average(total: int, count: int): float { return total / count * 1.5; }
+            const LIMIT = 10;
+            let mean = average(12, 4);
+            let label = "mean";
+            let result = load();
+            let fallback = null ?? (null ?? 3);
+            let scale = 2.0;
+            let half = 10;
+            half = half / 2;
+        "#).unwrap();
+        TypeChecker::new().check(&mut ast).unwrap();
+        let php = CodeGenerator::new(Rc::new(config)).generate(&ast).unwrap();
+
+        assert!(php.contains("/** @var int */\nconst LIMIT = 10;\n"), "{}", php);
+        assert!(php.contains("/** @var float $mean */\n$mean = average(12, 4);\n"), "{}", php);
+        assert!(php.contains("/** @var string $label */\n$label = \"mean\";\n"), "{}", php);
+        assert!(php.contains("/** @var int $fallback */\n"), "{}", php);
+        assert!(php.contains("\n$result = load();\n") && !php.contains("$result */"), "{}", php);
+        assert!(php.contains("/** @var float $scale */\n$scale = 2.0;\n"), "{}", php);
+        assert!(php.contains("/** @var int|float $half */\n$half = 10;\n"), "{}", php);
+    }
+
+    #[test]
+    /// Test constants are generated without `$` wherever they are read
+    fn test_constant_references_generation() {
+        let mut ast = parse_with_error(r#"
+            const MAX = 10;
+            println(MAX);
+            fn limit(): int { fn inner(): int { return MAX; } return inner(); }
+            fn shadow(MAX: int): int { return MAX; }
+            class Box { size: int = MAX; }
+        "#).unwrap();
+        Resolver::new().resolve(&ast).unwrap();
+        SemanticAnalyzer::new().analyze(&ast).unwrap();
+        TypeChecker::new().check(&mut ast).unwrap();
+        let php = CodeGenerator::new(Rc::new(default_corrozy_config())).generate(&ast).unwrap();
+
+        assert!(php.contains("const MAX = 10;\necho MAX . \"\\n\";\n"), "{}", php);
+        assert!(php.contains("    $inner = function(): int {\n        return MAX;\n    };\n"), "{}", php);
+        assert!(php.contains("function shadow(int $MAX): int {\n    return $MAX;\n}\n"), "{}", php);
+        assert!(php.contains("public int $size = MAX;\n"), "{}", php);
+    }
+}
@@ -0,0 +1,67 @@
+use corrozy_core::language::AstNode;
+
+#[cfg(test)]
+mod type_annotation_tests {
+    use corrozy_core::utils::test_utils::{parse_single_statement, parse_with_error};
+
+    use super::*;
+
+    fn declared_type(code: &str) -> String {
+        match parse_single_statement(code) {
+            AstNode::VariableDeclaration { var_type, .. } => var_type.expect("Expected a type annotation"),
+            other => panic!("Expected VariableDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test union types are stored without spaces
+    fn test_union_type() {
+        assert_eq!(declared_type("let amount: int | float = 1;"), "int|float");
+        assert_eq!(declared_type("let id: int|string? = null;"), "int|string?");
+    }
+
+    #[test]
+    /// Test intersection of class types
+    fn test_intersection_type() {
+        assert_eq!(declared_type("let items: Countable & Traversable = load();"), "Countable&Traversable");
+    }
+
+    #[test]
+    /// Test array element types, nested and nullable
+    fn test_array_type() {
+        assert_eq!(declared_type("let ids: int[] = load();"), "int[]");
+        assert_eq!(declared_type("let grid: User[][]? = null;"), "User[][]?");
+        assert_eq!(declared_type("let mixed: int[] | string = load();"), "int[]|string");
+    }
+
+    #[test]
+    /// Test composite types in function signatures
+    fn test_function_signature_types() {
+        let ast = parse_single_statement("fn sum(values: float[], scale: int | float): int | float { return 0; }");
+
+        match ast {
+            AstNode::FunctionDeclaration { params, return_type, .. } => {
+                assert_eq!(params[0].param_type.as_deref(), Some("float[]"));
+                assert_eq!(params[1].param_type.as_deref(), Some("int|float"));
+                assert_eq!(return_type.as_deref(), Some("int|float"));
+            },
+            other => panic!("Expected FunctionDeclaration, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    /// Test invalid composite type syntax
+    fn test_invalid_type_syntax() {
+        let invalid_cases = vec![
+            "let x: int | = 1;",              // Missing union member
+            "let x: int & float = 1;",        // Intersections only take class types
+            "let x: int [] = load();",        // No space before `[]`
+            "let x: [int] = load();",         // Element type goes first
+            "let x: int | | float = 1;",      // Empty member
+        ];
+
+        for code in invalid_cases {
+            assert!(parse_with_error(code).is_err(), "Should fail: {}", code);
+        }
+    }
+}
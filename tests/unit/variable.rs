@@ -12,7 +12,7 @@ mod variable_declaration_tests {
         let ast = parse_single_statement("let x: int = 1;");
         
         match ast {
-            AstNode::VariableDeclaration { name, var_type, value } => {
+            AstNode::VariableDeclaration { name, var_type, value, .. } => {
                 assert_eq!(name, "x");
                 assert_eq!(var_type.as_ref().unwrap(), "int");
                 assert_eq!(extract_literal_value(&value), "1");
@@ -27,7 +27,7 @@ mod variable_declaration_tests {
         let ast = parse_single_statement("let nombre = \"Diego\";");
         
         match ast {
-            AstNode::VariableDeclaration { name, var_type, value } => {
+            AstNode::VariableDeclaration { name, var_type, value, .. } => {
                 assert_eq!(name, "nombre");
                 assert!(var_type.is_none());
                 assert_eq!(extract_literal_value(&value), "\"Diego\"");
@@ -42,7 +42,7 @@ mod variable_declaration_tests {
         let ast = parse_single_statement("let nombre = 'Diego';");
         
         match ast {
-            AstNode::VariableDeclaration { name, var_type, value } => {
+            AstNode::VariableDeclaration { name, var_type, value, .. } => {
                 assert_eq!(name, "nombre");
                 assert!(var_type.is_none());
                 assert_eq!(extract_literal_value(&value), "'Diego'");
@@ -57,7 +57,7 @@ mod variable_declaration_tests {
         let ast = parse_single_statement("let _variable_name = 42;");
         
         match ast {
-            AstNode::VariableDeclaration { name, var_type, value } => {
+            AstNode::VariableDeclaration { name, var_type, value, .. } => {
                 assert_eq!(name, "_variable_name");
                 assert!(var_type.is_none());
                 assert_eq!(extract_literal_value(&value), "42");
@@ -72,7 +72,7 @@ mod variable_declaration_tests {
         let ast = parse_single_statement("let empty = \"\";");
         
         match ast {
-            AstNode::VariableDeclaration { name, var_type, value } => {
+            AstNode::VariableDeclaration { name, var_type, value, .. } => {
                 assert_eq!(name, "empty");
                 assert!(var_type.is_none());
                 assert_eq!(extract_literal_value(&value), "\"\"");
@@ -80,6 +80,29 @@ mod variable_declaration_tests {
             other => panic!("Expected VariableDeclaration, got: {:?}", other),
         }
     }
+
+    #[test]
+    /// Test mutable variable declaration with `let mut`
+    fn test_mutable_variable_declaration() {
+        let ast = parse_single_statement("let mut counter: int = 0;");
+
+        match ast {
+            AstNode::VariableDeclaration { name, var_type, mutable, .. } => {
+                assert_eq!(name, "counter");
+                assert_eq!(var_type.as_deref(), Some("int"));
+                assert!(mutable);
+            },
+            other => panic!("Expected VariableDeclaration, got: {:?}", other),
+        }
+
+        match parse_single_statement("let mutable = 1;") {
+            AstNode::VariableDeclaration { name, mutable, .. } => {
+                assert_eq!(name, "mutable");
+                assert!(!mutable);
+            },
+            other => panic!("Expected VariableDeclaration, got: {:?}", other),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -94,7 +117,7 @@ mod constant_declaration_tests {
         let ast = parse_single_statement("const PI: float = 3.14;");
         
         match ast {
-            AstNode::ConstantDeclaration { name, const_type, value } => {
+            AstNode::ConstantDeclaration { name, const_type, value, .. } => {
                 assert_eq!(name, "PI");
                 assert_eq!(const_type.as_ref().unwrap(), "float");
                 assert_eq!(extract_literal_value(&value), "3.14");
@@ -109,7 +132,7 @@ mod constant_declaration_tests {
         let ast = parse_single_statement("const MAX_SIZE = 100;");
         
         match ast {
-            AstNode::ConstantDeclaration { name, const_type, value } => {
+            AstNode::ConstantDeclaration { name, const_type, value, .. } => {
                 assert_eq!(name, "MAX_SIZE");
                 assert!(const_type.is_none());
                 assert_eq!(extract_literal_value(&value), "100");
@@ -124,7 +147,7 @@ mod constant_declaration_tests {
         let ast = parse_single_statement("const BIG_NUMBER = 9999999999;");
         
         match ast {
-            AstNode::ConstantDeclaration { name, const_type, value } => {
+            AstNode::ConstantDeclaration { name, const_type, value, .. } => {
                 assert_eq!(name, "BIG_NUMBER");
                 assert!(const_type.is_none());
                 assert_eq!(extract_literal_value(&value), "9999999999");
@@ -259,6 +282,12 @@ mod syntax_error_tests {
         
         let result = parse_with_error("let var-name = 5;");
         assert!(result.is_err());
+
+        let result = parse_with_error("let mut = 5;");
+        assert!(result.is_err());
+
+        let result = parse_with_error("const mut X = 5;");
+        assert!(result.is_err());
     }
 
     #[test]